@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use pactus::genesis::{Genesis, GENESIS_FILE_NAME};
+use pactus::signer::create_local_signer;
+use pactus::signer::protocol::{generate_auth_keypair, Endpoint};
+use pactus::signer::server::SignerServer;
+use pactus::slashing_protection::create_slashing_protection;
+use pactus_types::crypto::secret_key::SecretKey;
+use pactus_types::crypto::signer::Signer;
+use pactus_types::crypto::KeyPairType;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+const VALIDATOR_KEY_FILE_NAME: &str = "validator_key";
+const AUTH_KEY_FILE_NAME: &str = "auth_key";
+const AUTHORIZED_NODES_FILE_NAME: &str = "authorized_nodes";
+const SLASHING_PROTECTION_FILE_NAME: &str = "slashing_protection.json";
+
+lazy_static! {
+    static ref DEFAULT_WORKING_DIR: String = format!(
+        "{}/pactus-signer",
+        env::var("HOME").as_deref().unwrap_or(".")
+    );
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "pactus-signer",
+    about = "Signs the consensus messages for the pactus nodes"
+)]
+enum Command {
+    /// Generates the auth key of the signer.
+    /// The validator key and the genesis file of the network should be copied into the working directory.
+    #[structopt(name = "init")]
+    Init {
+        #[structopt(short = "w", default_value = &DEFAULT_WORKING_DIR)]
+        working_dir: PathBuf,
+        /// Overwrites the auth key of an initialized working directory
+        #[structopt(long)]
+        force: bool,
+    },
+
+    /// Serves the signing requests of the authorized nodes.
+    #[structopt(name = "start")]
+    Start {
+        #[structopt(short = "w", default_value = &DEFAULT_WORKING_DIR)]
+        working_dir: PathBuf,
+        /// `tcp://host:port` or `unix:///path/to/socket`
+        #[structopt(short, long, default_value = "tcp://127.0.0.1:7333")]
+        listen: String,
+    },
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    let result = match Command::from_args() {
+        Command::Init { working_dir, force } => init(&working_dir, force),
+        Command::Start {
+            working_dir,
+            listen,
+        } => start(&working_dir, &listen),
+    };
+    println!("{:?}", result);
+}
+
+fn init(dir: &Path, force: bool) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let auth_key_file = dir.join(AUTH_KEY_FILE_NAME);
+    if auth_key_file.exists() && !force {
+        return Err(anyhow!(
+            "{} already exists, use --force to overwrite it",
+            auth_key_file.display()
+        ));
+    }
+    let auth_key = generate_auth_keypair()?;
+    fs::write(auth_key_file, hex::encode(auth_key.secret))?;
+    // Nodes are added by their auth public key, one per line
+    let authorized_nodes = dir.join(AUTHORIZED_NODES_FILE_NAME);
+    if !authorized_nodes.exists() {
+        fs::write(authorized_nodes, "")?;
+    }
+    println!("signer auth public key: {}", hex::encode(auth_key.public));
+    Ok(())
+}
+
+fn start(dir: &Path, listen: &str) -> Result<()> {
+    let hex = fs::read_to_string(dir.join(VALIDATOR_KEY_FILE_NAME))?;
+    let validator_key = SecretKey::from_string(KeyPairType::KeyPairBLS, hex.trim())?;
+    let auth_key = hex::decode(fs::read_to_string(dir.join(AUTH_KEY_FILE_NAME))?.trim())?;
+    let authorized_keys = fs::read_to_string(dir.join(AUTHORIZED_NODES_FILE_NAME))?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(hex::decode)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if authorized_keys.is_empty() {
+        return Err(anyhow!("no authorized nodes"));
+    }
+
+    let genesis_hash = Genesis::load(&dir.join(GENESIS_FILE_NAME))?.hash()?;
+    let slashing_protection =
+        create_slashing_protection(genesis_hash, Some(dir.join(SLASHING_PROTECTION_FILE_NAME)))?;
+    let signer = create_local_signer(Signer::new(validator_key), slashing_protection);
+
+    SignerServer::new(signer, auth_key, authorized_keys).serve(&Endpoint::parse(listen))?;
+    Ok(())
+}
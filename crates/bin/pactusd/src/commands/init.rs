@@ -1,13 +1,18 @@
 use crate::file::{save_text_file, save_toml_file};
-use anyhow::Result;
+use crate::keystore::Keystore;
+use anyhow::{anyhow, Result};
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use structopt::StructOpt;
 use pactus::config::Config;
+use pactus::genesis::{Genesis, GenesisValidator, GENESIS_FILE_NAME};
+use pactus::network::identity::generate_network_key;
+use pactus::signer::protocol::generate_auth_keypair;
 use pactus_types::crypto::bls::secret_key::SecretKey;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "init", about = "Initializing the working directory")]
-
 pub struct InitCmd {
     #[structopt(short = "w", default_value = &super::DEFAULT_WORKING_DIR)]
     pub working_dir: String,
@@ -15,21 +20,86 @@ pub struct InitCmd {
     pub peer_address: String,
     #[structopt(long, short = "l", default_value = "127.0.0.1:6333")]
     pub listen_address: SocketAddr,
+    /// Number of the validator keys to generate
+    #[structopt(long, default_value = "1")]
+    pub validators: usize,
+    /// Genesis file of the network to join.
+    /// Without it, a local network is created with the generated validators as the committee.
+    #[structopt(long)]
+    pub genesis: Option<PathBuf>,
+    /// Overwrites the keys of an initialized working directory
+    #[structopt(long)]
+    pub force: bool,
 }
 
+/// Stake of each validator in the genesis of a local network.
+const LOCAL_VALIDATOR_STAKE: i64 = 1_000_000_000;
+
 impl InitCmd {
     /// Run the command
     pub fn execute(&self) -> Result<()> {
-        let sec = SecretKey::random();
         let node_config = Config::default();
 
         let mut dir = self.working_dir.clone();
         dir.push(std::path::MAIN_SEPARATOR);
 
+        // Overwriting the keys changes the identity of the node and its validators
+        if !self.force {
+            let files = [
+                super::CONFIG_FILE_NAME,
+                super::KEYSTORE_FILE_NAME,
+                super::VALIDATOR_KEY_FILE_NAME,
+                GENESIS_FILE_NAME,
+                super::SIGNER_AUTH_KEY_FILE_NAME,
+                super::NETWORK_KEY_FILE_NAME,
+            ];
+            for file in files {
+                let path = dir.clone() + file;
+                if Path::new(&path).exists() {
+                    return Err(anyhow!(
+                        "{} already exists, use --force to overwrite it",
+                        path
+                    ));
+                }
+            }
+        }
+
         save_toml_file(&node_config, dir.clone() + super::CONFIG_FILE_NAME)?;
+
+        let mut keystore = Keystore::new();
+        let mut genesis_validators = Vec::new();
+        for i in 1..=self.validators {
+            let secret_key = SecretKey::random();
+            keystore.add_key(&format!("validator_{}", i), &secret_key.to_string())?;
+            genesis_validators.push(GenesisValidator {
+                public_key: secret_key.public_key().to_string(),
+                stake: LOCAL_VALIDATOR_STAKE,
+            });
+        }
+        keystore.save(dir.clone() + super::KEYSTORE_FILE_NAME)?;
+
+        let genesis = match &self.genesis {
+            Some(path) => Genesis::load(path)?,
+            None => Genesis {
+                genesis_time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+                accounts: Vec::new(),
+                validators: genesis_validators,
+            },
+        };
+        genesis.save(Path::new(&(dir.clone() + GENESIS_FILE_NAME)))?;
+        println!("genesis hash: {}", hex::encode(genesis.hash()?.as_bytes()));
+
+        // The key that authenticates this node to a remote signer
+        let auth_key = generate_auth_keypair()?;
+        save_text_file(
+            &hex::encode(auth_key.secret),
+            dir.clone() + super::SIGNER_AUTH_KEY_FILE_NAME,
+        )?;
+        println!("signer auth public key: {}", hex::encode(auth_key.public));
+
         save_text_file(
-            &sec.to_string(),
-            dir.clone() + super::VALIDATOR_KEY_FILE_NAME,
+            &generate_network_key()?,
+            dir.clone() + super::NETWORK_KEY_FILE_NAME,
         )?;
         Ok(())
     }
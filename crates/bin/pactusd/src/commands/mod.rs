@@ -1,4 +1,5 @@
 pub mod init;
+pub mod slashing_protection;
 pub mod start;
 
 use anyhow::Result;
@@ -7,7 +8,13 @@ use structopt::StructOpt;
 use std::env;
 
 pub const CONFIG_FILE_NAME: &str = "config.toml";
+pub const KEYSTORE_FILE_NAME: &str = "keystore.json";
+/// Older nodes kept a single validator key in this file.
 pub const VALIDATOR_KEY_FILE_NAME: &str = "validator_key";
+pub const EVIDENCE_FILE_NAME: &str = "evidences";
+pub const SLASHING_PROTECTION_FILE_NAME: &str = "slashing_protection.json";
+pub const SIGNER_AUTH_KEY_FILE_NAME: &str = "signer_auth_key";
+pub const NETWORK_KEY_FILE_NAME: &str = "network_key";
 
 lazy_static! {
     static ref DEFAULT_WORKING_DIR: String =
@@ -15,6 +22,7 @@ lazy_static! {
 }
 
 use crate::commands::init::InitCmd;
+use crate::commands::slashing_protection::SlashingProtectionCmd;
 use crate::commands::start::StartCmd;
 
 pub trait PactusDaemonCommand {
@@ -29,6 +37,9 @@ pub enum Command {
 
     #[structopt(name = "start")]
     Start(StartCmd),
+
+    #[structopt(name = "slashing-protection")]
+    SlashingProtection(SlashingProtectionCmd),
 }
 
 impl Command {
@@ -43,6 +54,7 @@ impl PactusDaemonCommand for Command {
         match self {
             Self::Init(command) => command.execute(),
             Self::Start(command) => command.execute(),
+            Self::SlashingProtection(command) => command.execute(),
         }
     }
 }
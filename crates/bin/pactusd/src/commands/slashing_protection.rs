@@ -0,0 +1,69 @@
+use crate::file::{load_json_file, save_json_file};
+use anyhow::Result;
+use pactus::genesis::{Genesis, GENESIS_FILE_NAME};
+use pactus::slashing_protection::create_slashing_protection;
+use pactus::slashing_protection::interchange::Interchange;
+use pactus_types::hash::Hash32;
+use std::path::Path;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "slashing-protection",
+    about = "Exporting or importing the signing history of the validators"
+)]
+pub enum SlashingProtectionCmd {
+    #[structopt(name = "export")]
+    Export {
+        #[structopt(short = "w", default_value = &super::DEFAULT_WORKING_DIR)]
+        working_dir: String,
+        /// Path of the interchange file to write
+        #[structopt(short, long)]
+        file: String,
+    },
+
+    #[structopt(name = "import")]
+    Import {
+        #[structopt(short = "w", default_value = &super::DEFAULT_WORKING_DIR)]
+        working_dir: String,
+        /// Path of the interchange file to read
+        #[structopt(short, long)]
+        file: String,
+    },
+}
+
+impl SlashingProtectionCmd {
+    /// Run the command
+    pub fn execute(&self) -> Result<()> {
+        match self {
+            Self::Export { working_dir, file } => {
+                let protection = create_slashing_protection(
+                    genesis_hash(working_dir)?,
+                    Some(history_file(working_dir).into()),
+                )?;
+                save_json_file(&protection.export(), file)?;
+            }
+            Self::Import { working_dir, file } => {
+                // The history is locked while the node is running, so it can't be overwritten
+                let protection = create_slashing_protection(
+                    genesis_hash(working_dir)?,
+                    Some(history_file(working_dir).into()),
+                )?;
+                let interchange: Interchange = load_json_file(file)?;
+                protection.import(interchange)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn genesis_hash(working_dir: &str) -> Result<Hash32> {
+    let genesis = Genesis::load(&Path::new(working_dir).join(GENESIS_FILE_NAME))?;
+    Ok(genesis.hash()?)
+}
+
+fn history_file(working_dir: &str) -> String {
+    let mut dir = working_dir.to_string();
+    dir.push(std::path::MAIN_SEPARATOR);
+    dir + super::SLASHING_PROTECTION_FILE_NAME
+}
@@ -1,4 +1,5 @@
-use crate::file::{load_text_file, load_toml_file};
+use crate::file::{load_text_file, load_toml_file, save_text_file};
+use crate::keystore::Keystore;
 use anyhow::Result;
 use async_std::task;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -7,12 +8,23 @@ use std::thread::{self, sleep};
 use std::time::Duration;
 use structopt::StructOpt;
 use pactus::config::Config;
-use pactus::network::create_network_service;
-use pactus::sync::create_sync_service;
+use pactus::consensus::create_consensus;
+use pactus::event::create_event_bus;
+use pactus::evidence::create_evidence_pool;
+use pactus::genesis::{Genesis, GENESIS_FILE_NAME};
+use pactus::grpc::create_grpc_server;
+use pactus::http::create_http_server;
+use pactus::metrics::{create_metrics, create_metrics_server};
+use pactus::network::identity::generate_network_key;
+use pactus::network::{create_network_service, NetworkService};
+use pactus::publisher::create_raw_publisher;
+use pactus::signer::{create_local_signer, create_remote_signer};
+use pactus::slashing_protection::create_slashing_protection;
+use pactus::state::create_state;
+use pactus::sync::{create_sync_service, SyncService};
+use pactus::txpool::create_txpool;
 use pactus::Service;
-use pactus_types::crypto::secret_key::SecretKey;
-use pactus_types::crypto::signer::Signer;
-use pactus_types::crypto::KeyPairType;
+use std::path::Path;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "start", about = "run the node")]
@@ -36,18 +48,99 @@ impl StartCmd {
         let mut dir = self.working_dir.clone();
         dir.push(std::path::MAIN_SEPARATOR);
 
-        let hex: String = load_text_file(dir.clone() + super::VALIDATOR_KEY_FILE_NAME)?;
-        let validator_key = SecretKey::from_string(KeyPairType::KeyPairBLS, &hex)?;
-        let signer = Signer::new(validator_key);
-
         //load the configuration file
-        let config: Config = load_toml_file(dir.clone() + super::CONFIG_FILE_NAME)?;
+        let mut config: Config = load_toml_file(dir.clone() + super::CONFIG_FILE_NAME)?;
+        if config.network.network_key.is_none() {
+            config.network.network_key = Some(network_key_file(&dir)?.into());
+        }
 
         //load the genesis file
-        //let genesis: Genesis = file::load_genesis_file(dir.clone() + "/genesis.json").unwrap();
-
-        let mut network = create_network_service(config.network)?;
-        let sync = create_sync_service(config.sync, signer, &mut network).unwrap();
+        let genesis = Genesis::load(Path::new(&(dir.clone() + GENESIS_FILE_NAME)))?;
+        let genesis_hash = genesis.hash()?;
+
+        let signers = match &config.signer.remote_address {
+            None => {
+                let keystore = load_keystore(&dir)?;
+                let slashing_protection = create_slashing_protection(
+                    genesis_hash.clone(),
+                    Some((dir.clone() + super::SLASHING_PROTECTION_FILE_NAME).into()),
+                )?;
+                keystore
+                    .signers()?
+                    .into_iter()
+                    .map(|(label, signer)| {
+                        println!("validator {}: {}", label, signer.address());
+                        create_local_signer(signer, slashing_protection.clone())
+                    })
+                    .collect()
+            }
+            // The remote signer holds one validator key
+            Some(_) => {
+                let auth_key: String =
+                    load_text_file(dir.clone() + super::SIGNER_AUTH_KEY_FILE_NAME)?;
+                vec![create_remote_signer(&config.signer, hex::decode(auth_key)?)?]
+            }
+        };
+
+        let event_bus = create_event_bus();
+        let state = create_state(
+            genesis_hash,
+            genesis.committee()?,
+            genesis.accounts(),
+            event_bus.clone(),
+        )?;
+        let (broadcast_sender, broadcast_receiver) = async_std::channel::unbounded();
+        let tx_pool = create_txpool(
+            config.txpool,
+            state.clone(),
+            broadcast_sender.clone(),
+            event_bus.clone(),
+        );
+        let evidence_pool = create_evidence_pool(Some(
+            (dir.clone() + super::EVIDENCE_FILE_NAME).into(),
+        ))?;
+        let consensus = create_consensus(
+            config.consensus,
+            signers.clone(),
+            state.clone(),
+            tx_pool.clone(),
+            evidence_pool.clone(),
+            broadcast_sender,
+            event_bus.clone(),
+        )?;
+        let metrics = create_metrics();
+        let mut network = create_network_service(config.network, metrics.clone())?;
+        let sync = create_sync_service(
+            config.sync,
+            signers,
+            state.clone(),
+            consensus,
+            tx_pool.clone(),
+            broadcast_receiver,
+            &mut network,
+            metrics.clone(),
+            event_bus.clone(),
+        )
+        .unwrap();
+        let grpc = create_grpc_server(
+            config.grpc,
+            state.clone(),
+            tx_pool,
+            evidence_pool.clone(),
+            sync.peer_set(),
+            network.network_name(),
+            event_bus.clone(),
+        )?;
+        let http = create_http_server(
+            config.http,
+            state,
+            evidence_pool,
+            sync.peer_set(),
+            network.network_name(),
+            network.self_id().to_string(),
+        )?;
+        let metrics_server = create_metrics_server(config.metrics, metrics)?;
+        let publisher = create_raw_publisher(config.publisher, event_bus)?;
 
         let network_task = task::spawn(async {
             network.start().await;
@@ -60,6 +153,22 @@ impl StartCmd {
             sync.start().await;
         });
 
+        let grpc_task = task::spawn(async {
+            grpc.start().await;
+        });
+
+        let http_task = task::spawn(async {
+            http.start().await;
+        });
+
+        let metrics_task = task::spawn(async {
+            metrics_server.start().await;
+        });
+
+        let publisher_task = task::spawn(async {
+            publisher.start().await;
+        });
+
         while running.load(Ordering::SeqCst) {
             thread::sleep(std::time::Duration::from_secs(1));
         }
@@ -69,6 +178,10 @@ impl StartCmd {
         let _handle = task::spawn(async {
             network_task.cancel().await;
             sync_task.cancel().await;
+            grpc_task.cancel().await;
+            http_task.cancel().await;
+            metrics_task.cancel().await;
+            publisher_task.cancel().await;
         });
 
         // futures::executor::block_on(handle);
@@ -76,3 +189,26 @@ impl StartCmd {
         Ok(())
     }
 }
+
+/// Returns the network key file in the working directory.
+/// Nodes that are initialized before the network key was introduced get a new one here.
+fn network_key_file(dir: &str) -> Result<String> {
+    let network_key_file = dir.to_string() + super::NETWORK_KEY_FILE_NAME;
+    if !Path::new(&network_key_file).exists() {
+        save_text_file(&generate_network_key()?, &network_key_file)?;
+        println!("network key is generated: {}", network_key_file);
+    }
+    Ok(network_key_file)
+}
+
+/// Loads the keystore, falling back to the single validator key of older nodes.
+fn load_keystore(dir: &str) -> Result<Keystore> {
+    let keystore_file = dir.to_string() + super::KEYSTORE_FILE_NAME;
+    if Path::new(&keystore_file).exists() {
+        return Keystore::load(keystore_file);
+    }
+    let hex: String = load_text_file(dir.to_string() + super::VALIDATOR_KEY_FILE_NAME)?;
+    let mut keystore = Keystore::new();
+    keystore.add_key("validator", &hex)?;
+    Ok(keystore)
+}
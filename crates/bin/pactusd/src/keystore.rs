@@ -0,0 +1,100 @@
+use crate::file::{load_json_file, save_json_file};
+use anyhow::{anyhow, Result};
+use pactus_types::crypto::secret_key::SecretKey;
+use pactus_types::crypto::signer::Signer;
+use pactus_types::crypto::KeyPairType;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A validator key, with a label to tell the keys apart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub label: String,
+    /// Hex encoded BLS secret key.
+    pub secret_key: String,
+}
+
+/// Holds the validator keys of the node.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Keystore {
+    keys: Vec<KeyEntry>,
+}
+
+impl Keystore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let keystore: Keystore = load_json_file(path)?;
+        // Make sure that all the keys are valid
+        keystore.signers()?;
+        Ok(keystore)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save_json_file(self, path)
+    }
+
+    pub fn add_key(&mut self, label: &str, secret_key: &str) -> Result<()> {
+        if self.keys.iter().any(|key| key.label == label) {
+            return Err(anyhow!("duplicated label: {}", label));
+        }
+        SecretKey::from_string(KeyPairType::KeyPairBLS, secret_key)?;
+        self.keys.push(KeyEntry {
+            label: label.to_string(),
+            secret_key: secret_key.to_string(),
+        });
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the signers of the keys, with their labels.
+    pub fn signers(&self) -> Result<Vec<(String, Signer)>> {
+        self.keys
+            .iter()
+            .map(|key| {
+                let secret_key = SecretKey::from_string(KeyPairType::KeyPairBLS, &key.secret_key)?;
+                Ok((key.label.clone(), Signer::new(secret_key)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::bls::secret_key::SecretKey as BLSSecretKey;
+
+    #[test]
+    fn test_keystore() {
+        let mut keystore = Keystore::new();
+        keystore
+            .add_key("validator_1", &BLSSecretKey::random().to_string())
+            .unwrap();
+        keystore
+            .add_key("validator_2", &BLSSecretKey::random().to_string())
+            .unwrap();
+        assert!(keystore
+            .add_key("validator_1", &BLSSecretKey::random().to_string())
+            .is_err());
+        assert!(keystore.add_key("validator_3", "invalid").is_err());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        keystore.save(file.path()).unwrap();
+        let loaded = Keystore::load(file.path()).unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        let signers = loaded.signers().unwrap();
+        let expected = keystore.signers().unwrap();
+        assert_eq!(signers[1].0, "validator_2");
+        assert_eq!(signers[1].1.address(), expected[1].1.address());
+    }
+}
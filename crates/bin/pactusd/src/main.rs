@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod file;
+pub mod keystore;
 
 pub use crate::commands::Command;
 
@@ -7,5 +8,6 @@ fn main() {
     match Command::from_args() {
         Command::Init(cmd) => println!("{:?}", cmd.execute()),
         Command::Start(cmd) => println!("{:?}", cmd.execute()),
+        Command::SlashingProtection(cmd) => println!("{:?}", cmd.execute()),
     }
 }
@@ -0,0 +1,14 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the bundled protoc, so the build doesn't depend on the system
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    tonic_build::configure().compile_protos(
+        &[
+            "proto/blockchain.proto",
+            "proto/transaction.proto",
+            "proto/network.proto",
+            "proto/event.proto",
+        ],
+        &["proto"],
+    )?;
+    Ok(())
+}
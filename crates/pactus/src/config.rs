@@ -1,17 +1,15 @@
-use crate::{network, sync};
+use crate::{consensus, grpc, http, metrics, network, publisher, signer, sync, txpool};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     pub network: network::config::Config,
     pub sync: sync::config::Config,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            network: network::config::Config::default(),
-            sync: sync::config::Config::default(),
-        }
-    }
+    pub consensus: consensus::config::Config,
+    pub txpool: txpool::config::Config,
+    pub signer: signer::config::Config,
+    pub grpc: grpc::config::Config,
+    pub http: http::config::Config,
+    pub metrics: metrics::config::Config,
+    pub publisher: publisher::config::Config,
 }
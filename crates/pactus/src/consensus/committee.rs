@@ -0,0 +1,67 @@
+use crate::error::Result;
+use pactus_types::address::Address;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::validator::Validator;
+
+/// A validator in the committee, with its decoded public key and address.
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub number: i32,
+    pub address: Address,
+    pub public_key: PublicKey,
+    pub power: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Committee {
+    members: Vec<Member>,
+}
+
+impl Committee {
+    pub fn new(validators: &[Validator]) -> Result<Self> {
+        let mut members = Vec::with_capacity(validators.len());
+        for val in validators {
+            members.push(Member {
+                number: val.number(),
+                address: val.address()?,
+                public_key: PublicKey::BLS(val.public_key()?),
+                power: val.stake(),
+            });
+        }
+        members.sort_by_key(|member| member.number);
+        Ok(Self { members })
+    }
+
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    pub fn member(&self, address: &Address) -> Option<&Member> {
+        self.members
+            .iter()
+            .find(|member| &member.address == address)
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.member(address).is_some()
+    }
+
+    pub fn total_power(&self) -> i64 {
+        self.members.iter().map(|member| member.power).sum()
+    }
+
+    /// Returns true if the power is more than two-thirds of the committee power.
+    pub fn is_quorum(&self, power: i64) -> bool {
+        self.total_power() > 0 && 3 * power > 2 * self.total_power()
+    }
+
+    /// Returns the proposer for the given height and round.
+    /// The proposer rotates through the committee by height and moves on to the next one in each round.
+    pub fn proposer(&self, height: i32, round: i32) -> Option<&Member> {
+        if self.members.is_empty() {
+            return None;
+        }
+        let index = (height as i64 + round as i64).rem_euclid(self.members.len() as i64);
+        self.members.get(index as usize)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The time between two blocks.
+    pub block_time: Duration,
+    /// The time to wait for a proposal before voting to change the proposer.
+    pub change_proposer_timeout: Duration,
+    /// The timeout grows by this amount in each round.
+    pub change_proposer_delta: Duration,
+    pub max_transactions_per_block: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            block_time: Duration::from_secs(10),
+            change_proposer_timeout: Duration::from_secs(8),
+            change_proposer_delta: Duration::from_secs(4),
+            max_transactions_per_block: 1000,
+        }
+    }
+}
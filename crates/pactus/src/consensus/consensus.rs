@@ -0,0 +1,783 @@
+use super::committee::Committee;
+use super::config::Config;
+use super::vote_set::VoteSet;
+use super::{Broadcast, Consensus};
+use crate::error::{Error, Result};
+use crate::event::{Event, EventBus};
+use crate::evidence::EvidencePool;
+use crate::signer::Signer;
+use crate::state::State;
+use crate::txpool::TxPool;
+use async_std::channel::Sender;
+use async_std::task;
+use log::{debug, error, info, warn};
+use pactus_types::block::{Block, BlockHeader};
+use pactus_types::evidence::Evidence;
+use pactus_types::hash::Hash32;
+use pactus_types::proposal::Proposal;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::vote::{Vote, VoteType};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Votes for the rounds further than this from the current round are ignored.
+const MAX_ROUNDS_AHEAD: i32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    NewHeight,
+    Propose,
+    Prepare,
+    Precommit,
+    ChangeProposer,
+    Commit,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Ticker {
+    height: i32,
+    round: i32,
+    target: Step,
+}
+
+pub(super) struct PactusConsensus {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    this: Weak<PactusConsensus>,
+    config: Config,
+    /// The validators of this node.
+    signers: Vec<Arc<dyn Signer>>,
+    state: Arc<dyn State>,
+    tx_pool: Arc<dyn TxPool>,
+    evidence_pool: Arc<dyn EvidencePool>,
+    broadcaster: Sender<Broadcast>,
+    event_bus: Arc<dyn EventBus>,
+    height: i32,
+    round: i32,
+    step: Step,
+    committee: Arc<Committee>,
+    proposals: HashMap<i32, Proposal>,
+    votes: HashMap<(i32, VoteType), VoteSet>,
+    /// The highest round that we have asked the votes for.
+    queried_votes_round: i32,
+    /// The round that we have asked the proposal for.
+    queried_proposal_round: i32,
+}
+
+impl PactusConsensus {
+    pub fn new(
+        config: Config,
+        signers: Vec<Arc<dyn Signer>>,
+        state: Arc<dyn State>,
+        tx_pool: Arc<dyn TxPool>,
+        evidence_pool: Arc<dyn EvidencePool>,
+        broadcaster: Sender<Broadcast>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Arc<Self> {
+        Arc::new_cyclic(|this| PactusConsensus {
+            inner: Mutex::new(Inner {
+                this: this.clone(),
+                config,
+                signers,
+                state,
+                tx_pool,
+                evidence_pool,
+                broadcaster,
+                event_bus,
+                height: 0,
+                round: 0,
+                step: Step::NewHeight,
+                committee: Arc::new(Committee::default()),
+                proposals: HashMap::new(),
+                votes: HashMap::new(),
+                queried_votes_round: -1,
+                queried_proposal_round: -1,
+            }),
+        })
+    }
+
+    fn handle_timeout(&self, ticker: Ticker) {
+        self.inner.lock().unwrap().handle_timeout(ticker)
+    }
+}
+
+impl Consensus for PactusConsensus {
+    fn move_to_new_height(&self) {
+        self.inner.lock().unwrap().move_to_new_height()
+    }
+
+    fn height_round(&self) -> (i32, i32) {
+        let inner = self.inner.lock().unwrap();
+        (inner.height, inner.round)
+    }
+
+    fn add_vote(&self, vote: Vote) -> Result<()> {
+        self.inner.lock().unwrap().add_vote(vote)
+    }
+
+    fn set_proposal(&self, proposal: Proposal) -> Result<()> {
+        self.inner.lock().unwrap().set_proposal(proposal)
+    }
+
+    fn proposal(&self, height: i32, round: i32) -> Option<Proposal> {
+        let inner = self.inner.lock().unwrap();
+        if height != inner.height {
+            return None;
+        }
+        inner.proposals.get(&round).cloned()
+    }
+
+    fn votes(&self, height: i32, round: i32) -> Vec<Vote> {
+        let inner = self.inner.lock().unwrap();
+        if height != inner.height {
+            return Vec::new();
+        }
+        inner
+            .votes
+            .iter()
+            .filter(|((vote_round, _), _)| *vote_round == round)
+            .flat_map(|(_, votes)| votes.votes().cloned())
+            .collect()
+    }
+}
+
+impl Inner {
+    fn move_to_new_height(&mut self) {
+        let height = self.state.last_block_height() + 1;
+        if self.height >= height {
+            return;
+        }
+
+        let committee = match Committee::new(&self.state.committee_validators()) {
+            Ok(committee) => committee,
+            Err(err) => {
+                warn!("unable to load the committee: {}", err);
+                return;
+            }
+        };
+        self.height = height;
+        self.round = 0;
+        self.set_step(Step::NewHeight);
+        self.committee = Arc::new(committee);
+        self.proposals.clear();
+        self.votes.clear();
+        self.queried_votes_round = -1;
+        self.queried_proposal_round = -1;
+        debug!("entering new height {}", height);
+
+        let delay = self.new_height_delay();
+        self.schedule_timeout(delay, Step::NewHeight);
+    }
+
+    fn set_step(&mut self, step: Step) {
+        self.step = step;
+        self.event_bus.publish(Event::ConsensusStep {
+            height: self.height,
+            round: self.round,
+            step: format!("{:?}", step),
+        });
+    }
+
+    /// Returns how long to wait before starting the new height, so blocks are `block_time` apart.
+    fn new_height_delay(&self) -> Duration {
+        let last_block_time = match self.last_block() {
+            Some(block) => block.header().unix_time(),
+            None => return Duration::ZERO,
+        };
+        let next_block_time =
+            UNIX_EPOCH + Duration::from_secs(last_block_time as u64) + self.config.block_time;
+        next_block_time
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn schedule_timeout(&self, duration: Duration, target: Step) {
+        let ticker = Ticker {
+            height: self.height,
+            round: self.round,
+            target,
+        };
+        let this = self.this.clone();
+        task::spawn(async move {
+            task::sleep(duration).await;
+            if let Some(consensus) = this.upgrade() {
+                // Handling the timeout may wait for the signer
+                task::spawn_blocking(move || consensus.handle_timeout(ticker)).await;
+            }
+        });
+    }
+
+    fn handle_timeout(&mut self, ticker: Ticker) {
+        if ticker.height != self.height || ticker.round != self.round {
+            return;
+        }
+        match ticker.target {
+            Step::NewHeight if self.step == Step::NewHeight => self.enter_propose(0),
+            Step::ChangeProposer if self.step == Step::Prepare => self.enter_change_proposer(),
+            _ => {}
+        }
+    }
+
+    fn enter_propose(&mut self, round: i32) {
+        self.round = round;
+        self.set_step(Step::Propose);
+
+        let proposer = self.committee.proposer(self.height, round).and_then(|proposer| {
+            self.signers
+                .iter()
+                .find(|signer| signer.address() == proposer.address)
+                .cloned()
+        });
+        if let Some(signer) = proposer {
+            match self.create_proposal(signer.as_ref()) {
+                Ok(proposal) => {
+                    info!("proposing block at {}/{}", self.height, round);
+                    self.proposals.insert(round, proposal.clone());
+                    self.broadcast(Broadcast::Proposal(Box::new(proposal)));
+                }
+                Err(err) => warn!("unable to create a proposal: {}", err),
+            }
+        }
+
+        let timeout =
+            self.config.change_proposer_timeout + self.config.change_proposer_delta * round as u32;
+        self.schedule_timeout(timeout, Step::ChangeProposer);
+        self.enter_prepare();
+    }
+
+    fn enter_prepare(&mut self) {
+        self.set_step(Step::Prepare);
+        if let Some(hash) = self.proposal_hash(self.round) {
+            self.cast_vote(VoteType::Prepare, hash);
+        }
+        self.check_prepare();
+    }
+
+    fn enter_precommit(&mut self, block_hash: Hash32) {
+        self.set_step(Step::Precommit);
+        self.cast_vote(VoteType::Precommit, block_hash);
+        self.check_precommit();
+    }
+
+    fn enter_change_proposer(&mut self) {
+        info!("changing the proposer at {}/{}", self.height, self.round);
+        self.set_step(Step::ChangeProposer);
+        self.cast_vote(VoteType::ChangeProposer, Hash32::UNDEF);
+        self.check_change_proposer(self.round);
+    }
+
+    fn check_prepare(&mut self) {
+        if self.step != Step::Prepare {
+            return;
+        }
+        let quorum_hash = self
+            .votes
+            .get(&(self.round, VoteType::Prepare))
+            .and_then(|votes| votes.quorum_hash());
+        if let Some(hash) = quorum_hash {
+            match self.proposal_hash(self.round) {
+                Some(proposal_hash) if proposal_hash == hash => self.enter_precommit(hash),
+                Some(_) => warn!("prepared block is not the proposed one"),
+                None => self.query_proposal(),
+            }
+        }
+    }
+
+    fn check_precommit(&mut self) {
+        if self.step != Step::Prepare && self.step != Step::Precommit {
+            return;
+        }
+        let cert = match self
+            .votes
+            .get(&(self.round, VoteType::Precommit))
+            .and_then(|votes| votes.to_certificate())
+        {
+            Some(cert) => cert,
+            None => return,
+        };
+        let proposal = match self.proposals.get(&self.round) {
+            Some(proposal) => proposal,
+            None => return,
+        };
+        if proposal.block().hash().ok().as_ref() != Some(cert.block_hash()) {
+            return;
+        }
+
+        let block = proposal.block().clone();
+        self.set_step(Step::Commit);
+        if let Err(err) = self.state.commit_block(self.height, block.clone(), cert) {
+            warn!("unable to commit block {}: {}", self.height, err);
+            return;
+        }
+        info!("block {} committed", self.height);
+        self.tx_pool.handle_committed_block(&block);
+        self.broadcast(Broadcast::BlockAnnounce {
+            height: self.height,
+        });
+        self.move_to_new_height();
+    }
+
+    fn check_change_proposer(&mut self, round: i32) {
+        if round < self.round || self.step == Step::Commit || self.step == Step::NewHeight {
+            return;
+        }
+        let has_quorum = self
+            .votes
+            .get(&(round, VoteType::ChangeProposer))
+            .is_some_and(|votes| votes.has_quorum());
+        if has_quorum {
+            self.enter_propose(round + 1);
+        }
+    }
+
+    fn add_vote(&mut self, vote: Vote) -> Result<()> {
+        if vote.height() != self.height {
+            debug!("vote for another height: {}", vote.height());
+            return Ok(());
+        }
+        if vote.round() > self.round + MAX_ROUNDS_AHEAD {
+            debug!("vote for a far round: {}", vote.round());
+            return Ok(());
+        }
+        // The vote is checked before it can create a vote set or trigger a query
+        let member = self.committee.member(vote.signer()).ok_or_else(|| {
+            Error::InvalidVote(format!(
+                "signer is not in the committee: {:?}",
+                vote.signer()
+            ))
+        })?;
+        vote.verify(&member.public_key)?;
+
+        if vote.round() > self.round {
+            // Other validators are ahead of us, we may have missed some votes
+            self.query_votes(vote.round());
+        }
+        let key = (vote.round(), vote.vote_type());
+        let (height, committee) = (self.height, self.committee.clone());
+        let vote_set = self
+            .votes
+            .entry(key)
+            .or_insert_with(|| VoteSet::new(committee, key.1, height, key.0));
+        let added = match vote_set.add_vote(vote.clone()) {
+            Ok(added) => added,
+            Err(err @ Error::DoubleSign(_)) => {
+                if let Some(first) = vote_set.conflicting_vote(&vote).cloned() {
+                    self.report_evidence(Evidence::DuplicateVote {
+                        first,
+                        second: vote,
+                    });
+                }
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        };
+        if !added {
+            return Ok(());
+        }
+
+        match key.1 {
+            VoteType::Prepare => self.check_prepare(),
+            VoteType::Precommit => self.check_precommit(),
+            VoteType::ChangeProposer => self.check_change_proposer(key.0),
+        }
+        Ok(())
+    }
+
+    fn set_proposal(&mut self, proposal: Proposal) -> Result<()> {
+        if proposal.height() != self.height || proposal.round() < self.round {
+            debug!(
+                "proposal for another height or round: {}/{}",
+                proposal.height(),
+                proposal.round()
+            );
+            return Ok(());
+        }
+        let proposer = self
+            .committee
+            .proposer(proposal.height(), proposal.round())
+            .ok_or_else(|| Error::InvalidProposal("no proposer".to_string()))?;
+        if let Some(existing) = self.proposals.get(&proposal.round()) {
+            if existing.block().hash()? == proposal.block().hash()? {
+                return Ok(());
+            }
+            proposal.verify(&proposer.public_key)?;
+            let evidence = Evidence::DuplicateProposal {
+                first: Box::new(existing.clone()),
+                second: Box::new(proposal),
+            };
+            self.report_evidence(evidence);
+            return Err(Error::DoubleSign(format!(
+                "proposer {} proposed two blocks",
+                proposer.number
+            )));
+        }
+        proposal.verify(&proposer.public_key)?;
+        if proposal.block().header().prev_block_hash() != &self.state.last_block_hash() {
+            return Err(Error::InvalidProposal(
+                "invalid previous block hash".to_string(),
+            ));
+        }
+        self.state.validate_block(proposal.block())?;
+
+        let round = proposal.round();
+        let block_hash = proposal.block().hash()?;
+        self.proposals.insert(round, proposal);
+        if round == self.round && self.step == Step::Prepare {
+            self.cast_vote(VoteType::Prepare, block_hash);
+            self.check_prepare();
+        }
+        Ok(())
+    }
+
+    fn create_proposal(&self, signer: &dyn Signer) -> Result<Proposal> {
+        let last_block = self.last_block();
+        let prev_cert = self.state.last_certificate();
+        let prev_cert_hash = match &prev_cert {
+            Some(cert) => cert.hash()?,
+            None => Hash32::UNDEF,
+        };
+        let prev_seed = last_block
+            .as_ref()
+            .map_or(Seed::UNDEF, |block| block.header().sortition_seed().clone());
+        let txs = self
+            .tx_pool
+            .prepare_block_transactions(self.config.max_transactions_per_block)
+            .into();
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        let header = BlockHeader::new(
+            1,
+            unix_time,
+            self.state.last_block_hash(),
+            prev_cert_hash,
+            Hash32::UNDEF,
+            pactus_types::block::Transactions::root(&txs)?,
+            signer.generate_seed(&prev_seed)?,
+            signer.address(),
+        );
+        let mut proposal =
+            Proposal::new(self.height, self.round, Block::new(header, prev_cert, txs));
+        signer.sign_proposal(&mut proposal)?;
+        Ok(proposal)
+    }
+
+    /// Casts the vote for all of our validators in the committee.
+    fn cast_vote(&mut self, vote_type: VoteType, block_hash: Hash32) {
+        // Sign all the votes first, adding a vote may move us to the next step
+        let mut votes = Vec::new();
+        for signer in &self.signers {
+            let address = signer.address();
+            if !self.committee.contains(&address) {
+                continue;
+            }
+            let has_voted = self
+                .votes
+                .get(&(self.round, vote_type))
+                .is_some_and(|votes| votes.has_voted(&address));
+            if has_voted {
+                continue;
+            }
+
+            let mut vote = Vote::new(vote_type, self.height, self.round, block_hash.clone(), address);
+            if let Err(err) = signer.sign_vote(&mut vote) {
+                error!("unable to sign the vote: {}", err);
+                continue;
+            }
+            votes.push(vote);
+        }
+
+        for vote in votes {
+            self.broadcast(Broadcast::Vote(vote.clone()));
+            if let Err(err) = self.add_vote(vote) {
+                warn!("unable to add our vote: {}", err);
+            }
+        }
+    }
+
+    fn report_evidence(&self, evidence: Evidence) {
+        let offender = evidence.offender();
+        if self.signers.iter().any(|signer| &signer.address() == offender) {
+            error!(
+                "our validator signed conflicting messages at {}/{}, is it running on another host?",
+                evidence.height(),
+                evidence.round()
+            );
+        } else {
+            warn!(
+                "validator {:?} signed conflicting messages at {}/{}",
+                evidence.offender(),
+                evidence.height(),
+                evidence.round()
+            );
+        }
+        if let Err(err) = self.evidence_pool.add_evidence(evidence) {
+            warn!("unable to save the evidence: {}", err);
+        }
+    }
+
+    fn query_proposal(&mut self) {
+        if self.queried_proposal_round == self.round {
+            return;
+        }
+        self.queried_proposal_round = self.round;
+        self.broadcast(Broadcast::QueryProposal {
+            height: self.height,
+            round: self.round,
+        });
+    }
+
+    fn query_votes(&mut self, round: i32) {
+        if self.queried_votes_round >= round {
+            return;
+        }
+        self.queried_votes_round = round;
+        self.broadcast(Broadcast::QueryVotes {
+            height: self.height,
+            round,
+        });
+    }
+
+    fn proposal_hash(&self, round: i32) -> Option<Hash32> {
+        self.proposals
+            .get(&round)
+            .and_then(|proposal| proposal.block().hash().ok())
+    }
+
+    fn last_block(&self) -> Option<Block> {
+        let data = self.state.block_data(self.state.last_block_height())?;
+        Block::from_bytes(&data).ok()
+    }
+
+    fn broadcast(&self, msg: Broadcast) {
+        if self.broadcaster.try_send(msg).is_err() {
+            warn!("unable to broadcast the consensus message");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use crate::evidence::create_evidence_pool;
+    use crate::signer::create_local_signer;
+    use crate::slashing_protection::create_slashing_protection;
+    use pactus_types::crypto::signer::Signer as KeySigner;
+    use crate::state::create_state;
+    use crate::txpool::{config::Config as TxPoolConfig, create_txpool};
+    use async_std::channel::{unbounded, Receiver};
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::public_key::PublicKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::validator::Validator;
+
+    struct Node {
+        signers: Vec<KeySigner>,
+        state: Arc<dyn State>,
+        evidence_pool: Arc<dyn EvidencePool>,
+        consensus: Arc<PactusConsensus>,
+        receiver: Receiver<Broadcast>,
+    }
+
+    /// Sets up the nodes, each of them holding the given number of validator keys.
+    fn setup(keys_per_node: &[usize]) -> Vec<Node> {
+        let count = keys_per_node.iter().sum();
+        let mut signers: Vec<KeySigner> = (0..count)
+            .map(|_| KeySigner::new(CryptoSecretKey::BLS(SecretKey::random())))
+            .collect();
+        let validators: Vec<Validator> = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| {
+                let PublicKey::BLS(pk) = signer.public_key();
+                Validator::new(&pk, i as i32, 1)
+            })
+            .collect();
+
+        let config = Config {
+            block_time: Duration::from_millis(100),
+            ..Config::default()
+        };
+        keys_per_node
+            .iter()
+            .map(|keys| {
+                let signers: Vec<KeySigner> = signers.drain(..keys).collect();
+                let event_bus = create_event_bus();
+                let state = create_state(
+                    Hash32::UNDEF,
+                    validators.clone(),
+                    Vec::new(),
+                    event_bus.clone(),
+                )
+                .unwrap();
+                let evidence_pool = create_evidence_pool(None).unwrap();
+                let slashing_protection = create_slashing_protection(Hash32::UNDEF, None).unwrap();
+                let (sender, receiver) = unbounded();
+                let consensus = PactusConsensus::new(
+                    config.clone(),
+                    signers
+                        .iter()
+                        .map(|signer| create_local_signer(signer.clone(), slashing_protection.clone()))
+                        .collect(),
+                    state.clone(),
+                    create_txpool(
+                        TxPoolConfig::default(),
+                        state.clone(),
+                        sender.clone(),
+                        event_bus.clone(),
+                    ),
+                    evidence_pool.clone(),
+                    sender,
+                    event_bus,
+                );
+                Node {
+                    signers,
+                    state,
+                    evidence_pool,
+                    consensus,
+                    receiver,
+                }
+            })
+            .collect()
+    }
+
+    /// Relays the broadcast messages between the nodes until they all reach the height.
+    async fn run_until(nodes: &[Node], height: i32) {
+        for _ in 0..500 {
+            if nodes.iter().all(|n| n.state.last_block_height() >= height) {
+                return;
+            }
+            for (i, node) in nodes.iter().enumerate() {
+                while let Ok(msg) = node.receiver.try_recv() {
+                    for (j, other) in nodes.iter().enumerate() {
+                        if i == j {
+                            continue;
+                        }
+                        match &msg {
+                            Broadcast::Proposal(p) => {
+                                other.consensus.set_proposal(*p.clone()).unwrap()
+                            }
+                            Broadcast::Vote(v) => other.consensus.add_vote(v.clone()).unwrap(),
+                            Broadcast::QueryProposal { height, round } => {
+                                if let Some(p) = other.consensus.proposal(*height, *round) {
+                                    node.consensus.set_proposal(p).unwrap();
+                                }
+                            }
+                            Broadcast::QueryVotes { .. }
+                            | Broadcast::BlockAnnounce { .. }
+                            | Broadcast::Transactions(_) => {}
+                        }
+                    }
+                }
+            }
+            task::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("nodes didn't reach height {}", height);
+    }
+
+    #[async_std::test]
+    async fn test_commit_blocks() {
+        let nodes = setup(&[1, 1, 1, 1]);
+        for node in &nodes {
+            node.consensus.move_to_new_height();
+        }
+
+        run_until(&nodes, 2).await;
+
+        let hash = nodes[0].state.last_block_hash();
+        for node in &nodes {
+            assert_eq!(node.state.last_block_hash(), hash);
+            let cert = node.state.last_certificate().unwrap();
+            assert_eq!(cert.committers(), &[0, 1, 2, 3]);
+            assert!(cert.absentees().len() <= 1);
+        }
+    }
+
+    #[async_std::test]
+    async fn test_multiple_signers() {
+        let nodes = setup(&[2, 1, 1]);
+        for node in &nodes {
+            node.consensus.move_to_new_height();
+        }
+
+        run_until(&nodes, 2).await;
+
+        let hash = nodes[0].state.last_block_hash();
+        for node in &nodes {
+            assert_eq!(node.state.last_block_hash(), hash);
+        }
+        let cert = nodes[0].state.last_certificate().unwrap();
+        assert!(!cert.absentees().contains(&0));
+        assert!(!cert.absentees().contains(&1));
+    }
+
+    #[async_std::test]
+    async fn test_double_sign_evidence() {
+        let nodes = setup(&[1, 1, 1, 1]);
+        nodes[0].consensus.move_to_new_height();
+
+        let signer = &nodes[1].signers[0];
+        let mut votes = [b"block1", b"block2"].map(|data| {
+            Vote::new(
+                VoteType::Precommit,
+                1,
+                0,
+                Hash32::calculate(data),
+                signer.address(),
+            )
+        });
+        for vote in votes.iter_mut() {
+            signer.sign(vote);
+        }
+        let [first, second] = votes;
+        nodes[0].consensus.add_vote(first).unwrap();
+        assert!(matches!(
+            nodes[0].consensus.add_vote(second),
+            Err(Error::DoubleSign(_))
+        ));
+
+        let evidences = nodes[0].evidence_pool.evidences_of(&signer.address());
+        assert_eq!(evidences.len(), 1);
+        assert!(evidences[0].verify(&signer.public_key()).is_ok());
+    }
+
+    #[async_std::test]
+    async fn test_add_invalid_votes() {
+        let nodes = setup(&[1, 1]);
+        nodes[0].consensus.move_to_new_height();
+        let member = &nodes[1].signers[0];
+        let outsider = KeySigner::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let vote = |signer: &KeySigner, address, round| {
+            let mut vote = Vote::new(VoteType::Prepare, 1, round, Hash32::UNDEF, address);
+            signer.sign(&mut vote);
+            vote
+        };
+
+        // A signer outside the committee
+        let result = nodes[0].consensus.add_vote(vote(&outsider, outsider.address(), 1));
+        assert!(matches!(result, Err(Error::InvalidVote(_))));
+        // A member address with a signature of another key
+        let result = nodes[0].consensus.add_vote(vote(&outsider, member.address(), 1));
+        assert!(result.is_err());
+        // A valid vote for a far round
+        let far_round = MAX_ROUNDS_AHEAD + 1;
+        let result = nodes[0].consensus.add_vote(vote(member, member.address(), far_round));
+        assert!(result.is_ok());
+        // A change-proposer vote of another height, its signature is valid at this height too
+        let mut cp_vote =
+            Vote::new(VoteType::ChangeProposer, 2, 0, Hash32::UNDEF, member.address());
+        member.sign(&mut cp_vote);
+        assert!(nodes[0].consensus.add_vote(cp_vote).is_ok());
+
+        // None of them created a vote set or queried the votes
+        let inner = nodes[0].consensus.inner.lock().unwrap();
+        assert!(inner.votes.is_empty());
+        assert_eq!(inner.queried_votes_round, -1);
+    }
+}
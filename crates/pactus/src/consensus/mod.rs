@@ -0,0 +1,67 @@
+pub mod committee;
+pub mod config;
+#[allow(clippy::module_inception)]
+mod consensus;
+pub mod vote_set;
+
+use self::consensus::PactusConsensus;
+use crate::error::Result;
+use crate::event::EventBus;
+use crate::evidence::EvidencePool;
+use crate::signer::Signer;
+use crate::state::State;
+use crate::txpool::TxPool;
+use async_std::channel::Sender;
+use pactus_types::proposal::Proposal;
+use pactus_types::transaction::Transaction;
+use pactus_types::vote::Vote;
+use std::sync::Arc;
+
+/// The messages that consensus and the transaction pool ask to be broadcast to the network.
+#[derive(Debug)]
+pub enum Broadcast {
+    Proposal(Box<Proposal>),
+    Vote(Vote),
+    /// Asks the peers for the proposal of this round.
+    QueryProposal { height: i32, round: i32 },
+    /// Asks the peers for the votes of this round.
+    QueryVotes { height: i32, round: i32 },
+    /// A block is committed at this height.
+    BlockAnnounce {
+        height: i32,
+    },
+    /// New transactions are submitted to the pool.
+    Transactions(Vec<Transaction>),
+}
+
+pub trait Consensus: Send + Sync {
+    /// Moves to the next height if a new block is committed.
+    fn move_to_new_height(&self);
+    fn height_round(&self) -> (i32, i32);
+    fn add_vote(&self, vote: Vote) -> Result<()>;
+    fn set_proposal(&self, proposal: Proposal) -> Result<()>;
+    /// Returns the proposal of the round, if we have it.
+    fn proposal(&self, height: i32, round: i32) -> Option<Proposal>;
+    /// Returns the votes that we have for the round.
+    fn votes(&self, height: i32, round: i32) -> Vec<Vote>;
+}
+
+pub fn create_consensus(
+    config: config::Config,
+    signers: Vec<Arc<dyn Signer>>,
+    state: Arc<dyn State>,
+    tx_pool: Arc<dyn TxPool>,
+    evidence_pool: Arc<dyn EvidencePool>,
+    broadcaster: Sender<Broadcast>,
+    event_bus: Arc<dyn EventBus>,
+) -> Result<Arc<dyn Consensus>> {
+    Ok(PactusConsensus::new(
+        config,
+        signers,
+        state,
+        tx_pool,
+        evidence_pool,
+        broadcaster,
+        event_bus,
+    ))
+}
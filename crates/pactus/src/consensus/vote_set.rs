@@ -0,0 +1,253 @@
+use super::committee::Committee;
+use crate::error::{Error, Result};
+use pactus_types::address::Address;
+use pactus_types::certificate::Certificate;
+use pactus_types::crypto::bls::signature::Signature;
+use pactus_types::hash::Hash32;
+use pactus_types::vote::{Vote, VoteType};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Collects the votes of the committee for one height, round and vote type.
+pub struct VoteSet {
+    committee: Arc<Committee>,
+    vote_type: VoteType,
+    height: i32,
+    round: i32,
+    votes: HashMap<Address, Vote>,
+    /// Accumulated power of the votes, per block hash.
+    block_powers: HashMap<Hash32, i64>,
+    total_power: i64,
+}
+
+impl VoteSet {
+    pub fn new(committee: Arc<Committee>, vote_type: VoteType, height: i32, round: i32) -> Self {
+        Self {
+            committee,
+            vote_type,
+            height,
+            round,
+            votes: HashMap::new(),
+            block_powers: HashMap::new(),
+            total_power: 0,
+        }
+    }
+
+    pub fn vote_type(&self) -> VoteType {
+        self.vote_type
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn round(&self) -> i32 {
+        self.round
+    }
+
+    /// Adds a vote to the set. It returns false if the vote is already in the set,
+    /// and an error if the signer has voted for another block.
+    pub fn add_vote(&mut self, vote: Vote) -> Result<bool> {
+        if vote.vote_type() != self.vote_type
+            || vote.height() != self.height
+            || vote.round() != self.round
+        {
+            return Err(Error::InvalidVote(format!(
+                "vote doesn't belong to this set: {:?}/{}/{}",
+                vote.vote_type(),
+                vote.height(),
+                vote.round()
+            )));
+        }
+        let member = self.committee.member(vote.signer()).ok_or_else(|| {
+            Error::InvalidVote(format!(
+                "signer is not in the committee: {:?}",
+                vote.signer()
+            ))
+        })?;
+        vote.verify(&member.public_key)?;
+
+        if let Some(existing) = self.votes.get(vote.signer()) {
+            if existing.block_hash() == vote.block_hash() {
+                return Ok(false);
+            }
+            return Err(Error::DoubleSign(format!(
+                "validator {} voted for {:?} and {:?}",
+                member.number,
+                existing.block_hash(),
+                vote.block_hash()
+            )));
+        }
+
+        let power = member.power;
+        *self
+            .block_powers
+            .entry(vote.block_hash().clone())
+            .or_default() += power;
+        self.total_power += power;
+        self.votes.insert(vote.signer().clone(), vote);
+        Ok(true)
+    }
+
+    /// Returns the vote of the validator that conflicts with the given vote, if any.
+    pub fn conflicting_vote(&self, vote: &Vote) -> Option<&Vote> {
+        self.votes
+            .get(vote.signer())
+            .filter(|existing| existing.block_hash() != vote.block_hash())
+    }
+
+    pub fn has_voted(&self, address: &Address) -> bool {
+        self.votes.contains_key(address)
+    }
+
+    pub fn votes(&self) -> impl Iterator<Item = &Vote> {
+        self.votes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.votes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.votes.is_empty()
+    }
+
+    /// Returns the accumulated power of the votes.
+    pub fn power(&self) -> i64 {
+        self.total_power
+    }
+
+    /// Returns the block hash that has the votes of the quorum, if any.
+    pub fn quorum_hash(&self) -> Option<Hash32> {
+        self.block_powers
+            .iter()
+            .find(|(_, power)| self.committee.is_quorum(**power))
+            .map(|(hash, _)| hash.clone())
+    }
+
+    /// Returns true if the quorum has voted, regardless of the block hash.
+    pub fn has_quorum(&self) -> bool {
+        self.committee.is_quorum(self.total_power)
+    }
+
+    /// Aggregates the votes for the quorum hash into a certificate.
+    pub fn to_certificate(&self) -> Option<Certificate> {
+        let block_hash = self.quorum_hash()?;
+        let mut committers = Vec::new();
+        let mut absentees = Vec::new();
+        let mut sigs = Vec::new();
+        for member in self.committee.members() {
+            committers.push(member.number);
+            match self.votes.get(&member.address) {
+                Some(vote) if vote.block_hash() == &block_hash => {
+                    sigs.push(vote.signature().ok()?);
+                }
+                _ => absentees.push(member.number),
+            }
+        }
+        committers.sort_unstable();
+        absentees.sort_unstable();
+
+        Some(Certificate::new(
+            block_hash,
+            self.round,
+            committers,
+            absentees,
+            &Signature::aggregate(&sigs),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::bls::public_key::PublicKey as BLSPublicKey;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::public_key::PublicKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::validator::Validator;
+
+    fn setup(count: usize) -> (Vec<Signer>, Arc<Committee>) {
+        let signers: Vec<Signer> = (0..count)
+            .map(|_| Signer::new(CryptoSecretKey::BLS(SecretKey::random())))
+            .collect();
+        let validators: Vec<Validator> = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| {
+                let PublicKey::BLS(pk) = signer.public_key();
+                Validator::new(&pk, i as i32, 1)
+            })
+            .collect();
+        (signers, Arc::new(Committee::new(&validators).unwrap()))
+    }
+
+    fn vote(signer: &Signer, block_hash: &Hash32) -> Vote {
+        let mut vote = Vote::new(
+            VoteType::Precommit,
+            1,
+            0,
+            block_hash.clone(),
+            signer.address(),
+        );
+        signer.sign(&mut vote);
+        vote
+    }
+
+    #[test]
+    fn test_duplicated_and_double_sign() {
+        let (signers, committee) = setup(4);
+        let mut vote_set = VoteSet::new(committee, VoteType::Precommit, 1, 0);
+        let hash1 = Hash32::calculate(b"block1");
+        let hash2 = Hash32::calculate(b"block2");
+
+        assert!(vote_set.add_vote(vote(&signers[0], &hash1)).unwrap());
+        assert!(!vote_set.add_vote(vote(&signers[0], &hash1)).unwrap());
+        assert_eq!(vote_set.power(), 1);
+
+        let conflicting = vote(&signers[0], &hash2);
+        assert!(vote_set.conflicting_vote(&conflicting).is_some());
+        assert!(matches!(
+            vote_set.add_vote(conflicting),
+            Err(Error::DoubleSign(_))
+        ));
+        assert_eq!(vote_set.len(), 1);
+        assert_eq!(vote_set.power(), 1);
+
+        let outsider = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        assert!(vote_set.add_vote(vote(&outsider, &hash1)).is_err());
+    }
+
+    #[test]
+    fn test_certificate() {
+        let (signers, committee) = setup(4);
+        let mut vote_set = VoteSet::new(committee, VoteType::Precommit, 1, 0);
+        let hash = Hash32::calculate(b"block");
+
+        vote_set.add_vote(vote(&signers[3], &hash)).unwrap();
+        vote_set.add_vote(vote(&signers[1], &hash)).unwrap();
+        vote_set
+            .add_vote(vote(&signers[2], &Hash32::calculate(b"other")))
+            .unwrap();
+        assert!(vote_set.has_quorum());
+        assert!(vote_set.quorum_hash().is_none());
+        assert!(vote_set.to_certificate().is_none());
+
+        vote_set.add_vote(vote(&signers[0], &hash)).unwrap();
+        let cert = vote_set.to_certificate().unwrap();
+        assert_eq!(cert.block_hash(), &hash);
+        assert_eq!(cert.committers(), &[0, 1, 2, 3]);
+        assert_eq!(cert.absentees(), &[2]);
+
+        let pks: Vec<BLSPublicKey> = [0, 1, 3]
+            .iter()
+            .map(|i| {
+                let PublicKey::BLS(pk) = signers[*i].public_key();
+                pk
+            })
+            .collect();
+        let sign_bytes = Certificate::sign_bytes(&hash, 0);
+        assert!(BLSPublicKey::aggregate(&pks).verify(&cert.signature().unwrap(), &sign_bytes));
+    }
+}
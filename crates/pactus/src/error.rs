@@ -11,6 +11,26 @@ pub enum Error {
     NetworkError(String),
     #[error("invalid message: {0}")]
     InvalidMessage(String),
+    #[error("invalid block: {0}")]
+    InvalidBlock(String),
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("invalid vote: {0}")]
+    InvalidVote(String),
+    #[error("double signing: {0}")]
+    DoubleSign(String),
+    #[error("invalid proposal: {0}")]
+    InvalidProposal(String),
+    #[error("slashing protection: {0}")]
+    SlashingProtection(String),
+    #[error("signer error: {0}")]
+    SignerError(String),
+    #[error("types error: {0}")]
+    TypesError(String),
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
+    #[error("io error: {0}")]
+    IoError(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -26,3 +46,21 @@ impl From<decode::Error> for Error {
         Error::DecodeError(format!("{}", err))
     }
 }
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::IoError(format!("{}", err))
+    }
+}
+
+impl From<snow::Error> for Error {
+    fn from(err: snow::Error) -> Self {
+        Error::SignerError(format!("{}", err))
+    }
+}
+
+impl From<pactus_types::error::Error> for Error {
+    fn from(err: pactus_types::error::Error) -> Self {
+        Error::TypesError(format!("{}", err))
+    }
+}
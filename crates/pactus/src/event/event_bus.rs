@@ -0,0 +1,149 @@
+use super::{Event, EventBus, Filter};
+use async_std::channel::{Receiver, Sender, TrySendError};
+use log::warn;
+use std::sync::Mutex;
+
+/// Number of the events that a subscriber can fall behind, the next events are dropped for it.
+const SUBSCRIBER_CAPACITY: usize = 1024;
+
+struct Subscriber {
+    filter: Filter,
+    sender: Sender<Event>,
+}
+
+pub(super) struct PactusEventBus {
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl PactusEventBus {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn add_subscriber(&self, filter: Filter, sender: Sender<Event>) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(Subscriber { filter, sender });
+    }
+}
+
+impl EventBus for PactusEventBus {
+    fn publish(&self, event: Event) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|subscriber| {
+            if !subscriber.filter.matches(&event) {
+                return !subscriber.sender.is_closed();
+            }
+            match subscriber.sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    warn!(
+                        "event subscriber is too slow, {:?} event is dropped",
+                        event.topic()
+                    );
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            }
+        });
+    }
+
+    fn subscribe(&self, filter: Filter) -> Receiver<Event> {
+        let (sender, receiver) = async_std::channel::bounded(SUBSCRIBER_CAPACITY);
+        self.add_subscriber(filter, sender);
+        receiver
+    }
+
+    fn subscribe_lossless(&self, filter: Filter) -> Receiver<Event> {
+        let (sender, receiver) = async_std::channel::unbounded();
+        self.add_subscriber(filter, sender);
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Topic;
+    use libp2p::PeerId;
+    use pactus_types::crypto::bls::secret_key::SecretKey as BLSSecretKey;
+    use pactus_types::crypto::secret_key::SecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+    use pactus_types::transaction::payload::Type as PayloadType;
+    use pactus_types::transaction::Transaction;
+
+    fn send_tx() -> Transaction {
+        let signer = Signer::new(SecretKey::BLS(BLSSecretKey::random()));
+        let receiver = Signer::new(SecretKey::BLS(BLSSecretKey::random())).address();
+        let payload = SendPayload::new(signer.address(), receiver, 1000);
+        Transaction::new(
+            Stamp::from_bytes(&[1, 2, 3, 4]).unwrap(),
+            1,
+            1000,
+            "".to_string(),
+            Box::new(payload),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_filters() {
+        let bus = PactusEventBus::new();
+        let tx = send_tx();
+        let all = bus.subscribe(Filter::default());
+        let peers = bus.subscribe(Filter {
+            topics: vec![Topic::Peer],
+            ..Default::default()
+        });
+        let by_receiver = bus.subscribe(Filter {
+            topics: vec![Topic::Transaction],
+            addresses: vec![tx.payload.receiver().unwrap().clone()],
+            ..Default::default()
+        });
+        let by_type = bus.subscribe(Filter {
+            topics: vec![Topic::Transaction],
+            payload_types: vec![PayloadType::Bond],
+            ..Default::default()
+        });
+
+        bus.publish(Event::PeerJoined(PeerId::random()));
+        bus.publish(Event::NewTransaction(tx));
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(peers.len(), 1);
+        assert!(matches!(
+            by_receiver.try_recv(),
+            Ok(Event::NewTransaction(_))
+        ));
+        assert!(by_receiver.is_empty());
+        assert!(by_type.is_empty());
+    }
+
+    #[test]
+    fn test_lossless() {
+        let bus = PactusEventBus::new();
+        let bounded = bus.subscribe(Filter::default());
+        let lossless = bus.subscribe_lossless(Filter::default());
+
+        for _ in 0..SUBSCRIBER_CAPACITY + 1 {
+            bus.publish(Event::PeerJoined(PeerId::random()));
+        }
+        assert_eq!(bounded.len(), SUBSCRIBER_CAPACITY);
+        assert_eq!(lossless.len(), SUBSCRIBER_CAPACITY + 1);
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let bus = PactusEventBus::new();
+        let receiver = bus.subscribe(Filter::default());
+        drop(receiver);
+        bus.publish(Event::PeerLeft(PeerId::random()));
+        assert!(bus.subscribers.lock().unwrap().is_empty());
+    }
+}
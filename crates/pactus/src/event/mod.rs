@@ -0,0 +1,103 @@
+#[allow(clippy::module_inception)]
+pub mod event_bus;
+
+use self::event_bus::PactusEventBus;
+use async_std::channel::Receiver;
+use libp2p::PeerId;
+use pactus_types::address::Address;
+use pactus_types::block::Block;
+use pactus_types::transaction::payload::Type as PayloadType;
+use pactus_types::transaction::Transaction;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Block,
+    Transaction,
+    Peer,
+    ConsensusStep,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A block is committed.
+    NewBlock {
+        height: i32,
+        block: Block,
+    },
+    /// A transaction entered the pool.
+    NewTransaction(Transaction),
+    PeerJoined(PeerId),
+    PeerLeft(PeerId),
+    /// Consensus moved to another step.
+    ConsensusStep {
+        height: i32,
+        round: i32,
+        step: String,
+    },
+}
+
+impl Event {
+    pub fn topic(&self) -> Topic {
+        match self {
+            Event::NewBlock { .. } => Topic::Block,
+            Event::NewTransaction(_) => Topic::Transaction,
+            Event::PeerJoined(_) | Event::PeerLeft(_) => Topic::Peer,
+            Event::ConsensusStep { .. } => Topic::ConsensusStep,
+        }
+    }
+}
+
+/// Selects the events of a subscription. Empty lists match everything.
+///
+/// The addresses and the payload types filter the transactions, and the blocks that include them.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub topics: Vec<Topic>,
+    pub addresses: Vec<Address>,
+    pub payload_types: Vec<PayloadType>,
+}
+
+impl Filter {
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.topics.is_empty() && !self.topics.contains(&event.topic()) {
+            return false;
+        }
+        match event {
+            Event::NewBlock { block, .. } => {
+                !self.filters_transactions()
+                    || block.transactions().iter().any(|tx| self.matches_tx(tx))
+            }
+            Event::NewTransaction(tx) => self.matches_tx(tx),
+            _ => true,
+        }
+    }
+
+    fn filters_transactions(&self) -> bool {
+        !self.addresses.is_empty() || !self.payload_types.is_empty()
+    }
+
+    fn matches_tx(&self, tx: &Transaction) -> bool {
+        let payload = &tx.payload;
+        let address_matched = self.addresses.is_empty()
+            || self
+                .addresses
+                .iter()
+                .any(|addr| payload.signer() == addr || payload.receiver() == Some(addr));
+        let type_matched =
+            self.payload_types.is_empty() || self.payload_types.contains(&payload.payload_type());
+        address_matched && type_matched
+    }
+}
+
+pub trait EventBus: Send + Sync {
+    fn publish(&self, event: Event);
+    /// Subscribes to the events that match the filter, until the receiver is dropped.
+    fn subscribe(&self, filter: Filter) -> Receiver<Event>;
+    /// Subscribes like `subscribe`, but no event is dropped if the subscriber falls behind.
+    fn subscribe_lossless(&self, filter: Filter) -> Receiver<Event>;
+}
+
+pub fn create_event_bus() -> Arc<dyn EventBus> {
+    Arc::new(PactusEventBus::new())
+}
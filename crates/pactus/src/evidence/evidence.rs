@@ -0,0 +1,172 @@
+use super::EvidencePool;
+use crate::error::Result;
+use log::warn;
+use pactus_types::address::Address;
+use pactus_types::evidence::Evidence;
+use pactus_types::hash::Hash32;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+#[derive(Default)]
+struct Pool {
+    hashes: HashSet<Hash32>,
+    evidences: Vec<Evidence>,
+}
+
+/// Keeps the evidences in memory and appends them to the file, one CBOR item after another.
+pub(super) struct PactusEvidencePool {
+    file: Option<PathBuf>,
+    pool: RwLock<Pool>,
+}
+
+impl PactusEvidencePool {
+    pub fn new(file: Option<PathBuf>) -> Result<Self> {
+        let mut pool = Pool::default();
+        if let Some(path) = &file {
+            if path.exists() {
+                let data = fs::read(path)?;
+                let mut decoder = minicbor::Decoder::new(&data);
+                while decoder.position() < data.len() {
+                    let position = decoder.position();
+                    let evidence: Evidence = match decoder.decode() {
+                        Ok(evidence) => evidence,
+                        Err(err) => {
+                            // A crash while appending cuts off the last evidence, it is dropped
+                            // so the next evidences are appended after the valid ones.
+                            warn!("dropping the last evidence in {:?}: {}", path, err);
+                            OpenOptions::new()
+                                .write(true)
+                                .open(path)?
+                                .set_len(position as u64)?;
+                            break;
+                        }
+                    };
+                    pool.hashes.insert(evidence.hash()?);
+                    pool.evidences.push(evidence);
+                }
+            }
+        }
+
+        Ok(Self {
+            file,
+            pool: RwLock::new(pool),
+        })
+    }
+
+    fn persist(&self, evidence: &Evidence) -> Result<()> {
+        if let Some(path) = &self.file {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(&evidence.to_bytes()?)?;
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+impl EvidencePool for PactusEvidencePool {
+    fn add_evidence(&self, evidence: Evidence) -> Result<bool> {
+        evidence.basic_check()?;
+        let hash = evidence.hash()?;
+        let mut pool = self.pool.write().unwrap();
+        if pool.hashes.contains(&hash) {
+            return Ok(false);
+        }
+        self.persist(&evidence)?;
+        pool.hashes.insert(hash);
+        pool.evidences.push(evidence);
+        Ok(true)
+    }
+
+    fn evidences(&self) -> Vec<Evidence> {
+        self.pool.read().unwrap().evidences.clone()
+    }
+
+    fn evidences_of(&self, offender: &Address) -> Vec<Evidence> {
+        self.pool
+            .read()
+            .unwrap()
+            .evidences
+            .iter()
+            .filter(|evidence| evidence.offender() == offender)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::vote::{Vote, VoteType};
+
+    fn evidence(signer: &Signer, round: i32) -> Evidence {
+        let mut votes = [b"block1", b"block2"].map(|data| {
+            Vote::new(
+                VoteType::Precommit,
+                1,
+                round,
+                Hash32::calculate(data),
+                signer.address(),
+            )
+        });
+        for vote in votes.iter_mut() {
+            signer.sign(vote);
+        }
+        let [first, second] = votes;
+        Evidence::DuplicateVote { first, second }
+    }
+
+    #[test]
+    fn test_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("evidences");
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+
+        let pool = PactusEvidencePool::new(Some(file.clone())).unwrap();
+        assert!(pool.add_evidence(evidence(&signer, 0)).unwrap());
+        assert!(!pool.add_evidence(evidence(&signer, 0)).unwrap());
+        assert!(pool.add_evidence(evidence(&signer, 1)).unwrap());
+
+        let pool = PactusEvidencePool::new(Some(file)).unwrap();
+        assert_eq!(pool.evidences().len(), 2);
+        assert_eq!(pool.evidences_of(&signer.address()).len(), 2);
+        assert!(!pool.add_evidence(evidence(&signer, 1)).unwrap());
+
+        let other = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        assert!(pool.evidences_of(&other.address()).is_empty());
+    }
+
+    #[test]
+    fn test_cut_off_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("evidences");
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+
+        let pool = PactusEvidencePool::new(Some(file.clone())).unwrap();
+        pool.add_evidence(evidence(&signer, 0)).unwrap();
+        pool.add_evidence(evidence(&signer, 1)).unwrap();
+        // The last evidence is not written completely
+        let len = fs::metadata(&file).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_len(len - 10)
+            .unwrap();
+
+        let pool = PactusEvidencePool::new(Some(file.clone())).unwrap();
+        assert_eq!(pool.evidences().len(), 1);
+        assert!(pool.add_evidence(evidence(&signer, 1)).unwrap());
+
+        let pool = PactusEvidencePool::new(Some(file)).unwrap();
+        assert_eq!(pool.evidences().len(), 2);
+    }
+}
@@ -0,0 +1,23 @@
+#[allow(clippy::module_inception)]
+mod evidence;
+
+use self::evidence::PactusEvidencePool;
+use crate::error::Result;
+use pactus_types::address::Address;
+use pactus_types::evidence::Evidence;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub trait EvidencePool: Send + Sync {
+    /// Adds the evidence to the pool. It returns false if the evidence is known already.
+    fn add_evidence(&self, evidence: Evidence) -> Result<bool>;
+    /// Returns all the evidences, in the order they were found.
+    fn evidences(&self) -> Vec<Evidence>;
+    /// Returns the evidences against the validator.
+    fn evidences_of(&self, offender: &Address) -> Vec<Evidence>;
+}
+
+/// Creates the evidence pool. If a file is given, the evidences are persisted there.
+pub fn create_evidence_pool(file: Option<PathBuf>) -> Result<Arc<dyn EvidencePool>> {
+    Ok(Arc::new(PactusEvidencePool::new(file)?))
+}
@@ -0,0 +1,127 @@
+use crate::error::{Error, Result};
+use pactus_types::account::Account;
+use pactus_types::address::Address;
+use pactus_types::crypto::bls::public_key::PublicKey;
+use pactus_types::hash::Hash32;
+use pactus_types::validator::Validator;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The genesis file is kept in the working directory of the node and the signer.
+pub const GENESIS_FILE_NAME: &str = "genesis.json";
+
+/// An account with an initial balance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisAccount {
+    pub address: Address,
+    pub balance: i64,
+}
+
+/// A validator of the first committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisValidator {
+    /// Hex encoded BLS public key.
+    pub public_key: String,
+    pub stake: i64,
+}
+
+/// The genesis of the network. Nodes with different genesis files can't talk to each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Genesis {
+    /// Unix time of the genesis, in seconds.
+    pub genesis_time: i64,
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+    pub validators: Vec<GenesisValidator>,
+}
+
+impl Genesis {
+    pub fn load(path: &Path) -> Result<Self> {
+        serde_json::from_slice(&fs::read(path)?).map_err(|err| Error::DecodeError(err.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data =
+            serde_json::to_vec_pretty(self).map_err(|err| Error::EncodeError(err.to_string()))?;
+        Ok(fs::write(path, data)?)
+    }
+
+    /// The hash of the JSON encoded genesis, so it doesn't depend on the formatting of the file.
+    pub fn hash(&self) -> Result<Hash32> {
+        let data = serde_json::to_vec(self).map_err(|err| Error::EncodeError(err.to_string()))?;
+        Ok(Hash32::calculate(&data))
+    }
+
+    /// Returns the initial accounts. The accounts are numbered in the order of the file.
+    pub fn accounts(&self) -> Vec<Account> {
+        self.accounts
+            .iter()
+            .enumerate()
+            .map(|(number, acc)| Account {
+                address: acc.address.clone(),
+                number: number as i32,
+                sequence: 0,
+                balance: acc.balance,
+            })
+            .collect()
+    }
+
+    /// Returns the first committee. The validators are numbered in the order of the file.
+    pub fn committee(&self) -> Result<Vec<Validator>> {
+        self.validators
+            .iter()
+            .enumerate()
+            .map(|(number, val)| {
+                let public_key = PublicKey::from_string(&val.public_key)?;
+                Ok(Validator::new(&public_key, number as i32, val.stake))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+
+    #[test]
+    fn test_genesis() {
+        let public_key = SecretKey::random().public_key();
+        let genesis = Genesis {
+            genesis_time: 1_700_000_000,
+            accounts: vec![GenesisAccount {
+                address: Address::from_bytes(&[2; 21]).unwrap(),
+                balance: 5000,
+            }],
+            validators: vec![GenesisValidator {
+                public_key: public_key.to_string(),
+                stake: 1000,
+            }],
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(GENESIS_FILE_NAME);
+        genesis.save(&path).unwrap();
+        let loaded = Genesis::load(&path).unwrap();
+        assert_eq!(loaded, genesis);
+        assert_eq!(loaded.hash().unwrap(), genesis.hash().unwrap());
+
+        let committee = loaded.committee().unwrap();
+        assert_eq!(committee.len(), 1);
+        assert_eq!(committee[0].number(), 0);
+        assert_eq!(committee[0].stake(), 1000);
+        assert_eq!(committee[0].public_key().unwrap(), public_key);
+
+        let accounts = loaded.accounts();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].number, 0);
+        assert_eq!(accounts[0].balance, 5000);
+
+        let other = Genesis {
+            genesis_time: 1_700_000_001,
+            ..genesis
+        };
+        assert_ne!(other.hash().unwrap(), loaded.hash().unwrap());
+    }
+}
@@ -0,0 +1,348 @@
+use super::proto::blockchain_server::Blockchain;
+use super::proto::{
+    AccountInfo, BlockHeaderInfo, BlockVerbosity, CertificateInfo, EvidenceInfo,
+    GetAccountRequest, GetAccountResponse, GetBlockRequest, GetBlockResponse,
+    GetBlockchainInfoRequest, GetBlockchainInfoResponse, GetEvidencesRequest, GetEvidencesResponse,
+    GetValidatorRequest, GetValidatorResponse, TransactionVerbosity, ValidatorInfo,
+};
+use super::transaction::transaction_info;
+use super::{internal_error, invalid_argument};
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use pactus_types::account::Account;
+use pactus_types::address::Address;
+use pactus_types::block::Block;
+use pactus_types::certificate::Certificate;
+use pactus_types::evidence::Evidence;
+use pactus_types::hash::Hash32;
+use pactus_types::validator::Validator;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+pub(super) struct PactusBlockchain {
+    state: Arc<dyn State>,
+    evidence_pool: Arc<dyn EvidencePool>,
+}
+
+impl PactusBlockchain {
+    pub fn new(state: Arc<dyn State>, evidence_pool: Arc<dyn EvidencePool>) -> Self {
+        Self {
+            state,
+            evidence_pool,
+        }
+    }
+}
+
+/// Addresses are bech32m encoded in the requests and responses, the same as the Go implementation.
+pub(super) fn parse_address(address: &str) -> Result<Address, Status> {
+    Address::from_string(address).map_err(invalid_argument)
+}
+
+fn validator_info(val: &Validator) -> Result<ValidatorInfo, Status> {
+    let data = val.to_bytes().map_err(internal_error)?;
+    Ok(ValidatorInfo {
+        hash: Hash32::calculate(&data).as_bytes().to_vec(),
+        public_key: val.public_key().map_err(internal_error)?.to_bytes(),
+        number: val.number(),
+        stake: val.stake(),
+        address: val.address().map_err(internal_error)?.to_string(),
+        data,
+    })
+}
+
+fn account_info(acc: &Account) -> Result<AccountInfo, Status> {
+    let data = acc.to_bytes().map_err(internal_error)?;
+    Ok(AccountInfo {
+        hash: Hash32::calculate(&data).as_bytes().to_vec(),
+        number: acc.number,
+        sequence: acc.sequence,
+        balance: acc.balance,
+        address: acc.address.to_string(),
+        data,
+    })
+}
+
+fn certificate_info(cert: &Certificate) -> Result<CertificateInfo, Status> {
+    Ok(CertificateInfo {
+        hash: cert.hash().map_err(internal_error)?.as_bytes().to_vec(),
+        round: cert.round(),
+        committers: cert.committers().to_vec(),
+        absentees: cert.absentees().to_vec(),
+        signature: cert.signature().map_err(internal_error)?.to_bytes(),
+    })
+}
+
+fn evidence_info(evidence: &Evidence) -> Result<EvidenceInfo, Status> {
+    Ok(EvidenceInfo {
+        hash: evidence.hash().map_err(internal_error)?.as_bytes().to_vec(),
+        data: evidence.to_bytes().map_err(internal_error)?,
+        height: evidence.height(),
+        round: evidence.round(),
+        offender: evidence.offender().to_string(),
+    })
+}
+
+#[tonic::async_trait]
+impl Blockchain for PactusBlockchain {
+    async fn get_block(
+        &self,
+        request: Request<GetBlockRequest>,
+    ) -> Result<Response<GetBlockResponse>, Status> {
+        let request = request.into_inner();
+        let height = i32::try_from(request.height).map_err(invalid_argument)?;
+        let data = self
+            .state
+            .block_data(height)
+            .ok_or_else(|| Status::not_found("block not found"))?;
+        let block = Block::from_bytes(&data).map_err(internal_error)?;
+        let header = block.header();
+
+        let mut response = GetBlockResponse {
+            height: request.height,
+            hash: block.hash().map_err(internal_error)?.as_bytes().to_vec(),
+            block_time: header.unix_time() as u32,
+            ..Default::default()
+        };
+        let tx_verbosity = match request.verbosity() {
+            BlockVerbosity::BlockData => {
+                response.data = data;
+                return Ok(Response::new(response));
+            }
+            BlockVerbosity::BlockInfo => TransactionVerbosity::TransactionData,
+            BlockVerbosity::BlockTransactions => TransactionVerbosity::TransactionInfo,
+        };
+
+        response.header = Some(BlockHeaderInfo {
+            prev_block_hash: header.prev_block_hash().as_bytes().to_vec(),
+            sortition_seed: header.sortition_seed().as_bytes().to_vec(),
+            proposer_address: header.proposer_address().to_string(),
+        });
+        response.prev_cert = block.prev_cert().map(certificate_info).transpose()?;
+        response.txs = block
+            .transactions()
+            .iter()
+            .map(|tx| transaction_info(tx, tx_verbosity))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_blockchain_info(
+        &self,
+        _request: Request<GetBlockchainInfoRequest>,
+    ) -> Result<Response<GetBlockchainInfoResponse>, Status> {
+        let committee = self.state.committee_validators();
+        Ok(Response::new(GetBlockchainInfoResponse {
+            last_block_height: self.state.last_block_height() as u32,
+            last_block_hash: self.state.last_block_hash().as_bytes().to_vec(),
+            committee_power: committee.iter().map(|val| val.stake()).sum(),
+            committee_validators: committee
+                .iter()
+                .map(validator_info)
+                .collect::<Result<_, _>>()?,
+        }))
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<GetAccountRequest>,
+    ) -> Result<Response<GetAccountResponse>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+        let account = self
+            .state
+            .account(&address)
+            .ok_or_else(|| Status::not_found("account not found"))?;
+        Ok(Response::new(GetAccountResponse {
+            account: Some(account_info(&account)?),
+        }))
+    }
+
+    async fn get_validator(
+        &self,
+        request: Request<GetValidatorRequest>,
+    ) -> Result<Response<GetValidatorResponse>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+        // Only the committee validators are known to the state
+        for val in self.state.committee_validators() {
+            if val.address().map_err(internal_error)? == address {
+                return Ok(Response::new(GetValidatorResponse {
+                    validator: Some(validator_info(&val)?),
+                }));
+            }
+        }
+        Err(Status::not_found("validator not found"))
+    }
+
+    async fn get_evidences(
+        &self,
+        request: Request<GetEvidencesRequest>,
+    ) -> Result<Response<GetEvidencesResponse>, Status> {
+        let offender = request.into_inner().offender;
+        let evidences = if offender.is_empty() {
+            self.evidence_pool.evidences()
+        } else {
+            self.evidence_pool.evidences_of(&parse_address(&offender)?)
+        };
+        Ok(Response::new(GetEvidencesResponse {
+            evidences: evidences
+                .iter()
+                .map(evidence_info)
+                .collect::<Result<_, _>>()?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use crate::evidence::create_evidence_pool;
+    use crate::state::create_state;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::vote::{Vote, VoteType};
+
+    #[async_std::test]
+    async fn test_get_validator() {
+        let public_key = SecretKey::random().public_key();
+        let val = Validator::new(&public_key, 0, 1000);
+        let state =
+            create_state(Hash32::UNDEF, vec![val.clone()], Vec::new(), create_event_bus()).unwrap();
+        let blockchain = PactusBlockchain::new(state, create_evidence_pool(None).unwrap());
+
+        let info = blockchain
+            .get_blockchain_info(Request::new(GetBlockchainInfoRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(info.last_block_height, 0);
+        assert_eq!(info.committee_power, 1000);
+        assert_eq!(info.committee_validators.len(), 1);
+
+        let address = val.address().unwrap().to_string();
+        let res = blockchain
+            .get_validator(Request::new(GetValidatorRequest { address }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(res.validator.unwrap().data, val.to_bytes().unwrap());
+
+        let address = Address::from_bytes(&[1; 21]).unwrap().to_string();
+        let status = blockchain
+            .get_validator(Request::new(GetValidatorRequest { address }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let address = "invalid".to_string();
+        let status = blockchain
+            .get_validator(Request::new(GetValidatorRequest { address }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let request = GetBlockRequest {
+            height: 1,
+            verbosity: BlockVerbosity::BlockInfo as i32,
+        };
+        let status = blockchain
+            .get_block(Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[async_std::test]
+    async fn test_get_evidences() {
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let mut votes = [b"block1", b"block2"].map(|data| {
+            Vote::new(
+                VoteType::Precommit,
+                1,
+                0,
+                Hash32::calculate(data),
+                signer.address(),
+            )
+        });
+        for vote in votes.iter_mut() {
+            signer.sign(vote);
+        }
+        let [first, second] = votes;
+        let evidence = Evidence::DuplicateVote { first, second };
+
+        let evidence_pool = create_evidence_pool(None).unwrap();
+        evidence_pool.add_evidence(evidence.clone()).unwrap();
+        let state =
+            create_state(Hash32::UNDEF, Vec::new(), Vec::new(), create_event_bus()).unwrap();
+        let blockchain = PactusBlockchain::new(state, evidence_pool);
+
+        let request = GetEvidencesRequest {
+            offender: String::new(),
+        };
+        let res = blockchain
+            .get_evidences(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(res.evidences.len(), 1);
+        assert_eq!(res.evidences[0].data, evidence.to_bytes().unwrap());
+        assert_eq!(res.evidences[0].height, 1);
+
+        let offender = signer.address().to_string();
+        let res = blockchain
+            .get_evidences(Request::new(GetEvidencesRequest { offender }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(res.evidences.len(), 1);
+
+        let offender = Address::from_bytes(&[1; 21]).unwrap().to_string();
+        let res = blockchain
+            .get_evidences(Request::new(GetEvidencesRequest { offender }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(res.evidences.is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_get_account() {
+        let acc = Account {
+            address: Address::from_bytes(&[2; 21]).unwrap(),
+            number: 0,
+            sequence: 3,
+            balance: 5000,
+        };
+        let state =
+            create_state(Hash32::UNDEF, Vec::new(), vec![acc.clone()], create_event_bus()).unwrap();
+        let blockchain = PactusBlockchain::new(state, create_evidence_pool(None).unwrap());
+
+        let address = acc.address.to_string();
+        assert!(address.starts_with("pc1"));
+        let res = blockchain
+            .get_account(Request::new(GetAccountRequest { address }))
+            .await
+            .unwrap()
+            .into_inner();
+        let info = res.account.unwrap();
+        assert_eq!(info.data, acc.to_bytes().unwrap());
+        assert_eq!(info.address, acc.address.to_string());
+        assert_eq!(info.balance, 5000);
+
+        let address = Address::from_bytes(&[3; 21]).unwrap().to_string();
+        let status = blockchain
+            .get_account(Request::new(GetAccountRequest { address }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        // Hex addresses are not accepted anymore
+        let address = hex::encode(acc.address.as_bytes());
+        let status = blockchain
+            .get_account(Request::new(GetAccountRequest { address }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
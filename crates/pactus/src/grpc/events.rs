@@ -0,0 +1,154 @@
+use super::blockchain::parse_address;
+use super::proto::event_message::Event as EventKind;
+use super::proto::events_server::Events;
+use super::proto::{
+    ConsensusStepEvent, EventMessage, EventTopic, NewBlockEvent, NewTransactionEvent, PayloadType,
+    PeerEvent, SubscribeRequest, TransactionVerbosity,
+};
+use super::transaction::transaction_info;
+use super::{internal_error, invalid_argument};
+use crate::event::{Event, EventBus, Filter, Topic};
+use futures::stream::{Stream, StreamExt};
+use pactus_types::transaction::payload::Type;
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+pub(super) struct PactusEvents {
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl PactusEvents {
+    pub fn new(event_bus: Arc<dyn EventBus>) -> Self {
+        Self { event_bus }
+    }
+}
+
+fn parse_filter(request: &SubscribeRequest) -> Result<Filter, Status> {
+    let topics = request
+        .topics()
+        .map(|topic| match topic {
+            EventTopic::NewBlock => Topic::Block,
+            EventTopic::NewTransaction => Topic::Transaction,
+            EventTopic::Peer => Topic::Peer,
+            EventTopic::ConsensusStep => Topic::ConsensusStep,
+        })
+        .collect();
+    let addresses = request
+        .addresses
+        .iter()
+        .map(|address| parse_address(address))
+        .collect::<Result<_, _>>()?;
+    let payload_types = request
+        .payload_types()
+        .map(|payload_type| match payload_type {
+            PayloadType::Unknown => Err(invalid_argument("unknown payload type")),
+            PayloadType::SendPayload => Ok(Type::Send),
+            PayloadType::BondPayload => Ok(Type::Bond),
+            PayloadType::SortitionPayload => Ok(Type::Sortition),
+            PayloadType::UnbondPayload => Ok(Type::Unbond),
+            PayloadType::WithdrawPayload => Ok(Type::Withdraw),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Filter {
+        topics,
+        addresses,
+        payload_types,
+    })
+}
+
+fn event_message(event: Event) -> Result<EventMessage, Status> {
+    let event = match event {
+        Event::NewBlock { height, block } => EventKind::NewBlock(NewBlockEvent {
+            height: height as u32,
+            hash: block.hash().map_err(internal_error)?.as_bytes().to_vec(),
+            tx_ids: block
+                .transactions()
+                .iter()
+                .map(|tx| Ok(tx.id().map_err(internal_error)?.as_bytes().to_vec()))
+                .collect::<Result<_, Status>>()?,
+        }),
+        Event::NewTransaction(tx) => EventKind::NewTransaction(NewTransactionEvent {
+            transaction: Some(transaction_info(
+                &tx,
+                TransactionVerbosity::TransactionInfo,
+            )?),
+        }),
+        Event::PeerJoined(peer_id) => EventKind::PeerJoined(PeerEvent {
+            peer_id: peer_id.to_bytes(),
+        }),
+        Event::PeerLeft(peer_id) => EventKind::PeerLeft(PeerEvent {
+            peer_id: peer_id.to_bytes(),
+        }),
+        Event::ConsensusStep {
+            height,
+            round,
+            step,
+        } => EventKind::ConsensusStep(ConsensusStepEvent {
+            height: height as u32,
+            round,
+            step,
+        }),
+    };
+    Ok(EventMessage { event: Some(event) })
+}
+
+#[tonic::async_trait]
+impl Events for PactusEvents {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<EventMessage, Status>> + Send>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let filter = parse_filter(&request.into_inner())?;
+        // The subscription ends when the client drops the stream
+        let receiver = self.event_bus.subscribe(filter);
+        Ok(Response::new(Box::pin(receiver.map(event_message))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use libp2p::PeerId;
+
+    #[async_std::test]
+    async fn test_subscribe() {
+        let event_bus = create_event_bus();
+        let api = PactusEvents::new(event_bus.clone());
+
+        let request = SubscribeRequest {
+            topics: vec![EventTopic::Peer as i32],
+            ..Default::default()
+        };
+        let mut stream = api
+            .subscribe(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let peer_id = PeerId::random();
+        event_bus.publish(Event::ConsensusStep {
+            height: 1,
+            round: 0,
+            step: "Propose".to_string(),
+        });
+        event_bus.publish(Event::PeerJoined(peer_id));
+
+        let msg = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.event,
+            Some(EventKind::PeerJoined(PeerEvent {
+                peer_id: peer_id.to_bytes()
+            }))
+        );
+
+        let request = SubscribeRequest {
+            payload_types: vec![PayloadType::Unknown as i32],
+            ..Default::default()
+        };
+        assert!(api.subscribe(Request::new(request)).await.is_err());
+    }
+}
@@ -0,0 +1,50 @@
+pub mod config;
+
+mod blockchain;
+mod events;
+mod network;
+mod server;
+mod transaction;
+
+#[allow(clippy::all)]
+pub mod proto {
+    tonic::include_proto!("pactus");
+}
+
+use self::server::GrpcServer;
+use crate::error::Result;
+use crate::event::EventBus;
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use crate::sync::peerset::peerset::PeerSet;
+use crate::txpool::TxPool;
+use std::sync::{Arc, RwLock};
+use tonic::Status;
+
+pub fn create_grpc_server(
+    config: config::Config,
+    state: Arc<dyn State>,
+    tx_pool: Arc<dyn TxPool>,
+    evidence_pool: Arc<dyn EvidencePool>,
+    peer_set: Arc<RwLock<PeerSet>>,
+    network_name: String,
+    event_bus: Arc<dyn EventBus>,
+) -> Result<impl crate::Service> {
+    Ok(GrpcServer::new(
+        config,
+        state,
+        tx_pool,
+        evidence_pool,
+        peer_set,
+        network_name,
+        event_bus,
+    ))
+}
+
+fn internal_error<E: std::fmt::Display>(err: E) -> Status {
+    Status::internal(err.to_string())
+}
+
+fn invalid_argument<E: std::fmt::Display>(err: E) -> Status {
+    Status::invalid_argument(err.to_string())
+}
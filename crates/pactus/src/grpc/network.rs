@@ -0,0 +1,88 @@
+use super::proto::network_server::Network;
+use super::proto::{GetNetworkInfoRequest, GetNetworkInfoResponse, PeerInfo};
+use crate::sync::peerset::peer::{Peer, StatusCode};
+use crate::sync::peerset::peerset::PeerSet;
+use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
+use tonic::{Request, Response, Status};
+
+pub(super) struct PactusNetworkInfo {
+    peer_set: Arc<RwLock<PeerSet>>,
+    network_name: String,
+}
+
+impl PactusNetworkInfo {
+    pub fn new(peer_set: Arc<RwLock<PeerSet>>, network_name: String) -> Self {
+        Self {
+            peer_set,
+            network_name,
+        }
+    }
+}
+
+/// Returns the status code of the peer as the Go node reports it.
+fn status_code(status: StatusCode) -> i32 {
+    match status {
+        StatusCode::Banned => -1,
+        StatusCode::Unknown => 0,
+        StatusCode::Disconnected => 1,
+        StatusCode::Connected => 2,
+        StatusCode::Known => 3,
+    }
+}
+
+fn peer_info(peer: &Peer) -> PeerInfo {
+    PeerInfo {
+        status: status_code(peer.status),
+        moniker: peer.moniker.clone(),
+        agent: peer.agent.clone(),
+        peer_id: peer.peer_id.to_bytes(),
+        consensus_keys: peer.consensus_keys.iter().map(|pk| pk.to_bytes()).collect(),
+        height: peer.height as u32,
+        received_messages: peer.received_messages as i64,
+        invalid_messages: peer.invalid_messages as i64,
+        last_seen: peer
+            .last_seen
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default(),
+        received_bytes: peer.received_bytes as i64,
+        sent_bytes: peer.sent_bytes as i64,
+    }
+}
+
+#[tonic::async_trait]
+impl Network for PactusNetworkInfo {
+    async fn get_network_info(
+        &self,
+        _request: Request<GetNetworkInfoRequest>,
+    ) -> Result<Response<GetNetworkInfoResponse>, Status> {
+        let peer_set = self.peer_set.read().unwrap();
+        let peers: Vec<PeerInfo> = peer_set.peers().map(peer_info).collect();
+        Ok(Response::new(GetNetworkInfoResponse {
+            total_sent_bytes: peers.iter().map(|peer| peer.sent_bytes).sum(),
+            total_received_bytes: peers.iter().map(|peer| peer.received_bytes).sum(),
+            peers,
+            network_name: self.network_name.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::PeerId;
+
+    #[test]
+    fn test_status_code() {
+        assert_eq!(status_code(StatusCode::Banned), -1);
+        assert_eq!(status_code(StatusCode::Unknown), 0);
+        assert_eq!(status_code(StatusCode::Disconnected), 1);
+        assert_eq!(status_code(StatusCode::Connected), 2);
+        assert_eq!(status_code(StatusCode::Known), 3);
+
+        let mut peer = Peer::new(PeerId::random());
+        peer.status = StatusCode::Connected;
+        assert_eq!(peer_info(&peer).status, 2);
+    }
+}
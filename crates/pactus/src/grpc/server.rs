@@ -0,0 +1,77 @@
+use super::blockchain::PactusBlockchain;
+use super::config::Config;
+use super::events::PactusEvents;
+use super::network::PactusNetworkInfo;
+use super::proto::blockchain_server::BlockchainServer;
+use super::proto::events_server::EventsServer;
+use super::proto::network_server::NetworkServer;
+use super::proto::transaction_server::TransactionServer;
+use super::transaction::PactusTransaction;
+use crate::event::EventBus;
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use crate::sync::peerset::peerset::PeerSet;
+use crate::txpool::TxPool;
+use async_trait::async_trait;
+use log::{error, info};
+use std::sync::{Arc, RwLock};
+use tonic::transport::Server;
+
+pub(super) struct GrpcServer {
+    config: Config,
+    blockchain: PactusBlockchain,
+    transaction: PactusTransaction,
+    network: PactusNetworkInfo,
+    events: PactusEvents,
+}
+
+impl GrpcServer {
+    pub fn new(
+        config: Config,
+        state: Arc<dyn State>,
+        tx_pool: Arc<dyn TxPool>,
+        evidence_pool: Arc<dyn EvidencePool>,
+        peer_set: Arc<RwLock<PeerSet>>,
+        network_name: String,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            config,
+            blockchain: PactusBlockchain::new(state.clone(), evidence_pool),
+            transaction: PactusTransaction::new(state, tx_pool),
+            network: PactusNetworkInfo::new(peer_set, network_name),
+            events: PactusEvents::new(event_bus),
+        }
+    }
+}
+
+#[async_trait]
+impl crate::Service for GrpcServer {
+    async fn start(self) {
+        if !self.config.enable {
+            return;
+        }
+
+        // tonic runs on tokio, so the server gets its own runtime
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                error!("unable to start the grpc runtime: {}", err);
+                return;
+            }
+        };
+        let listen = self.config.listen;
+        let router = Server::builder()
+            .add_service(BlockchainServer::new(self.blockchain))
+            .add_service(TransactionServer::new(self.transaction))
+            .add_service(NetworkServer::new(self.network))
+            .add_service(EventsServer::new(self.events));
+
+        info!("grpc server is listening on {}", listen);
+        let result =
+            async_std::task::spawn_blocking(move || runtime.block_on(router.serve(listen))).await;
+        if let Err(err) = result {
+            error!("grpc server stopped: {}", err);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use super::proto::transaction_server::Transaction as TransactionApi;
+use super::proto::{
+    GetTransactionRequest, GetTransactionResponse, PayloadType, SendRawTransactionRequest,
+    SendRawTransactionResponse, TransactionInfo, TransactionVerbosity,
+};
+use super::{internal_error, invalid_argument};
+use crate::state::State;
+use crate::txpool::TxPool;
+use pactus_types::block::Block;
+use pactus_types::hash::Hash32;
+use pactus_types::transaction::payload::Type;
+use pactus_types::transaction::Transaction;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+pub(super) struct PactusTransaction {
+    state: Arc<dyn State>,
+    tx_pool: Arc<dyn TxPool>,
+}
+
+impl PactusTransaction {
+    pub fn new(state: Arc<dyn State>, tx_pool: Arc<dyn TxPool>) -> Self {
+        Self { state, tx_pool }
+    }
+}
+
+fn payload_type(tx: &Transaction) -> PayloadType {
+    match tx.payload.payload_type() {
+        Type::Send => PayloadType::SendPayload,
+        Type::Bond => PayloadType::BondPayload,
+        Type::Sortition => PayloadType::SortitionPayload,
+        Type::Unbond => PayloadType::UnbondPayload,
+        Type::Withdraw => PayloadType::WithdrawPayload,
+    }
+}
+
+/// The data verbosity has only the ID and the encoded transaction.
+pub(super) fn transaction_info(
+    tx: &Transaction,
+    verbosity: TransactionVerbosity,
+) -> Result<TransactionInfo, Status> {
+    let mut info = TransactionInfo {
+        id: tx.id().map_err(internal_error)?.as_bytes().to_vec(),
+        data: tx.to_bytes().map_err(internal_error)?,
+        ..Default::default()
+    };
+    if verbosity == TransactionVerbosity::TransactionInfo {
+        info.stamp = tx.stamp.as_bytes().to_vec();
+        info.sequence = tx.sequence;
+        info.value = tx.payload.value();
+        info.fee = tx.fee;
+        info.payload_type = payload_type(tx) as i32;
+        info.memo = tx.memo.clone();
+        info.public_key = tx
+            .public_key
+            .as_ref()
+            .map(|pk| pk.to_bytes())
+            .unwrap_or_default();
+        info.signature = tx
+            .signature
+            .as_ref()
+            .map(|sig| sig.to_bytes())
+            .unwrap_or_default();
+    }
+    Ok(info)
+}
+
+#[tonic::async_trait]
+impl TransactionApi for PactusTransaction {
+    async fn get_transaction(
+        &self,
+        request: Request<GetTransactionRequest>,
+    ) -> Result<Response<GetTransactionResponse>, Status> {
+        let request = request.into_inner();
+        let id = Hash32::from_bytes(&request.id).map_err(invalid_argument)?;
+        let not_found = || Status::not_found("transaction not found");
+        let height = self.state.transaction_height(&id).ok_or_else(not_found)?;
+        let data = self.state.block_data(height).ok_or_else(not_found)?;
+        let block = Block::from_bytes(&data).map_err(internal_error)?;
+        for tx in block.transactions().iter() {
+            if tx.id().map_err(internal_error)? == id {
+                return Ok(Response::new(GetTransactionResponse {
+                    transaction: Some(transaction_info(tx, request.verbosity())?),
+                    block_height: height as u32,
+                    block_time: block.header().unix_time() as u32,
+                }));
+            }
+        }
+        Err(not_found())
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        request: Request<SendRawTransactionRequest>,
+    ) -> Result<Response<SendRawTransactionResponse>, Status> {
+        let tx = Transaction::from_bytes(&request.into_inner().data).map_err(invalid_argument)?;
+        let id = tx.id().map_err(internal_error)?;
+        // The pool checks the signer, the signature, the sequence and the balance
+        self.tx_pool
+            .append_tx_and_broadcast(tx)
+            .map_err(invalid_argument)?;
+        Ok(Response::new(SendRawTransactionResponse {
+            id: id.as_bytes().to_vec(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use crate::state::create_state;
+    use crate::txpool::{config::Config, create_txpool};
+    use async_std::channel::unbounded;
+    use pactus_types::account::Account;
+    use pactus_types::address::Address;
+    use pactus_types::crypto::bls::secret_key::SecretKey as BLSSecretKey;
+    use pactus_types::crypto::secret_key::SecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+
+    fn random_signer() -> Signer {
+        Signer::new(SecretKey::BLS(BLSSecretKey::random()))
+    }
+
+    fn setup(funded: &[Address]) -> (PactusTransaction, Arc<dyn TxPool>) {
+        let event_bus = create_event_bus();
+        let accounts = funded
+            .iter()
+            .enumerate()
+            .map(|(number, address)| Account {
+                address: address.clone(),
+                number: number as i32,
+                sequence: 0,
+                balance: 5000,
+            })
+            .collect();
+        let state = create_state(Hash32::UNDEF, Vec::new(), accounts, event_bus.clone()).unwrap();
+        let (broadcaster, _) = unbounded();
+        let tx_pool = create_txpool(Config::default(), state.clone(), broadcaster, event_bus);
+        (PactusTransaction::new(state, tx_pool.clone()), tx_pool)
+    }
+
+    /// Makes a transaction that sends from `sender`, signed by `signer`.
+    fn send_tx(signer: &Signer, sender: Address, amount: i64) -> Transaction {
+        let payload = SendPayload::new(sender, Address::from_bytes(&[2; 21]).unwrap(), amount);
+        let mut tx = Transaction::new(
+            Stamp::from_block_hash(&Hash32::UNDEF),
+            1,
+            1000,
+            "test send-tx".to_string(),
+            Box::new(payload),
+            None,
+            None,
+        );
+        signer.sign(&mut tx);
+        tx
+    }
+
+    async fn send(api: &PactusTransaction, tx: &Transaction) -> Result<Hash32, Status> {
+        let data = tx.to_bytes().unwrap();
+        let res = api
+            .send_raw_transaction(Request::new(SendRawTransactionRequest { data }))
+            .await?
+            .into_inner();
+        Ok(Hash32::from_bytes(&res.id).unwrap())
+    }
+
+    #[async_std::test]
+    async fn test_send_raw_transaction() {
+        let signer = random_signer();
+        let (api, tx_pool) = setup(&[signer.address()]);
+
+        let tx = send_tx(&signer, signer.address(), 1000);
+        let data = tx.to_bytes().unwrap();
+        let id = Transaction::from_bytes(&data).unwrap().id().unwrap();
+        let res = api
+            .send_raw_transaction(Request::new(SendRawTransactionRequest { data }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(res.id, id.as_bytes().to_vec());
+        assert!(tx_pool.has_tx(&id));
+
+        let status = api
+            .send_raw_transaction(Request::new(SendRawTransactionRequest { data: vec![1, 2] }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        // The transaction is in the pool, but not committed yet
+        let request = GetTransactionRequest {
+            id: id.as_bytes().to_vec(),
+            verbosity: TransactionVerbosity::TransactionInfo as i32,
+        };
+        let status = api
+            .get_transaction(Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[async_std::test]
+    async fn test_send_foreign_transaction() {
+        let victim = random_signer();
+        let attacker = random_signer();
+        let (api, tx_pool) = setup(&[victim.address(), attacker.address()]);
+
+        // Signed by the attacker, but it spends the funds of the victim
+        let tx = send_tx(&attacker, victim.address(), 1000);
+        let status = send(&api, &tx).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(!tx_pool.has_tx(&tx.id().unwrap()));
+
+        let mut tx = send_tx(&victim, victim.address(), 1000);
+        tx.signature = None;
+        let status = send(&api, &tx).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        // More than the balance, with the fee
+        let tx = send_tx(&victim, victim.address(), 4500);
+        let status = send(&api, &tx).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let tx = send_tx(&victim, victim.address(), 1000);
+        assert_eq!(send(&api, &tx).await.unwrap(), tx.id().unwrap());
+        // The sequence is used by the pending transaction
+        let tx = send_tx(&victim, victim.address(), 2000);
+        let status = send(&api, &tx).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
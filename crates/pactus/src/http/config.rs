@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub enable: bool,
+    /// Address that the HTTP server listens on.
+    pub listen: SocketAddr,
+    /// Origins that are allowed to call the API from browsers, `*` allows any origin.
+    pub cors_origins: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            listen: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            cors_origins: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,478 @@
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use crate::sync::peerset::peer::Peer;
+use crate::sync::peerset::peerset::PeerSet;
+use axum::extract::{self, Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use pactus_types::account::Account;
+use pactus_types::address::Address;
+use pactus_types::block::Block;
+use pactus_types::evidence::Evidence;
+use pactus_types::hash::Hash32;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::stamp::Stamp;
+use pactus_types::transaction::Transaction;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
+
+/// The number of latest blocks that are returned by default, and at most.
+const DEFAULT_LATEST_BLOCKS: i32 = 10;
+const MAX_LATEST_BLOCKS: i32 = 100;
+
+#[derive(Clone)]
+pub(super) struct Context {
+    pub state: Arc<dyn State>,
+    pub evidence_pool: Arc<dyn EvidencePool>,
+    pub peer_set: Arc<RwLock<PeerSet>>,
+    pub network_name: String,
+    pub peer_id: String,
+}
+
+#[derive(Debug)]
+pub(super) enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    Internal(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+impl From<pactus_types::error::Error> for ApiError {
+    fn from(err: pactus_types::error::Error) -> Self {
+        ApiError::Internal(format!("{}", err))
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Debug, Serialize)]
+pub(super) struct NodeInfo {
+    agent: String,
+    network_name: String,
+    peer_id: String,
+    genesis_hash: Hash32,
+    last_block_height: i32,
+    last_block_hash: Hash32,
+    peers: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct PeerInfo {
+    peer_id: String,
+    status: String,
+    moniker: String,
+    agent: String,
+    height: i32,
+    direction: Option<String>,
+    /// Unix time of the last message from the peer.
+    last_seen: Option<u64>,
+    received_bytes: usize,
+    sent_bytes: usize,
+    received_messages: usize,
+    invalid_messages: usize,
+}
+
+impl From<&Peer> for PeerInfo {
+    fn from(peer: &Peer) -> Self {
+        Self {
+            peer_id: peer.peer_id.to_string(),
+            status: format!("{:?}", peer.status),
+            moniker: peer.moniker.clone(),
+            agent: peer.agent.clone(),
+            height: peer.height,
+            direction: peer.direction.map(|direction| format!("{:?}", direction)),
+            last_seen: peer
+                .last_seen
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs()),
+            received_bytes: peer.received_bytes,
+            sent_bytes: peer.sent_bytes,
+            received_messages: peer.received_messages,
+            invalid_messages: peer.invalid_messages,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct TransactionInfo {
+    id: Hash32,
+    stamp: Stamp,
+    sequence: i32,
+    fee: i64,
+    payload_type: String,
+    signer: Address,
+    value: i64,
+    memo: String,
+    /// Hex encoded transaction
+    data: String,
+}
+
+impl TryFrom<&Transaction> for TransactionInfo {
+    type Error = ApiError;
+
+    fn try_from(tx: &Transaction) -> Result<Self, ApiError> {
+        Ok(Self {
+            id: tx.id()?,
+            stamp: tx.stamp.clone(),
+            sequence: tx.sequence,
+            fee: tx.fee,
+            payload_type: format!("{:?}", tx.payload.payload_type()),
+            signer: tx.payload.signer().clone(),
+            value: tx.payload.value(),
+            memo: tx.memo.clone(),
+            data: hex::encode(tx.to_bytes()?),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct BlockInfo {
+    height: i32,
+    hash: Hash32,
+    time: i64,
+    prev_block_hash: Hash32,
+    proposer_address: Address,
+    sortition_seed: Seed,
+    transactions: Vec<TransactionInfo>,
+    /// Hex encoded block
+    data: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AccountInfo {
+    address: Address,
+    number: i32,
+    sequence: i32,
+    balance: i64,
+    /// Hex encoded account
+    data: String,
+}
+
+impl TryFrom<&Account> for AccountInfo {
+    type Error = ApiError;
+
+    fn try_from(acc: &Account) -> Result<Self, ApiError> {
+        Ok(Self {
+            address: acc.address.clone(),
+            number: acc.number,
+            sequence: acc.sequence,
+            balance: acc.balance,
+            data: hex::encode(acc.to_bytes()?),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct EvidenceInfo {
+    hash: Hash32,
+    height: i32,
+    round: i32,
+    offender: Address,
+    /// Hex encoded evidence
+    data: String,
+}
+
+impl TryFrom<&Evidence> for EvidenceInfo {
+    type Error = ApiError;
+
+    fn try_from(evidence: &Evidence) -> Result<Self, ApiError> {
+        Ok(Self {
+            hash: evidence.hash()?,
+            height: evidence.height(),
+            round: evidence.round(),
+            offender: evidence.offender().clone(),
+            data: hex::encode(evidence.to_bytes()?),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct TransactionResponse {
+    block_height: i32,
+    transaction: TransactionInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct LatestBlocksQuery {
+    count: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct EvidencesQuery {
+    offender: Option<String>,
+}
+
+fn parse_hash(hash: &str) -> Result<Hash32, ApiError> {
+    hex::decode(hash)
+        .ok()
+        .and_then(|data| Hash32::from_bytes(&data).ok())
+        .ok_or_else(|| ApiError::BadRequest(format!("invalid hash: {}", hash)))
+}
+
+fn parse_address(address: &str) -> Result<Address, ApiError> {
+    Address::from_string(address)
+        .map_err(|_| ApiError::BadRequest(format!("invalid address: {}", address)))
+}
+
+fn load_block(state: &dyn State, height: i32) -> Result<(Block, Vec<u8>), ApiError> {
+    let data = state
+        .block_data(height)
+        .ok_or_else(|| ApiError::NotFound(format!("block not found: {}", height)))?;
+    Ok((Block::from_bytes(&data)?, data))
+}
+
+fn block_info(state: &dyn State, height: i32) -> Result<BlockInfo, ApiError> {
+    let (block, data) = load_block(state, height)?;
+    let header = block.header();
+    Ok(BlockInfo {
+        height,
+        hash: block.hash()?,
+        time: header.unix_time(),
+        prev_block_hash: header.prev_block_hash().clone(),
+        proposer_address: header.proposer_address().clone(),
+        sortition_seed: header.sortition_seed().clone(),
+        transactions: block
+            .transactions()
+            .iter()
+            .map(TransactionInfo::try_from)
+            .collect::<Result<_, _>>()?,
+        data: hex::encode(data),
+    })
+}
+
+pub(super) async fn node_info(extract::State(ctx): extract::State<Context>) -> Json<NodeInfo> {
+    Json(NodeInfo {
+        agent: crate::agent(),
+        network_name: ctx.network_name.clone(),
+        peer_id: ctx.peer_id.clone(),
+        genesis_hash: ctx.state.genesis_hash(),
+        last_block_height: ctx.state.last_block_height(),
+        last_block_hash: ctx.state.last_block_hash(),
+        peers: ctx.peer_set.read().unwrap().len(),
+    })
+}
+
+pub(super) async fn peers(extract::State(ctx): extract::State<Context>) -> Json<Vec<PeerInfo>> {
+    let peer_set = ctx.peer_set.read().unwrap();
+    Json(peer_set.peers().map(PeerInfo::from).collect())
+}
+
+/// Returns the latest blocks, the newest first.
+pub(super) async fn latest_blocks(
+    extract::State(ctx): extract::State<Context>,
+    Query(query): Query<LatestBlocksQuery>,
+) -> ApiResult<Vec<BlockInfo>> {
+    let count = query
+        .count
+        .unwrap_or(DEFAULT_LATEST_BLOCKS)
+        .clamp(0, MAX_LATEST_BLOCKS);
+    let last_height = ctx.state.last_block_height();
+    let first_height = (last_height - count + 1).max(1);
+    let blocks = (first_height..=last_height)
+        .rev()
+        .map(|height| block_info(ctx.state.as_ref(), height))
+        .collect::<Result<_, _>>()?;
+    Ok(Json(blocks))
+}
+
+pub(super) async fn block_by_height(
+    extract::State(ctx): extract::State<Context>,
+    Path(height): Path<i32>,
+) -> ApiResult<BlockInfo> {
+    Ok(Json(block_info(ctx.state.as_ref(), height)?))
+}
+
+pub(super) async fn block_by_hash(
+    extract::State(ctx): extract::State<Context>,
+    Path(hash): Path<String>,
+) -> ApiResult<BlockInfo> {
+    let hash = parse_hash(&hash)?;
+    let height = ctx
+        .state
+        .block_height(&hash)
+        .ok_or_else(|| ApiError::NotFound("block not found".to_string()))?;
+    Ok(Json(block_info(ctx.state.as_ref(), height)?))
+}
+
+pub(super) async fn transaction(
+    extract::State(ctx): extract::State<Context>,
+    Path(id): Path<String>,
+) -> ApiResult<TransactionResponse> {
+    let id = parse_hash(&id)?;
+    let not_found = || ApiError::NotFound("transaction not found".to_string());
+    let height = ctx.state.transaction_height(&id).ok_or_else(not_found)?;
+    let (block, _) = load_block(ctx.state.as_ref(), height)?;
+    for tx in block.transactions().iter() {
+        if tx.id()? == id {
+            return Ok(Json(TransactionResponse {
+                block_height: height,
+                transaction: TransactionInfo::try_from(tx)?,
+            }));
+        }
+    }
+    Err(not_found())
+}
+
+/// Returns the evidences of misbehaving validators, optionally filtered by the offender.
+pub(super) async fn evidences(
+    extract::State(ctx): extract::State<Context>,
+    Query(query): Query<EvidencesQuery>,
+) -> ApiResult<Vec<EvidenceInfo>> {
+    let evidences = match query.offender {
+        Some(offender) => ctx.evidence_pool.evidences_of(&parse_address(&offender)?),
+        None => ctx.evidence_pool.evidences(),
+    };
+    let evidences = evidences
+        .iter()
+        .map(EvidenceInfo::try_from)
+        .collect::<Result<_, _>>()?;
+    Ok(Json(evidences))
+}
+
+pub(super) async fn account(
+    extract::State(ctx): extract::State<Context>,
+    Path(address): Path<String>,
+) -> ApiResult<AccountInfo> {
+    let address = parse_address(&address)?;
+    let account = ctx
+        .state
+        .account(&address)
+        .ok_or_else(|| ApiError::NotFound("account not found".to_string()))?;
+    Ok(Json(AccountInfo::try_from(&account)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use crate::evidence::create_evidence_pool;
+    use crate::state::create_state;
+
+    fn setup() -> Context {
+        Context {
+            state: create_state(
+                Hash32::calculate(b"genesis"),
+                Vec::new(),
+                Vec::new(),
+                create_event_bus(),
+            )
+            .unwrap(),
+            evidence_pool: create_evidence_pool(None).unwrap(),
+            peer_set: Arc::new(RwLock::new(PeerSet::new())),
+            network_name: "pactus-testnet".to_string(),
+            peer_id: "peer".to_string(),
+        }
+    }
+
+    #[async_std::test]
+    async fn test_node_info() {
+        let ctx = setup();
+        let info = node_info(extract::State(ctx.clone())).await.0;
+        assert_eq!(info.network_name, "pactus-testnet");
+        assert_eq!(info.last_block_height, 0);
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(
+            json["genesis_hash"],
+            hex::encode(Hash32::calculate(b"genesis").as_bytes())
+        );
+
+        assert!(peers(extract::State(ctx)).await.0.is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_blocks() {
+        let ctx = setup();
+        let query = LatestBlocksQuery { count: Some(1000) };
+        let blocks = latest_blocks(extract::State(ctx.clone()), Query(query))
+            .await
+            .unwrap();
+        assert!(blocks.0.is_empty());
+
+        let res = block_by_height(extract::State(ctx.clone()), Path(1)).await;
+        assert!(matches!(res, Err(ApiError::NotFound(_))));
+
+        let res = block_by_hash(extract::State(ctx.clone()), Path("invalid".to_string())).await;
+        assert!(matches!(res, Err(ApiError::BadRequest(_))));
+
+        let hash = hex::encode(Hash32::calculate(b"block").as_bytes());
+        let res = block_by_hash(extract::State(ctx.clone()), Path(hash.clone())).await;
+        assert!(matches!(res, Err(ApiError::NotFound(_))));
+
+        let res = transaction(extract::State(ctx), Path(hash)).await;
+        assert!(matches!(res, Err(ApiError::NotFound(_))));
+
+    }
+
+    #[async_std::test]
+    async fn test_account() {
+        let acc = Account {
+            address: Address::from_bytes(&[2; 21]).unwrap(),
+            number: 0,
+            sequence: 3,
+            balance: 5000,
+        };
+        let ctx = Context {
+            state: create_state(
+                Hash32::calculate(b"genesis"),
+                Vec::new(),
+                vec![acc.clone()],
+                create_event_bus(),
+            )
+            .unwrap(),
+            ..setup()
+        };
+
+        let address = acc.address.to_string();
+        let res = account(extract::State(ctx.clone()), Path(address.clone()))
+            .await
+            .unwrap();
+        let json = serde_json::to_value(&res.0).unwrap();
+        assert_eq!(json["address"], address);
+        assert_eq!(json["balance"], 5000);
+        assert_eq!(json["data"], hex::encode(acc.to_bytes().unwrap()));
+
+        let address = Address::from_bytes(&[3; 21]).unwrap().to_string();
+        let res = account(extract::State(ctx.clone()), Path(address)).await;
+        assert!(matches!(res, Err(ApiError::NotFound(_))));
+
+        let res = account(extract::State(ctx), Path("invalid".to_string())).await;
+        assert!(matches!(res, Err(ApiError::BadRequest(_))));
+    }
+
+    #[async_std::test]
+    async fn test_evidences() {
+        let ctx = setup();
+        let query = EvidencesQuery { offender: None };
+        let res = evidences(extract::State(ctx.clone()), Query(query))
+            .await
+            .unwrap();
+        assert!(res.0.is_empty());
+
+        let query = EvidencesQuery {
+            offender: Some(Address::from_bytes(&[1; 21]).unwrap().to_string()),
+        };
+        let res = evidences(extract::State(ctx.clone()), Query(query))
+            .await
+            .unwrap();
+        assert!(res.0.is_empty());
+
+        let query = EvidencesQuery {
+            offender: Some("invalid".to_string()),
+        };
+        let res = evidences(extract::State(ctx), Query(query)).await;
+        assert!(matches!(res, Err(ApiError::BadRequest(_))));
+    }
+}
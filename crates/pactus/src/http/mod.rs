@@ -0,0 +1,30 @@
+pub mod config;
+
+mod handlers;
+mod server;
+
+use self::server::HttpServer;
+use crate::error::Result;
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use crate::sync::peerset::peerset::PeerSet;
+use std::sync::{Arc, RwLock};
+
+/// Creates the HTTP server, which serves the node data as JSON.
+pub fn create_http_server(
+    config: config::Config,
+    state: Arc<dyn State>,
+    evidence_pool: Arc<dyn EvidencePool>,
+    peer_set: Arc<RwLock<PeerSet>>,
+    network_name: String,
+    peer_id: String,
+) -> Result<impl crate::Service> {
+    HttpServer::new(
+        config,
+        state,
+        evidence_pool,
+        peer_set,
+        network_name,
+        peer_id,
+    )
+}
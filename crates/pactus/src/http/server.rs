@@ -0,0 +1,113 @@
+use super::config::Config;
+use super::handlers::{self, Context};
+use crate::error::{Error, Result};
+use crate::evidence::EvidencePool;
+use crate::state::State;
+use crate::sync::peerset::peerset::PeerSet;
+use async_trait::async_trait;
+use axum::http::{HeaderValue, Method};
+use axum::routing::get;
+use axum::Router;
+use log::{error, info};
+use std::sync::{Arc, RwLock};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+pub(super) struct HttpServer {
+    config: Config,
+    router: Router,
+}
+
+impl HttpServer {
+    pub fn new(
+        config: Config,
+        state: Arc<dyn State>,
+        evidence_pool: Arc<dyn EvidencePool>,
+        peer_set: Arc<RwLock<PeerSet>>,
+        network_name: String,
+        peer_id: String,
+    ) -> Result<Self> {
+        let ctx = Context {
+            state,
+            evidence_pool,
+            peer_set,
+            network_name,
+            peer_id,
+        };
+        let router = Router::new()
+            .route("/node", get(handlers::node_info))
+            .route("/peers", get(handlers::peers))
+            .route("/blocks/latest", get(handlers::latest_blocks))
+            .route("/blocks/height/:height", get(handlers::block_by_height))
+            .route("/blocks/hash/:hash", get(handlers::block_by_hash))
+            .route("/transactions/:id", get(handlers::transaction))
+            .route("/accounts/:address", get(handlers::account))
+            .route("/evidences", get(handlers::evidences))
+            .layer(cors_layer(&config.cors_origins)?)
+            .with_state(ctx);
+
+        Ok(Self { config, router })
+    }
+}
+
+fn cors_layer(origins: &[String]) -> Result<CorsLayer> {
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins = origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .map_err(|_| Error::InvalidConfig(format!("invalid CORS origin: {}", origin)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+    Ok(CorsLayer::new()
+        .allow_methods([Method::GET])
+        .allow_origin(allow_origin))
+}
+
+#[async_trait]
+impl crate::Service for HttpServer {
+    async fn start(self) {
+        if !self.config.enable {
+            return;
+        }
+
+        // axum runs on tokio, so the server gets its own runtime
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                error!("unable to start the http runtime: {}", err);
+                return;
+            }
+        };
+        let listen = self.config.listen;
+        let router = self.router;
+
+        info!("http server is listening on {}", listen);
+        let result = async_std::task::spawn_blocking(move || {
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind(listen).await?;
+                axum::serve(listener, router).await
+            })
+        })
+        .await;
+        if let Err(err) = result {
+            error!("http server stopped: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_origins() {
+        assert!(cors_layer(&[]).is_ok());
+        assert!(cors_layer(&["*".to_string()]).is_ok());
+        assert!(cors_layer(&["https://pactus.org".to_string()]).is_ok());
+        assert!(cors_layer(&["invalid\norigin".to_string()]).is_err());
+    }
+}
@@ -1,9 +1,26 @@
+// Modules are named after their main type and the handlers have no state to default
+#![allow(clippy::module_inception, clippy::new_without_default)]
+
 use async_trait::async_trait;
 
 pub mod config;
+pub mod consensus;
 pub mod error;
+pub mod event;
+pub mod evidence;
+pub mod genesis;
+// tonic handlers return `Status` errors, which are large
+#[allow(clippy::result_large_err)]
+pub mod grpc;
+pub mod http;
+pub mod metrics;
 pub mod network;
+pub mod publisher;
+pub mod signer;
+pub mod slashing_protection;
+pub mod state;
 pub mod sync;
+pub mod txpool;
 
 #[async_trait]
 pub trait Service {
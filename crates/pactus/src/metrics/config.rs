@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub enable: bool,
+    /// Address that the metrics are served on, at `/metrics`.
+    pub listen: SocketAddr,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            listen: SocketAddr::from(([127, 0, 0, 1], 9090)),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+pub mod config;
+
+mod server;
+
+use self::server::MetricsServer;
+use crate::error::Result;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MessageLabels {
+    message_type: String,
+}
+
+impl MessageLabels {
+    fn new(message_type: &dyn Display) -> Self {
+        Self {
+            message_type: message_type.to_string(),
+        }
+    }
+}
+
+/// Metrics of the node, in the Prometheus format.
+pub struct Metrics {
+    registry: Mutex<Registry>,
+    bundles_received: Family<MessageLabels, Counter>,
+    bundles_sent: Family<MessageLabels, Counter>,
+    invalid_bundles: Counter,
+    firewall_bans: Counter,
+    sync_height: Gauge,
+    peers: Gauge,
+    handler_latency: Family<MessageLabels, Histogram, fn() -> Histogram>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let bundles_received = Family::<MessageLabels, Counter>::default();
+        let bundles_sent = Family::<MessageLabels, Counter>::default();
+        let invalid_bundles = Counter::default();
+        let firewall_bans = Counter::default();
+        let sync_height = Gauge::default();
+        let peers = Gauge::default();
+        // From 100 microseconds to about 3 seconds
+        let handler_latency: Family<MessageLabels, Histogram, fn() -> Histogram> =
+            Family::new_with_constructor(|| Histogram::new(exponential_buckets(0.0001, 2.0, 16)));
+
+        let mut registry = Registry::default();
+        let sub_registry = registry.sub_registry_with_prefix("pactus");
+        sub_registry.register(
+            "bundles_received",
+            "Number of the received bundles",
+            bundles_received.clone(),
+        );
+        sub_registry.register(
+            "bundles_sent",
+            "Number of the sent bundles",
+            bundles_sent.clone(),
+        );
+        sub_registry.register(
+            "invalid_bundles",
+            "Number of the bundles that were rejected",
+            invalid_bundles.clone(),
+        );
+        sub_registry.register(
+            "firewall_bans",
+            "Number of the peers that were banned by the firewall",
+            firewall_bans.clone(),
+        );
+        sub_registry.register(
+            "sync_height",
+            "Height of the last committed block",
+            sync_height.clone(),
+        );
+        sub_registry.register("peers", "Number of the connected peers", peers.clone());
+        sub_registry.register(
+            "handler_latency_seconds",
+            "Time spent to handle a bundle",
+            handler_latency.clone(),
+        );
+
+        Self {
+            registry: Mutex::new(registry),
+            bundles_received,
+            bundles_sent,
+            invalid_bundles,
+            firewall_bans,
+            sync_height,
+            peers,
+            handler_latency,
+        }
+    }
+
+    /// Registers the libp2p metrics. The swarm and the protocol events should be recorded into them.
+    pub fn libp2p_metrics(&self) -> libp2p_metrics::Metrics {
+        libp2p_metrics::Metrics::new(&mut self.registry.lock().unwrap())
+    }
+
+    /// Wraps the transport, so the bandwidth usage is measured.
+    pub fn bandwidth_transport<T>(&self, transport: T) -> libp2p_metrics::BandwidthTransport<T> {
+        libp2p_metrics::BandwidthTransport::new(transport, &mut self.registry.lock().unwrap())
+    }
+
+    pub fn inc_bundles_received(&self, message_type: &dyn Display) {
+        self.bundles_received
+            .get_or_create(&MessageLabels::new(message_type))
+            .inc();
+    }
+
+    pub fn inc_bundles_sent(&self, message_type: &dyn Display) {
+        self.bundles_sent
+            .get_or_create(&MessageLabels::new(message_type))
+            .inc();
+    }
+
+    pub fn inc_invalid_bundles(&self) {
+        self.invalid_bundles.inc();
+    }
+
+    pub fn inc_firewall_bans(&self) {
+        self.firewall_bans.inc();
+    }
+
+    pub fn set_sync_height(&self, height: i32) {
+        self.sync_height.set(height as i64);
+    }
+
+    pub fn set_peers(&self, count: usize) {
+        self.peers.set(count as i64);
+    }
+
+    pub fn observe_handler_latency(&self, message_type: &dyn Display, elapsed: Duration) {
+        self.handler_latency
+            .get_or_create(&MessageLabels::new(message_type))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Encodes the metrics in the text format.
+    pub fn encode(&self) -> String {
+        let mut buf = String::new();
+        let registry = self.registry.lock().unwrap();
+        // Writing into a string doesn't fail
+        prometheus_client::encoding::text::encode(&mut buf, &registry).unwrap();
+        buf
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn create_metrics() -> Arc<Metrics> {
+    Arc::new(Metrics::new())
+}
+
+pub fn create_metrics_server(
+    config: config::Config,
+    metrics: Arc<Metrics>,
+) -> Result<impl crate::Service> {
+    Ok(MetricsServer::new(config, metrics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let metrics = Metrics::new();
+        let _libp2p_metrics = metrics.libp2p_metrics();
+        metrics.inc_bundles_received(&"hello");
+        metrics.inc_bundles_received(&"hello");
+        metrics.inc_bundles_sent(&"vote");
+        metrics.inc_invalid_bundles();
+        metrics.set_sync_height(12);
+        metrics.observe_handler_latency(&"hello", Duration::from_millis(2));
+
+        let text = metrics.encode();
+        assert!(text.contains("pactus_bundles_received_total{message_type=\"hello\"} 2"));
+        assert!(text.contains("pactus_bundles_sent_total{message_type=\"vote\"} 1"));
+        assert!(text.contains("pactus_invalid_bundles_total 1"));
+        assert!(text.contains("pactus_sync_height 12"));
+        assert!(text.contains("pactus_handler_latency_seconds_count{message_type=\"hello\"} 1"));
+        assert!(text.contains("libp2p_"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
@@ -0,0 +1,59 @@
+use super::config::Config;
+use super::Metrics;
+use async_trait::async_trait;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use log::{error, info};
+use std::sync::Arc;
+
+const CONTENT_TYPE_OPENMETRICS: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+pub(super) struct MetricsServer {
+    config: Config,
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsServer {
+    pub fn new(config: Config, metrics: Arc<Metrics>) -> Self {
+        Self { config, metrics }
+    }
+}
+
+async fn serve_metrics(metrics: Arc<Metrics>) -> impl IntoResponse {
+    ([(CONTENT_TYPE, CONTENT_TYPE_OPENMETRICS)], metrics.encode())
+}
+
+#[async_trait]
+impl crate::Service for MetricsServer {
+    async fn start(self) {
+        if !self.config.enable {
+            return;
+        }
+
+        // axum runs on tokio, so the server gets its own runtime
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                error!("unable to start the metrics runtime: {}", err);
+                return;
+            }
+        };
+        let listen = self.config.listen;
+        let metrics = self.metrics;
+        let router = Router::new().route("/metrics", get(move || serve_metrics(metrics)));
+
+        info!("metrics are served on {}/metrics", listen);
+        let result = async_std::task::spawn_blocking(move || {
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind(listen).await?;
+                axum::serve(listener, router).await
+            })
+        })
+        .await;
+        if let Err(err) = result {
+            error!("metrics server stopped: {}", err);
+        }
+    }
+}
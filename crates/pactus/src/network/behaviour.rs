@@ -1,138 +1,113 @@
 use super::config::Config;
-use super::swarm::{Swarm, };
-use libp2p::identify::Config as IdentifyConfig;
-use libp2p::{
-    connection_limits::Behaviour as ConnectionLimits,
-    identify::Behaviour as Identify,
-    ping::{Behaviour as Ping, Config as PingConfig},
-    swarm::NetworkBehaviour,
-    PeerId,
+use super::stream::{StreamCodec, StreamProtocol};
+// The derive macro uses `Result` unqualified, so the crate's result isn't imported
+use crate::error::{self, Error};
+use libp2p::gossipsub::{
+    self, IdentTopic, MessageAuthenticity, MessageId, PublishError, SubscriptionError,
 };
-use libp2p_mdns::Behaviour;
-use libp2p_swarm::behaviour::toggle::Toggle;
-use log::{debug, trace, warn, info};
+use libp2p::identity::Keypair;
+use libp2p::kad::{self, store::MemoryStore, QueryId};
+use libp2p::multiaddr::Protocol;
+use libp2p::request_response::{self, OutboundRequestId, ProtocolSupport};
+use libp2p::swarm::{behaviour::toggle::Toggle, NetworkBehaviour};
+use libp2p::{identify, mdns, ping, Multiaddr, PeerId};
+use libp2p_metrics::{Metrics, Recorder};
+use log::{debug, info, trace, warn};
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
-use std::time::Duration;
-use std::{task::Context, task::Poll};
+use std::iter;
 
 #[derive(NetworkBehaviour)]
-#[behaviour(
-    out_event = "BehaviourEventOut",
-    poll_method = "poll",
-    event_process = true
-)]
 pub struct Behaviour {
-    swarm_api: Swarm,
-    gossipsub: Gossipsub,
-    mdns: Toggle<Mdns>,
-    ping: Ping,
-    identify: Identify,
-    kademlia: Toggle<Kademlia<MemoryStore>>,
-    #[behaviour(ignore)]
-    peers: HashSet<PeerId>,
-    #[behaviour(ignore)]
-    events: Vec<BehaviourEventOut>,
+    gossipsub: gossipsub::Behaviour,
+    mdns: Toggle<mdns::tokio::Behaviour>,
+    ping: ping::Behaviour,
+    identify: identify::Behaviour,
+    kademlia: Toggle<kad::Behaviour<MemoryStore>>,
+    request_response: request_response::Behaviour<StreamCodec>,
 }
 
 #[derive(Debug)]
 pub enum BehaviourEventOut {
-    PeerConnected(PeerId),
-    PeerDisconnected(PeerId),
-    MessageReceived { source: PeerId, data: Vec<u8> },
+    /// A peer is found on the local network, it should be dialed.
+    PeerDiscovered { peer_id: PeerId, addr: Multiaddr },
+    MessageReceived { source: PeerId, from: PeerId, data: Vec<u8> },
 }
 
 impl Behaviour {
-    fn poll(
-        &mut self,
-        _: &mut Context,
-        _: &mut impl PollParameters,
-    ) -> Poll<
-        NetworkBehaviourAction<
-            <Self as NetworkBehaviour>::OutEvent,
-            <Self as NetworkBehaviour>::ProtocolsHandler,
-        >,
-    > {
-        if !self.events.is_empty() {
-            return Poll::Ready(NetworkBehaviourAction::GenerateEvent(self.events.remove(0)));
-        }
-        Poll::Pending
-    }
+    pub fn new(local_key: &Keypair, config: &Config) -> error::Result<Self> {
+        let local_peer_id = local_key.public().to_peer_id();
 
-    pub fn new(local_key: &Keypair, config: &Config) -> Self {
         // To content-address message, we can take the hash of message and use it as an ID.
-        let message_id_fn = |message: &GossipsubMessage| {
+        let message_id_fn = |message: &gossipsub::Message| {
             let mut s = DefaultHasher::new();
             message.data.hash(&mut s);
             MessageId::from(s.finish().to_string())
         };
-
-        let local_peer_id = local_key.public().to_peer_id();
-        let gossipsub_config = GossipsubConfigBuilder::default()
-            .heartbeat_interval(Duration::from_secs(10))
+        let gossipsub_config = gossipsub::ConfigBuilder::default()
             .message_id_fn(message_id_fn) // content-address messages. No two messages of the same content will be propagated.
-            .build();
+            .build()
+            .map_err(|err| Error::NetworkError(err.to_string()))?;
+        let gossipsub = gossipsub::Behaviour::new(
+            MessageAuthenticity::Signed(local_key.clone()),
+            gossipsub_config,
+        )
+        .map_err(|err| Error::NetworkError(err.to_string()))?;
 
         // Kademlia config
-        let store = MemoryStore::new(local_peer_id.to_owned());
         let kademlia_opt = if config.kademlia {
-            let mut kad_config = KademliaConfig::default();
             let protocol_name = format!("/{}/kad/v1", config.network_name);
-            kad_config.set_protocol_name(protocol_name.as_bytes().to_vec());
-            let mut kademlia = Kademlia::with_config(local_peer_id.to_owned(), store, kad_config);
+            let protocol_name = libp2p::StreamProtocol::try_from_owned(protocol_name)
+                .map_err(|err| Error::NetworkError(err.to_string()))?;
+            let store = MemoryStore::new(local_peer_id);
+            let mut kademlia = kad::Behaviour::with_config(
+                local_peer_id,
+                store,
+                kad::Config::new(protocol_name),
+            );
             for multiaddr in config.bootstrap_peers.iter() {
                 let mut addr = multiaddr.to_owned();
-                if let Some(Protocol::P2p(mh)) = addr.pop() {
-                    let peer_id = PeerId::from_multihash(mh).unwrap();
+                if let Some(Protocol::P2p(peer_id)) = addr.pop() {
                     kademlia.add_address(&peer_id, addr);
                 } else {
                     warn!("Could not add addr {} to Kademlia DHT", multiaddr)
                 }
             }
-            if let Err(e) = kademlia.bootstrap() {
-                warn!("Kademlia bootstrap failed: {}", e);
-            }
             Some(kademlia)
         } else {
             None
         };
 
         let mdns_opt = if config.mdns {
-            Some(async_std::task::block_on(async {
-                Mdns::new(Default::default())
-                    .await
-                    .expect("Could not start mDNS")
-            }))
+            Some(mdns::tokio::Behaviour::new(
+                mdns::Config::default(),
+                local_peer_id,
+            )?)
         } else {
             None
         };
 
-        let identify = Identify::new(IdentifyConfig::new("pactus/v1".into(), local_key.public()));
+        let identify = identify::Behaviour::new(identify::Config::new(
+            "pactus/v1".into(),
+            local_key.public(),
+        ));
 
-        let gs_config_builder = GossipsubConfigBuilder::default();
-        // gs_config_builder.message_id_fn(|msg: &GossipsubMessage| {
-        //     let s = blake2b_256(&msg.data);
-        //     MessageId::from(s)
-        // });
-        let gossipsub_config = gs_config_builder.build().unwrap();
-        let gossipsub = Gossipsub::new(
-            MessageAuthenticity::Signed(local_key.clone()),
-            gossipsub_config,
-        )
-        .unwrap();
+        let request_response = request_response::Behaviour::<StreamCodec>::new(
+            iter::once((
+                StreamProtocol::new(&config.network_name),
+                ProtocolSupport::Full,
+            )),
+            request_response::Config::default(),
+        );
 
-        let swarm_api = Swarm::new();
-        Behaviour {
-            swarm_api,
+        Ok(Behaviour {
             gossipsub,
             mdns: mdns_opt.into(),
-            ping: Ping::default(),
+            ping: ping::Behaviour::default(),
             identify,
             kademlia: kademlia_opt.into(),
-            events: vec![],
-            peers: Default::default(),
-        }
+            request_response,
+        })
     }
 
     /// Bootstrap Kademlia network
@@ -147,189 +122,140 @@ impl Behaviour {
     /// Publish data over the gossip network.
     pub fn publish(
         &mut self,
-        topic: IdentTopic,
+        topic: &str,
         data: impl Into<Vec<u8>>,
     ) -> Result<MessageId, PublishError> {
-        self.gossipsub.publish(topic, data)
-    }
-
-    /// Subscribe to a gossip topic.
-    pub fn subscribe(&mut self, topic: &IdentTopic) -> Result<bool, SubscriptionError> {
-        self.gossipsub.subscribe(topic)
-    }
-
-    /// Adds peer to the peer set.
-    pub fn add_peer(&mut self, peer_id: PeerId) {
-        self.peers.insert(peer_id);
-    }
-
-    /// Adds peer to the peer set.
-    pub fn remove_peer(&mut self, peer_id: &PeerId) {
-        self.peers.remove(peer_id);
+        self.gossipsub.publish(IdentTopic::new(topic), data)
     }
 
-    /// Adds peer to the peer set.
-    pub fn peers(&self) -> &HashSet<PeerId> {
-        &self.peers
+    /// Sends data directly to a peer over the stream protocol.
+    pub fn send_stream(&mut self, target: &PeerId, data: Vec<u8>) -> OutboundRequestId {
+        self.request_response.send_request(target, data)
     }
 
-
-}
-
-impl NetworkBehaviourEventProcess<IdentifyEvent> for Behaviour {
-    fn inject_event(&mut self, event: IdentifyEvent) {
-        match event {
-            IdentifyEvent::Received { peer_id, info } => {
-                debug!("Identified Peer {}", peer_id);
-                debug!("protocol_version {}", info.protocol_version);
-                debug!("agent_version {}", info.agent_version);
-                debug!("listening_ addresses {:?}", info.listen_addrs);
-                debug!("observed_address {}", info.observed_addr);
-                debug!("protocols {:?}", info.protocols);
-            }
-            IdentifyEvent::Sent { .. } => (),
-            IdentifyEvent::Pushed { .. } => (),
-            IdentifyEvent::Error { .. } => (),
-        }
+    /// Subscribe to a gossip topic.
+    pub fn subscribe(&mut self, topic: &str) -> Result<bool, SubscriptionError> {
+        self.gossipsub.subscribe(&IdentTopic::new(topic))
     }
-}
 
-impl NetworkBehaviourEventProcess<MdnsEvent> for Behaviour {
-    fn inject_event(&mut self, event: MdnsEvent) {
+    /// Handles the events of the sub-behaviours. The events are recorded into the metrics too.
+    pub fn handle_event(
+        &mut self,
+        event: BehaviourEvent,
+        metrics: &Metrics,
+    ) -> Vec<BehaviourEventOut> {
         match event {
-            MdnsEvent::Discovered(list) => {
-                for (peer, addr) in list {
-                    info!("mdns: Discovered peer {}", peer.to_base58());
-                    self.add_peer(peer);
-                    if self.kademlia.is_enabled() {
-                        self.kademlia.as_mut().unwrap().add_address(&peer, addr);
-                    }
+            BehaviourEvent::Identify(event) => {
+                metrics.record(&event);
+                if let identify::Event::Received { peer_id, info, .. } = event {
+                    debug!("Identified Peer {}", peer_id);
+                    debug!("protocol_version {}", info.protocol_version);
+                    debug!("agent_version {}", info.agent_version);
+                    debug!("listening_ addresses {:?}", info.listen_addrs);
+                    debug!("observed_address {}", info.observed_addr);
+                    debug!("protocols {:?}", info.protocols);
                 }
+                Vec::new()
             }
-            MdnsEvent::Expired(list) => {
-                if self.mdns.is_enabled() {
-                    for (peer, _) in list {
-                        if !self.mdns.as_ref().unwrap().has_node(&peer) {
-                            self.remove_peer(&peer);
-                        }
+            BehaviourEvent::Mdns(mdns::Event::Discovered(list)) => list
+                .into_iter()
+                .map(|(peer_id, addr)| {
+                    info!("mdns: Discovered peer {}", peer_id.to_base58());
+                    if let Some(kademlia) = self.kademlia.as_mut() {
+                        kademlia.add_address(&peer_id, addr.clone());
                     }
+                    BehaviourEventOut::PeerDiscovered { peer_id, addr }
+                })
+                .collect(),
+            BehaviourEvent::Mdns(mdns::Event::Expired(list)) => {
+                for (peer_id, _) in list {
+                    trace!("mdns: Expired peer {}", peer_id.to_base58());
                 }
+                Vec::new()
             }
-        }
-    }
-}
-
-impl NetworkBehaviourEventProcess<KademliaEvent> for Behaviour {
-    fn inject_event(&mut self, event: KademliaEvent) {
-        match event {
-            KademliaEvent::RoutingUpdated { peer, .. } => {
-                self.add_peer(peer);
-            }
-            event => {
+            BehaviourEvent::Kademlia(event) => {
+                metrics.record(&event);
                 trace!("kad: {:?}", event);
+                Vec::new()
             }
-        }
-    }
-}
-
-impl NetworkBehaviourEventProcess<GossipsubEvent> for Behaviour {
-    fn inject_event(&mut self, message: GossipsubEvent) {
-        if let GossipsubEvent::Message {
-            propagation_source,
-            message,
-            message_id: _,
-        } = message
-        {
-            self.events.push(BehaviourEventOut::MessageReceived {
-                source: propagation_source,
-                data: message.data,
-            })
-        }
-    }
-}
-
-impl NetworkBehaviourEventProcess<PingEvent> for Behaviour {
-    fn inject_event(&mut self, event: PingEvent) {
-        match event.result {
-            Result::Ok(PingSuccess::Ping { rtt }) => {
-                trace!(
-                    "PingSuccess::Ping rtt to {} is {} ms",
-                    event.peer.to_base58(),
-                    rtt.as_millis()
-                );
-            }
-            Result::Ok(PingSuccess::Pong) => {
-                trace!("PingSuccess::Pong from {}", event.peer.to_base58());
-            }
-            Result::Err(PingFailure::Timeout) => {
-                debug!("PingFailure::Timeout {}", event.peer.to_base58());
-            }
-            Result::Err(PingFailure::Unsupported) => {
-                debug!("PingFailure::Unsupported {}", event.peer.to_base58());
-            }
-            Result::Err(PingFailure::Other { error }) => {
-                debug!("PingFailure::Other {}: {}", event.peer.to_base58(), error);
+            BehaviourEvent::Gossipsub(event) => {
+                metrics.record(&event);
+                match event {
+                    gossipsub::Event::Message {
+                        propagation_source,
+                        message,
+                        message_id: _,
+                    } => vec![BehaviourEventOut::MessageReceived {
+                        source: message.source.unwrap_or(propagation_source),
+                        from: propagation_source,
+                        data: message.data,
+                    }],
+                    _ => Vec::new(),
+                }
             }
-        }
-    }
-}
-
-impl NetworkBehaviourEventProcess<<Swarm as libp2p::swarm::NetworkBehaviour>::OutEvent>
-    for Behaviour
-{
-    fn inject_event(&mut self, event: <Swarm as libp2p::swarm::NetworkBehaviour>::OutEvent) {
-        match event {
-            SwarmEvent::PeerConnected(peer_id) => {
-                self.events.push(BehaviourEventOut::PeerConnected(peer_id))
+            BehaviourEvent::Ping(event) => {
+                metrics.record(&event);
+                match event.result {
+                    Ok(rtt) => trace!(
+                        "ping rtt to {} is {} ms",
+                        event.peer.to_base58(),
+                        rtt.as_millis()
+                    ),
+                    Err(err) => debug!("ping failure {}: {}", event.peer.to_base58(), err),
+                }
+                Vec::new()
             }
-            SwarmEvent::PeerDisconnected(peer_id) => self
-                .events
-                .push(BehaviourEventOut::PeerDisconnected(peer_id)),
+            BehaviourEvent::RequestResponse(event) => self.handle_stream_event(event),
         }
     }
-}
 
-impl NetworkBehaviourEventProcess<RequestResponseEvent<Vec<u8>, Vec<u8>>> for Behaviour {
-    fn inject_event(&mut self, event: RequestResponseEvent<Vec<u8>, Vec<u8>>) {
+    fn handle_stream_event(
+        &mut self,
+        event: request_response::Event<Vec<u8>, Vec<u8>>,
+    ) -> Vec<BehaviourEventOut> {
         match event {
-            RequestResponseEvent::Message { peer, message } => match message {
-                RequestResponseMessage::Request {
+            request_response::Event::Message { peer, message } => match message {
+                request_response::Message::Request {
                     request,
                     channel,
                     request_id: _,
                 } => {
-                    self.events.push(BehaviourEventOut::MessageReceived {
+                    // Acknowledging the request, the reply (if any) is sent as a new request.
+                    if self
+                        .request_response
+                        .send_response(channel, Vec::new())
+                        .is_err()
+                    {
+                        debug!("unable to acknowledge stream message from {:?}", peer);
+                    }
+                    vec![BehaviourEventOut::MessageReceived {
                         source: peer,
+                        from: peer,
                         data: request,
-                    });
-                    debug!("Request Response message: {:?}", channel);
+                    }]
                 }
-                RequestResponseMessage::Response {
-                    request_id: _,
-                    response: _,
-                } => {}
+                request_response::Message::Response { .. } => Vec::new(),
             },
-            RequestResponseEvent::OutboundFailure {
+            request_response::Event::OutboundFailure {
                 peer,
                 request_id,
                 error,
             } => {
                 debug!(
-                    "ChainExchange outbound error (peer: {:?}) (id: {:?}): {:?}",
+                    "stream outbound error (peer: {:?}) (id: {:?}): {:?}",
                     peer, request_id, error
                 );
+                Vec::new()
             }
-            RequestResponseEvent::InboundFailure {
+            request_response::Event::InboundFailure {
                 peer,
                 error,
                 request_id: _,
             } => {
-                debug!(
-                    "ChainExchange inbound error (peer: {:?}): {:?}",
-                    peer, error
-                );
+                debug!("stream inbound error (peer: {:?}): {:?}", peer, error);
+                Vec::new()
             }
-            _ => {}
+            request_response::Event::ResponseSent { .. } => Vec::new(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -8,6 +9,9 @@ pub struct Config {
     pub bootstrap_peers: Vec<Multiaddr>,
     pub mdns: bool,
     pub kademlia: bool,
+    /// File that keeps the identity key of the node.
+    /// If it is not set, the node gets a new identity on every start.
+    pub network_key: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -21,6 +25,7 @@ impl Default for Config {
             bootstrap_peers: vec!["/ip4/127.0.0.1/tcp/1347".parse().unwrap()],
             mdns: true,
             kademlia: true,
+            network_key: None,
         }
     }
 }
@@ -0,0 +1,38 @@
+use crate::error::{Error, Result};
+use libp2p::identity::Keypair;
+use std::fs;
+use std::path::Path;
+
+/// Generates a new identity key for the node, hex encoded to be saved in the network key file.
+pub fn generate_network_key() -> Result<String> {
+    let data = Keypair::generate_ed25519()
+        .to_protobuf_encoding()
+        .map_err(|err| Error::EncodeError(err.to_string()))?;
+    Ok(hex::encode(data))
+}
+
+/// Loads the identity key of the node from the network key file.
+pub fn load_network_key(path: &Path) -> Result<Keypair> {
+    let content = fs::read_to_string(path)?;
+    let data = hex::decode(content.trim())
+        .map_err(|err| Error::InvalidConfig(format!("invalid network key: {}", err)))?;
+    Keypair::from_protobuf_encoding(&data)
+        .map_err(|err| Error::InvalidConfig(format!("invalid network key: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_network_key() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        assert!(load_network_key(file.path()).is_err());
+
+        let key = generate_network_key().unwrap();
+        fs::write(file.path(), &key).unwrap();
+        let first = load_network_key(file.path()).unwrap();
+        let second = load_network_key(file.path()).unwrap();
+        assert_eq!(first.public().to_peer_id(), second.public().to_peer_id());
+    }
+}
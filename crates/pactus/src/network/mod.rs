@@ -1,20 +1,31 @@
 mod behaviour;
-mod swarm;
+mod stream;
 mod transport;
 
 pub mod config;
+pub mod identity;
 pub mod service;
 
 pub use crate::error::Result;
 use async_std::channel::{Receiver, Sender};
+use crate::metrics::Metrics;
 use libp2p::PeerId;
 use service::PactusNetwork;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
 
 #[derive(Debug)]
 pub enum NetworkEvent {
-    PeerConnected(PeerId),
+    PeerConnected { peer_id: PeerId, direction: Direction },
     PeerDisconnected(PeerId),
-    MessageReceived { source: PeerId, data: Vec<u8> },
+    /// A message is received. `source` is the peer that published the message
+    /// and `from` is the peer that relayed it to us.
+    MessageReceived { source: PeerId, from: PeerId, data: Vec<u8> },
 }
 
 #[derive(Debug)]
@@ -22,13 +33,18 @@ pub enum NetworkMessage {
     GeneralMessage { data: Vec<u8> },
     ConsensusMessage { data: Vec<u8> },
     StreamMessage { target: PeerId, data: Vec<u8> },
+    CloseConnection { target: PeerId },
 }
 pub trait NetworkService: crate::Service {
     fn self_id(&self) -> PeerId;
     fn message_sender(&self) -> Sender<NetworkMessage>;
     fn event_receiver(&self) -> Receiver<NetworkEvent>;
+    fn network_name(&self) -> String;
 }
 
-pub fn create_network_service(config: config::Config) -> Result<impl NetworkService> {
-    PactusNetwork::new(config)
+pub fn create_network_service(
+    config: config::Config,
+    metrics: Arc<Metrics>,
+) -> Result<impl NetworkService> {
+    PactusNetwork::new(config, metrics)
 }
@@ -1,23 +1,31 @@
+use super::behaviour::{Behaviour, BehaviourEventOut};
 use super::config::Config;
+use super::identity::load_network_key;
 use super::transport;
-use super::{behaviour, NetworkService};
-use super::{NetworkEvent, NetworkMessage};
+use super::NetworkService;
+use super::{Direction, NetworkEvent, NetworkMessage};
 use crate::error::{Error, Result};
+use crate::metrics::Metrics;
 use async_std::channel::{Receiver, Sender};
-use async_std::stream;
 use async_trait::async_trait;
-use behaviour::{Behaviour, BehaviourEventOut};
 use futures::select;
 use futures_util::stream::StreamExt;
-use libp2p::swarm::{ConnectionDenied, ConnectionId, SwarmEvent};
-use libp2p::{Multiaddr, Swarm};
+use libp2p::swarm::{Config as SwarmConfig, SwarmEvent};
+use libp2p::Swarm;
 use libp2p::{identity, PeerId};
+use libp2p_metrics::Recorder;
 use log::{debug, error, info, warn};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::runtime::Runtime;
 
 pub(super) struct PactusNetwork {
     config: Config,
+    /// libp2p runs on tokio, so the network gets its own runtime.
+    runtime: Option<Runtime>,
     swarm: Swarm<Behaviour>,
+    metrics: Arc<Metrics>,
+    p2p_metrics: libp2p_metrics::Metrics,
     message_receiver: Receiver<NetworkMessage>,
     message_sender: Sender<NetworkMessage>,
     event_receiver: Receiver<NetworkEvent>,
@@ -42,23 +50,38 @@ impl NetworkService for PactusNetwork {
         self.event_receiver.clone()
     }
 
-
+    fn network_name(&self) -> String {
+        self.config.network_name.clone()
+    }
 }
 
 impl PactusNetwork {
-    pub fn new(config: Config) -> Result<Self> {
-        let local_key = identity::Keypair::generate_ed25519();
+    pub fn new(config: Config, metrics: Arc<Metrics>) -> Result<Self> {
+        let local_key = match &config.network_key {
+            Some(path) => load_network_key(path)?,
+            None => identity::Keypair::generate_ed25519(),
+        };
         let local_public = local_key.public();
         let self_id = local_public.to_peer_id();
         info!("node identity is: {}", self_id.to_base58());
 
-        let transport = transport::build_transport(&local_key);
-        let behaviour = Behaviour::new(&local_key, &config);
-        let config = Config::default();
+        let runtime = Runtime::new()?;
+        // The transport and mDNS need the runtime to be set up
+        let _guard = runtime.enter();
+
+        let transport = transport::build_transport(&local_key, &metrics)?;
+        let behaviour = Behaviour::new(&local_key, &config)?;
+        let swarm_config = SwarmConfig::with_tokio_executor()
+            .with_idle_connection_timeout(Duration::from_secs(60));
 
-        let mut swarm = Swarm::new(transport, behaviour, self_id, config);
+        let mut swarm = Swarm::new(transport, behaviour, self_id, swarm_config);
 
-        Swarm::listen_on(&mut swarm, config.listening_addr.clone()).unwrap();
+        Swarm::listen_on(&mut swarm, config.listening_addr.clone()).map_err(|err| {
+            Error::NetworkError(format!(
+                "listen on {} failed: {}",
+                config.listening_addr, err
+            ))
+        })?;
 
         for to_dial in &config.bootstrap_peers {
             libp2p::Swarm::dial(&mut swarm, to_dial.clone()).map_err(|err| {
@@ -67,8 +90,10 @@ impl PactusNetwork {
         }
 
         // Bootstrap with Kademlia
-        if let Err(e) = swarm.behaviour_mut().bootstrap() {
-            warn!("failed to bootstrap with Kademlia: {}", e);
+        if config.kademlia {
+            if let Err(e) = swarm.behaviour_mut().bootstrap() {
+                warn!("failed to bootstrap with Kademlia: {}", e);
+            }
         }
 
         let (message_sender, message_receiver) = async_std::channel::unbounded();
@@ -76,7 +101,10 @@ impl PactusNetwork {
 
         Ok(Self {
             config,
+            runtime: Some(runtime),
             swarm,
+            p2p_metrics: metrics.libp2p_metrics(),
+            metrics,
             message_sender,
             message_receiver,
             event_sender,
@@ -84,12 +112,6 @@ impl PactusNetwork {
         })
     }
 
-    pub fn close(&self) {}
-
-    pub fn name(&self) -> String {
-        self.config.network_name.clone()
-    }
-
     fn topic(&self, topic_name: &str) -> String {
         format!("/{}/topic/{}/v1", self.config.network_name, topic_name)
     }
@@ -102,99 +124,110 @@ impl PactusNetwork {
         self.topic("consensus")
     }
 
-    // fn join_general_topic(&mut self) -> Result<bool> {
-    //     self.join_topic(&self.get_general_topic())
-    // }
-
-    // fn join_consensus_topic(&mut self) -> Result<bool> {
-    //     self.join_topic(&self.get_consensus_topic())
-    // }
-
-    // pub fn join_topic(&mut self, topic: &IdentTopic) -> Result<bool> {
-    //     self.swarm
-    //         .behaviour_mut()
-    //         .subscribe(&topic)
-    //         .map_err(|err| Error::NetworkError(format!("{:?}", err)))
-    // }
-
-
-}
-
-
-#[async_trait]
-impl crate::Service for PactusNetwork {
-    async fn start(self) {
+    async fn run(self) {
         let general_topic = self.general_topic();
         let consensus_topic = self.consensus_topic();
-        let mut swarm_stream = self.swarm.fuse();
+        let mut swarm = self.swarm;
         let mut network_stream = self.message_receiver.fuse();
-        let mut interval = stream::interval(Duration::from_secs(10)).fuse();
+        let mut interval = async_std::stream::interval(Duration::from_secs(10)).fuse();
         let mut has_joined_consensus_topic = false;
 
         // Join general topic by starting the service,
         // We will join consensus topic later (after syncing)
-        swarm_stream
-            .get_mut()
+        swarm
             .behaviour_mut()
             .subscribe(&general_topic)
             .expect("Unable to join general topic");
 
         loop {
             select! {
-                swarm_event = swarm_stream.next() => match swarm_event {
-                    Some(event) => match event {
-                        SwarmEvent::Behaviour(BehaviourEventOut::PeerConnected(peer_id)) => {
-                            info!("peer dialed {:?}", peer_id);
-                            emit_event(&self.event_sender, NetworkEvent::PeerConnected(peer_id)).await;
+                swarm_event = swarm.select_next_some() => {
+                    self.p2p_metrics.record(&swarm_event);
+                    match swarm_event {
+                        SwarmEvent::ConnectionEstablished { peer_id, endpoint, num_established, .. } if num_established.get() == 1 => {
+                            self.metrics.set_peers(swarm.connected_peers().count());
+                            info!("peer connected {:?}", peer_id);
+                            let direction = if endpoint.is_dialer() {
+                                Direction::Outbound
+                            } else {
+                                Direction::Inbound
+                            };
+                            emit_event(&self.event_sender, NetworkEvent::PeerConnected { peer_id, direction }).await;
                         }
-                        SwarmEvent::Behaviour(BehaviourEventOut::PeerDisconnected(peer_id)) => {
+                        SwarmEvent::ConnectionClosed { peer_id, num_established: 0, .. } => {
                             info!("peer disconnected {:?}", peer_id);
+                            self.metrics.set_peers(swarm.connected_peers().count());
                             emit_event(&self.event_sender, NetworkEvent::PeerDisconnected(peer_id)).await;
                         }
-                        SwarmEvent::Behaviour(BehaviourEventOut::MessageReceived {
-                            source,
-                            data,
-                        }) => {
-                            debug!("got a Gossip message from {:?}", source);
-                            emit_event(&self.event_sender, NetworkEvent::MessageReceived {
-                                source,  data
-                            }).await;
-                        }
-                        _ => {
-                            continue;
+                        SwarmEvent::Behaviour(event) => {
+                            for event in swarm.behaviour_mut().handle_event(event, &self.p2p_metrics) {
+                                match event {
+                                    BehaviourEventOut::PeerDiscovered { peer_id, addr } => {
+                                        if !swarm.is_connected(&peer_id) {
+                                            if let Err(err) = swarm.dial(addr) {
+                                                debug!("unable to dial {:?}: {}", peer_id, err);
+                                            }
+                                        }
+                                    }
+                                    BehaviourEventOut::MessageReceived { source, from, data } => {
+                                        debug!("got a message from {:?}", source);
+                                        emit_event(&self.event_sender, NetworkEvent::MessageReceived {
+                                            source, from, data
+                                        }).await;
+                                    }
+                                }
+                            }
                         }
+                        _ => {}
                     }
-                    None => { break; }
                 },
                 message = network_stream.next() => match message {
                     Some(msg) => match msg {
                         NetworkMessage::GeneralMessage{data} =>{
-                            if let Err(e) = swarm_stream.get_mut().behaviour_mut().publish(general_topic.clone(), data) {
+                            if let Err(e) = swarm.behaviour_mut().publish(&general_topic, data) {
                                 warn!("failed to publish message: {:?}", e);
                             }
                         }
                         NetworkMessage::ConsensusMessage{data} =>{
                             if !has_joined_consensus_topic {
-                                swarm_stream.get_mut().behaviour_mut().subscribe(&consensus_topic).expect("Unable to join consensus topic");
+                                swarm.behaviour_mut().subscribe(&consensus_topic).expect("Unable to join consensus topic");
                                 has_joined_consensus_topic = true;
                             }
-                            if let Err(e) = swarm_stream.get_mut().behaviour_mut().publish(consensus_topic.clone(), data) {
+                            if let Err(e) = swarm.behaviour_mut().publish(&consensus_topic, data) {
                                 warn!("failed to publish message: {:?}", e);
                             }
                         }
-                        NetworkMessage::StreamMessage{target: _, data: _} =>{
+                        NetworkMessage::StreamMessage{target, data} =>{
+                            let request_id = swarm.behaviour_mut().send_stream(&target, data);
+                            debug!("stream message {:?} sent to {:?}", request_id, target);
+                        }
+                        NetworkMessage::CloseConnection{target} =>{
+                            info!("closing connection with {:?}", target);
+                            if swarm.disconnect_peer_id(target).is_err() {
+                                debug!("peer {:?} is not connected", target);
+                            }
                         }
                     },
                     None => { break; }
                 },
                 interval_event = interval.next() => if interval_event.is_some() {
-                    debug!("connected peers: {}", swarm_stream.get_mut().behaviour_mut().peers().len());
+                    debug!("connected peers: {}", swarm.connected_peers().count());
                 }
             }
         }
     }
 }
 
+#[async_trait]
+impl crate::Service for PactusNetwork {
+    async fn start(mut self) {
+        let runtime = match self.runtime.take() {
+            Some(runtime) => runtime,
+            None => return,
+        };
+        async_std::task::spawn_blocking(move || runtime.block_on(self.run())).await;
+    }
+}
 
 #[cfg(test)]
 #[path = "./service_test.rs"]
@@ -4,33 +4,118 @@ mod tests {
         service::PactusNetwork,
         {NetworkEvent, NetworkMessage, NetworkService},
     };
+    use crate::metrics::create_metrics;
     use crate::Service;
     use async_std::task;
+    use libp2p::Multiaddr;
     use simple_logger::SimpleLogger;
     use std::{thread, time::Duration};
 
+    /// A config that finds the other nodes through mDNS, on its own port.
+    fn discovery_config() -> Config {
+        Config {
+            listening_addr: format!(
+                "/ip4/0.0.0.0/tcp/{}",
+                portpicker::pick_unused_port().unwrap()
+            )
+            .parse()
+            .unwrap(),
+            bootstrap_peers: Vec::new(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn network_initialize() {
-        let conf = Config::default();
-        let net = PactusNetwork::new(conf);
+        let conf = discovery_config();
+        let net = PactusNetwork::new(conf, create_metrics());
         assert!(net.is_ok(), "Network initialization failed");
     }
 
+    /// A config that doesn't use mDNS, Kademlia or the default bootstrap peers.
+    fn custom_config(network_name: &str, port: u16, bootstrap_peers: Vec<Multiaddr>) -> Config {
+        Config {
+            network_name: network_name.to_string(),
+            listening_addr: format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap(),
+            bootstrap_peers,
+            mdns: false,
+            kademlia: false,
+            network_key: None,
+        }
+    }
+
+    #[test]
+    fn network_custom_name() {
+        let port = portpicker::pick_unused_port().unwrap();
+        let conf = custom_config("pactus_custom", port, Vec::new());
+        let net = PactusNetwork::new(conf, create_metrics()).unwrap();
+
+        assert_eq!(net.network_name(), "pactus_custom");
+        assert_eq!(net.general_topic(), "/pactus_custom/topic/general/v1");
+        assert_eq!(net.consensus_topic(), "/pactus_custom/topic/consensus/v1");
+    }
+
+    #[async_std::test]
+    async fn network_custom_port() {
+        // The second node can reach the first one only through its custom port
+        let port1 = portpicker::pick_unused_port().unwrap();
+        let conf1 = custom_config("pactus_custom", port1, Vec::new());
+        let net1 = PactusNetwork::new(conf1, create_metrics()).unwrap();
+
+        let port2 = portpicker::pick_unused_port().unwrap();
+        let addr1 = format!("/ip4/127.0.0.1/tcp/{}", port1).parse().unwrap();
+        let conf2 = custom_config("pactus_custom", port2, vec![addr1]);
+        let net2 = PactusNetwork::new(conf2, create_metrics()).unwrap();
+
+        let net1_id = net1.self_id();
+        let net1_sender = net1.message_sender();
+        let net2_receiver = net2.event_receiver();
+
+        task::spawn(async {
+            net1.start().await;
+        });
+
+        task::spawn(async {
+            net2.start().await;
+        });
+
+        loop {
+            let event = net2_receiver.recv().await;
+            if let NetworkEvent::PeerConnected { peer_id, .. } = event.unwrap() {
+                assert_eq!(peer_id, net1_id);
+                break;
+            }
+        }
+
+        // Both nodes should subscribe to the topics of the custom network
+        let delay = Duration::from_millis(2000);
+        thread::sleep(delay);
+
+        let data1 = [9, 10, 11, 12].to_vec();
+        let msg1 = NetworkMessage::GeneralMessage {
+            data: data1.clone(),
+        };
+        net1_sender.send(msg1).await.unwrap();
+
+        loop {
+            let event = net2_receiver.recv().await;
+            if let NetworkEvent::MessageReceived { source, data, .. } = event.unwrap() {
+                assert_eq!(source, net1_id);
+                assert_eq!(data, data1);
+                break;
+            }
+        }
+    }
+
     #[async_std::test]
     async fn network_discovery() {
         SimpleLogger::new().with_utc_timestamps().init().unwrap();
 
-        let conf1 = Config::default();
-        let net1 = PactusNetwork::new(conf1).unwrap();
+        let conf1 = discovery_config();
+        let net1 = PactusNetwork::new(conf1, create_metrics()).unwrap();
 
-        let mut conf2 = Config::default();
-        conf2.listening_addr = format!(
-            "/ip4/0.0.0.0/tcp/{}",
-            portpicker::pick_unused_port().unwrap()
-        )
-        .parse()
-        .unwrap();
-        let net2 = PactusNetwork::new(conf2).unwrap();
+        let conf2 = discovery_config();
+        let net2 = PactusNetwork::new(conf2, create_metrics()).unwrap();
 
         let net1_sender = net1.message_sender();
         let net2_receiver = net2.event_receiver();
@@ -55,15 +140,66 @@ mod tests {
 
         loop {
             let msg2 = net2_receiver.recv().await;
-            match msg2.unwrap() {
-                NetworkEvent::MessageReceived {
-                    source: _,
-                    data,
-                } => {
-                    assert_eq!(data, data1);
-                    break;
-                }
-                _ => {}
+            if let NetworkEvent::MessageReceived {
+                source: _,
+                from: _,
+                data,
+            } = msg2.unwrap()
+            {
+                assert_eq!(data, data1);
+                break;
+            }
+        }
+    }
+
+    #[async_std::test]
+    async fn network_stream() {
+        let port1 = portpicker::pick_unused_port().unwrap();
+        let conf1 = custom_config("pactus_testnet", port1, Vec::new());
+        let net1 = PactusNetwork::new(conf1, create_metrics()).unwrap();
+
+        let port2 = portpicker::pick_unused_port().unwrap();
+        let addr1 = format!("/ip4/127.0.0.1/tcp/{}", port1).parse().unwrap();
+        let conf2 = custom_config("pactus_testnet", port2, vec![addr1]);
+        let net2 = PactusNetwork::new(conf2, create_metrics()).unwrap();
+
+        let net1_sender = net1.message_sender();
+        let net1_id = net1.self_id();
+        let net2_id = net2.self_id();
+        let net2_receiver = net2.event_receiver();
+
+        task::spawn(async {
+            net1.start().await;
+        });
+
+        task::spawn(async {
+            net2.start().await;
+        });
+
+        // The stream message is sent over the connection that the second node makes
+        loop {
+            let event = net2_receiver.recv().await;
+            if let NetworkEvent::PeerConnected { peer_id, .. } = event.unwrap() {
+                assert_eq!(peer_id, net1_id);
+                break;
+            }
+        }
+
+        let data1 = [5, 6, 7, 8].to_vec();
+        let msg1 = NetworkMessage::StreamMessage {
+            target: net2_id,
+            data: data1.clone(),
+        };
+
+        net1_sender.send(msg1).await.unwrap();
+
+        loop {
+            let msg2 = net2_receiver.recv().await;
+            if let NetworkEvent::MessageReceived { source, from, data } = msg2.unwrap() {
+                assert_eq!(source, net1_id);
+                assert_eq!(from, net1_id);
+                assert_eq!(data, data1);
+                break;
             }
         }
     }
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use futures::prelude::*;
+use libp2p::request_response::Codec;
+use std::io;
+
+/// Maximum size of a stream message in bytes.
+const MAX_STREAM_MESSAGE_SIZE: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct StreamProtocol(String);
+
+impl StreamProtocol {
+    pub fn new(network_name: &str) -> Self {
+        Self(format!("/{}/stream/v1", network_name))
+    }
+}
+
+impl AsRef<str> for StreamProtocol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The stream codec sends a bundle to the peer as a request.
+/// The response is an empty acknowledgment. Replies to the bundle are sent as new requests.
+#[derive(Debug, Clone, Default)]
+pub struct StreamCodec;
+
+async fn read_data<T>(io: &mut T) -> io::Result<Vec<u8>>
+where
+    T: AsyncRead + Unpin + Send,
+{
+    let mut data = Vec::new();
+    io.take(MAX_STREAM_MESSAGE_SIZE).read_to_end(&mut data).await?;
+    Ok(data)
+}
+
+async fn write_data<T>(io: &mut T, data: Vec<u8>) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin + Send,
+{
+    io.write_all(&data).await?;
+    io.close().await
+}
+
+#[async_trait]
+impl Codec for StreamCodec {
+    type Protocol = StreamProtocol;
+    type Request = Vec<u8>;
+    type Response = Vec<u8>;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_data(io).await
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_data(io).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        data: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_data(io, data).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        data: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_data(io, data).await
+    }
+}
@@ -1,34 +1,43 @@
+use crate::error::{Error, Result};
+use crate::metrics::Metrics;
 use libp2p::{
-    core, core::muxing::StreamMuxerBox, core::transport::Boxed, identity::Keypair,  noise,
-    yamux, PeerId, Transport,
+    core, core::muxing::StreamMuxerBox, core::transport::Boxed, identity::Keypair, noise, PeerId,
+    Transport,
 };
-use libp2p_mplex::Multiplex;
+use libp2p_mplex::MplexConfig;
 use std::time::Duration;
 
 /// Builds the transport stack that LibP2P will communicate over.
-pub fn build_transport(local_key: &Keypair) -> Boxed<(PeerId, StreamMuxerBox)> {
-    let transport = libp2p::tcp::Config::new().nodelay(true);
-    let transport = async_std::task::block_on(libp2p::dns::ResolverConfig::system(transport)).unwrap();
-    let auth_config = {
-        let dh_keys = Keypair::generate_ed25519();
-        noise::Config::xx(dh_keys).into_authenticated()
-    };
+/// The bandwidth usage of the connections is recorded into the metrics.
+///
+/// It should be called inside the tokio runtime of the network.
+pub fn build_transport(
+    local_key: &Keypair,
+    metrics: &Metrics,
+) -> Result<Boxed<(PeerId, StreamMuxerBox)>> {
+    let transport = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::new().nodelay(true));
+    let transport = libp2p::dns::tokio::Transport::system(transport)
+        .map_err(|err| Error::NetworkError(format!("unable to set up DNS: {}", err)))?;
+    // The noise handshake is authenticated by the node identity
+    let auth_config = noise::Config::new(local_key)
+        .map_err(|err| Error::NetworkError(format!("unable to set up noise: {}", err)))?;
 
     let mplex_config = {
-        let mut mplex_config = libp2p_mplex::MplexConfig::new();
+        let mut mplex_config = MplexConfig::new();
         mplex_config.set_max_buffer_size(usize::MAX);
 
-        let mut yamux_config = libp2p::yamux::Config::default();
-        yamux_config.set_max_buffer_size(16 * 1024 * 1024);
-        yamux_config.set_receive_window_size(16 * 1024 * 1024);
-        // yamux_config.set_window_update_mode(WindowUpdateMode::OnRead);
+        let yamux_config = libp2p::yamux::Config::default();
         core::upgrade::SelectUpgrade::new(yamux_config, mplex_config)
     };
 
-    transport
+    let transport = transport
         .upgrade(core::upgrade::Version::V1)
         .authenticate(auth_config)
         .multiplex(mplex_config)
-        .timeout(Duration::from_secs(20))
-        .boxed()
+        .timeout(Duration::from_secs(20));
+
+    Ok(metrics
+        .bandwidth_transport(transport)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed())
 }
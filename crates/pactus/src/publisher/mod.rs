@@ -0,0 +1,22 @@
+pub mod config;
+#[allow(clippy::module_inception)]
+mod publisher;
+
+use self::publisher::RawPublisher;
+use crate::error::Result;
+use crate::event::EventBus;
+use std::sync::Arc;
+
+/// Creates the publisher that pushes the committed blocks and the accepted transactions
+/// over WebSocket.
+///
+/// Clients connect to `/rawblock` or `/rawtx`. Each binary message is the epoch of the node and
+/// the sequence number of the topic, each as 8 little-endian bytes, followed by the CBOR encoded
+/// block or transaction. The epoch changes and the sequence numbers start from zero when the
+/// node restarts, so clients can tell a restart from a gap.
+pub fn create_raw_publisher(
+    config: config::Config,
+    event_bus: Arc<dyn EventBus>,
+) -> Result<impl crate::Service> {
+    Ok(RawPublisher::new(config, event_bus))
+}
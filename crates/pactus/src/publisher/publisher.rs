@@ -0,0 +1,214 @@
+use super::config::Config;
+use crate::event::{Event, EventBus, Filter, Topic};
+use async_std::channel::Receiver;
+use async_trait::async_trait;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use log::{error, info, warn};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::{self, error::RecvError, Sender};
+
+/// Number of the messages that a client can fall behind, the older ones are skipped for it.
+const CLIENT_CAPACITY: usize = 1024;
+
+/// Returns a number that is different in each run of the node.
+/// The clients can tell a restart, where the sequences begin again, from a gap.
+fn new_epoch() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+}
+
+/// The messages of a topic, numbered in the order they are published.
+/// Each message starts with the epoch and the sequence number.
+struct RawTopic {
+    epoch: u64,
+    sequence: u64,
+    sender: Sender<Vec<u8>>,
+}
+
+impl RawTopic {
+    fn new(epoch: u64) -> Self {
+        let (sender, _) = broadcast::channel(CLIENT_CAPACITY);
+        Self {
+            epoch,
+            sequence: 0,
+            sender,
+        }
+    }
+
+    fn publish(&mut self, data: &[u8]) {
+        let mut msg = Vec::with_capacity(16 + data.len());
+        msg.extend_from_slice(&self.epoch.to_le_bytes());
+        msg.extend_from_slice(&self.sequence.to_le_bytes());
+        msg.extend_from_slice(data);
+        self.sequence += 1;
+
+        // It fails only if no client is connected
+        let _ = self.sender.send(msg);
+    }
+}
+
+pub(super) struct RawPublisher {
+    config: Config,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl RawPublisher {
+    pub fn new(config: Config, event_bus: Arc<dyn EventBus>) -> Self {
+        Self { config, event_bus }
+    }
+}
+
+async fn forward(events: Receiver<Event>, mut blocks: RawTopic, mut txs: RawTopic) {
+    while let Ok(event) = events.recv().await {
+        let (topic, data) = match &event {
+            Event::NewBlock { block, .. } => (&mut blocks, block.to_bytes()),
+            Event::NewTransaction(tx) => (&mut txs, tx.to_bytes()),
+            _ => continue,
+        };
+        match data {
+            Ok(data) => topic.publish(&data),
+            Err(err) => error!("unable to encode {:?} event: {}", event.topic(), err),
+        }
+    }
+}
+
+async fn subscribe(ws: WebSocketUpgrade, State(sender): State<Sender<Vec<u8>>>) -> Response {
+    let receiver = sender.subscribe();
+    ws.on_upgrade(move |socket| push(socket, receiver))
+}
+
+async fn push(mut socket: WebSocket, mut receiver: broadcast::Receiver<Vec<u8>>) {
+    loop {
+        match receiver.recv().await {
+            Ok(msg) => {
+                if socket.send(Message::Binary(msg)).await.is_err() {
+                    return;
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    "publisher client is too slow, {} messages are skipped",
+                    skipped
+                )
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+#[async_trait]
+impl crate::Service for RawPublisher {
+    async fn start(self) {
+        if !self.config.enable {
+            return;
+        }
+
+        // axum runs on tokio, so the publisher gets its own runtime
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                error!("unable to start the publisher runtime: {}", err);
+                return;
+            }
+        };
+        let listen = self.config.listen;
+        // The sequences have no gap, so no event should be dropped
+        let events = self.event_bus.subscribe_lossless(Filter {
+            topics: vec![Topic::Block, Topic::Transaction],
+            ..Default::default()
+        });
+        let epoch = new_epoch();
+        let blocks = RawTopic::new(epoch);
+        let txs = RawTopic::new(epoch);
+        let router = Router::new()
+            .route(
+                "/rawblock",
+                get(subscribe).with_state(blocks.sender.clone()),
+            )
+            .route("/rawtx", get(subscribe).with_state(txs.sender.clone()));
+
+        info!("raw blocks and transactions are published on {}", listen);
+        let result = async_std::task::spawn_blocking(move || {
+            runtime.block_on(async move {
+                tokio::spawn(forward(events, blocks, txs));
+                let listener = tokio::net::TcpListener::bind(listen).await?;
+                axum::serve(listener, router).await
+            })
+        })
+        .await;
+        if let Err(err) = result {
+            error!("publisher stopped: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use pactus_types::crypto::bls::secret_key::SecretKey as BLSSecretKey;
+    use pactus_types::crypto::secret_key::SecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+    use pactus_types::transaction::Transaction;
+
+    #[test]
+    fn test_sequence() {
+        let epoch = 7u64.to_le_bytes();
+        let mut topic = RawTopic::new(7);
+        topic.publish(&[1, 2, 3]);
+        let mut receiver = topic.sender.subscribe();
+        topic.publish(&[4, 5]);
+        topic.publish(&[6]);
+
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            [epoch.as_slice(), &1u64.to_le_bytes(), &[4, 5]].concat()
+        );
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            [epoch.as_slice(), &2u64.to_le_bytes(), &[6]].concat()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_raw_transaction() {
+        let signer = Signer::new(SecretKey::BLS(BLSSecretKey::random()));
+        let payload = SendPayload::new(signer.address(), signer.address(), 1000);
+        let tx = Transaction::new(
+            Stamp::from_bytes(&[1, 2, 3, 4]).unwrap(),
+            1,
+            1000,
+            "".to_string(),
+            Box::new(payload),
+            None,
+            None,
+        );
+
+        let event_bus = create_event_bus();
+        let events = event_bus.subscribe_lossless(Filter::default());
+        let epoch = new_epoch();
+        let blocks = RawTopic::new(epoch);
+        let txs = RawTopic::new(epoch);
+        let mut block_receiver = blocks.sender.subscribe();
+        let mut tx_receiver = txs.sender.subscribe();
+
+        event_bus.publish(Event::NewTransaction(tx.clone()));
+        // Closes the subscription, so forwarding stops
+        drop(event_bus);
+        forward(events, blocks, txs).await;
+
+        let msg = tx_receiver.try_recv().unwrap();
+        assert_eq!(msg[..8], epoch.to_le_bytes());
+        assert_eq!(msg[8..16], 0u64.to_le_bytes());
+        assert_eq!(msg[16..], tx.to_bytes().unwrap());
+        assert!(block_receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Address of the remote signer, `tcp://host:port` or `unix:///path/to/socket`.
+    /// The validator key is loaded into the node if it is not set.
+    pub remote_address: Option<String>,
+    /// Hex encoded Noise public key of the remote signer.
+    pub remote_public_key: Option<String>,
+}
@@ -0,0 +1,59 @@
+use super::Signer;
+use crate::error::Result;
+use crate::slashing_protection::SlashingProtection;
+use crate::sync::bundle::message::hello::HelloMessage;
+use pactus_types::address::Address;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::crypto::signer::Signer as KeySigner;
+use pactus_types::proposal::Proposal;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::vote::Vote;
+use std::sync::Arc;
+
+/// Signs with a validator key that is loaded into this process.
+pub(super) struct LocalSigner {
+    signer: KeySigner,
+    slashing_protection: Arc<dyn SlashingProtection>,
+}
+
+impl LocalSigner {
+    pub fn new(signer: KeySigner, slashing_protection: Arc<dyn SlashingProtection>) -> Self {
+        Self {
+            signer,
+            slashing_protection,
+        }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key(&self) -> PublicKey {
+        self.signer.public_key()
+    }
+
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn sign_vote(&self, vote: &mut Vote) -> Result<()> {
+        self.slashing_protection
+            .check_vote(&self.signer.public_key(), vote)?;
+        self.signer.sign(vote);
+        Ok(())
+    }
+
+    fn sign_proposal(&self, proposal: &mut Proposal) -> Result<()> {
+        self.slashing_protection
+            .check_proposal(&self.signer.public_key(), proposal)?;
+        self.signer.sign(proposal);
+        Ok(())
+    }
+
+    fn generate_seed(&self, prev_seed: &Seed) -> Result<Seed> {
+        Ok(prev_seed.generate(&self.signer))
+    }
+
+    fn sign_hello(&self, msg: &mut HelloMessage) -> Result<()> {
+        self.signer.sign(msg);
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+pub mod config;
+mod local;
+pub mod protocol;
+mod remote;
+pub mod server;
+
+use self::local::LocalSigner;
+use self::protocol::Endpoint;
+use self::remote::RemoteSigner;
+use crate::error::{Error, Result};
+use crate::slashing_protection::SlashingProtection;
+use crate::sync::bundle::message::hello::HelloMessage;
+use pactus_types::address::Address;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::crypto::signer::Signer as KeySigner;
+use pactus_types::proposal::Proposal;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::vote::Vote;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The time that the remote signer has to respond to a request.
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Signs the consensus messages with the validator key.
+/// The key can be held by the node itself, or by a separate signer process.
+pub trait Signer: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+    fn address(&self) -> Address;
+    /// Signs the vote, if the slashing protection allows it.
+    fn sign_vote(&self, vote: &mut Vote) -> Result<()>;
+    /// Signs the proposal, if the slashing protection allows it.
+    fn sign_proposal(&self, proposal: &mut Proposal) -> Result<()>;
+    /// Generates the sortition seed of the next block.
+    fn generate_seed(&self, prev_seed: &Seed) -> Result<Seed>;
+    /// Signs the hello message, to prove that we own the validator key.
+    fn sign_hello(&self, msg: &mut HelloMessage) -> Result<()>;
+}
+
+pub fn create_local_signer(
+    signer: KeySigner,
+    slashing_protection: Arc<dyn SlashingProtection>,
+) -> Arc<dyn Signer> {
+    Arc::new(LocalSigner::new(signer, slashing_protection))
+}
+
+/// Connects to the signer process. The auth key is the Noise static key of this node.
+pub fn create_remote_signer(config: &config::Config, auth_key: Vec<u8>) -> Result<Arc<dyn Signer>> {
+    let address = config
+        .remote_address
+        .as_ref()
+        .ok_or_else(|| Error::SignerError("no remote signer address".to_string()))?;
+    let public_key = config
+        .remote_public_key
+        .as_ref()
+        .ok_or_else(|| Error::SignerError("no remote signer public key".to_string()))?;
+    let public_key = hex::decode(public_key).map_err(|err| Error::DecodeError(err.to_string()))?;
+
+    Ok(Arc::new(RemoteSigner::new(
+        Endpoint::parse(address),
+        auth_key,
+        public_key,
+        REMOTE_SIGNER_TIMEOUT,
+    )?))
+}
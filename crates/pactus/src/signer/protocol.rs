@@ -0,0 +1,318 @@
+use crate::error::{Error, Result};
+use minicbor::{bytes::ByteVec, Decode, Encode};
+use pactus_types::proposal::Proposal;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::vote::Vote;
+use snow::{Builder, HandshakeState, TransportState};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The node knows the static key of the signer, and sends its own static key during the handshake.
+const NOISE_PARAMS: &str = "Noise_XK_25519_ChaChaPoly_BLAKE2s";
+const MAX_NOISE_MESSAGE_SIZE: usize = 65535;
+const NOISE_TAG_SIZE: usize = 16;
+/// Maximum size of a request or response. Big messages are sent in several Noise messages.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Encode, Decode)]
+pub(super) enum Request {
+    #[n(1)]
+    PublicKey,
+    #[n(2)]
+    SignVote(#[n(0)] Vote),
+    #[n(3)]
+    SignProposal(#[n(0)] Box<Proposal>),
+    #[n(4)]
+    GenerateSeed(#[n(0)] Seed),
+    /// The signer builds the hello sign bytes from the agent and the peer ID.
+    #[n(5)]
+    SignHello {
+        #[n(0)]
+        agent: String,
+        #[n(1)]
+        peer_id: ByteVec,
+    },
+}
+
+#[derive(Debug, Encode, Decode)]
+pub(super) enum Response {
+    #[n(1)]
+    PublicKey(#[n(0)] ByteVec),
+    #[n(2)]
+    Signature(#[n(0)] ByteVec),
+    #[n(3)]
+    Seed(#[n(0)] Seed),
+    #[n(4)]
+    Error(#[n(0)] String),
+}
+
+pub trait Stream: Read + Write + Send {
+    /// Sets the timeout of the reads, `None` waits forever.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()>;
+}
+
+impl Stream for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(unix)]
+impl Stream for std::os::unix::net::UnixStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    Tcp(String),
+    Unix(PathBuf),
+}
+
+impl Endpoint {
+    /// Parses `unix:///path/to/socket` or `tcp://host:port`. Addresses without a scheme are TCP.
+    pub fn parse(address: &str) -> Self {
+        match address.strip_prefix("unix://") {
+            Some(path) => Endpoint::Unix(PathBuf::from(path)),
+            None => Endpoint::Tcp(
+                address
+                    .strip_prefix("tcp://")
+                    .unwrap_or(address)
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Connects to the signer. Reading or writing fails if the signer doesn't respond in time.
+    pub(super) fn connect(&self, timeout: Duration) -> Result<Box<dyn Stream>> {
+        match self {
+            Endpoint::Tcp(address) => {
+                let addr = address.to_socket_addrs()?.next().ok_or_else(|| {
+                    Error::SignerError(format!("unable to resolve {}", address))
+                })?;
+                let stream = TcpStream::connect_timeout(&addr, timeout)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => Err(Error::SignerError(
+                "unix sockets are not supported".to_string(),
+            )),
+        }
+    }
+}
+
+/// A Noise static key pair, used to authenticate the node and the signer to each other.
+pub struct AuthKeypair {
+    pub secret: Vec<u8>,
+    pub public: Vec<u8>,
+}
+
+pub fn generate_auth_keypair() -> Result<AuthKeypair> {
+    let keypair = builder()?.generate_keypair()?;
+    Ok(AuthKeypair {
+        secret: keypair.private,
+        public: keypair.public,
+    })
+}
+
+fn builder<'a>() -> Result<Builder<'a>> {
+    let params = NOISE_PARAMS
+        .parse()
+        .map_err(|err: snow::Error| Error::SignerError(err.to_string()))?;
+    Ok(Builder::new(params))
+}
+
+/// An encrypted and authenticated connection between the node and the signer.
+pub(super) struct SecureStream {
+    stream: Box<dyn Stream>,
+    noise: TransportState,
+}
+
+impl SecureStream {
+    /// Runs the handshake as the node. It fails if the signer doesn't own the expected key.
+    pub fn initiate(
+        mut stream: Box<dyn Stream>,
+        local_key: &[u8],
+        remote_public_key: &[u8],
+    ) -> Result<Self> {
+        let mut noise = builder()?
+            .local_private_key(local_key)
+            .remote_public_key(remote_public_key)
+            .build_initiator()?;
+        send_handshake(&mut stream, &mut noise)?;
+        recv_handshake(&mut stream, &mut noise)?;
+        send_handshake(&mut stream, &mut noise)?;
+
+        Ok(Self {
+            stream,
+            noise: noise.into_transport_mode()?,
+        })
+    }
+
+    /// Runs the handshake as the signer. It returns the static key of the node.
+    pub fn respond(mut stream: Box<dyn Stream>, local_key: &[u8]) -> Result<(Self, Vec<u8>)> {
+        let mut noise = builder()?.local_private_key(local_key).build_responder()?;
+        recv_handshake(&mut stream, &mut noise)?;
+        send_handshake(&mut stream, &mut noise)?;
+        recv_handshake(&mut stream, &mut noise)?;
+        let remote_public_key = noise
+            .get_remote_static()
+            .ok_or_else(|| Error::SignerError("no remote static key".to_string()))?
+            .to_vec();
+
+        Ok((
+            Self {
+                stream,
+                noise: noise.into_transport_mode()?,
+            },
+            remote_public_key,
+        ))
+    }
+
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.stream.set_read_timeout(timeout)?)
+    }
+
+    pub fn send(&mut self, data: &[u8]) -> Result<()> {
+        if data.len() > MAX_MESSAGE_SIZE {
+            return Err(Error::SignerError("message is too big".to_string()));
+        }
+        let mut plain = (data.len() as u32).to_be_bytes().to_vec();
+        plain.extend_from_slice(data);
+        let mut buf = vec![0; MAX_NOISE_MESSAGE_SIZE];
+        for chunk in plain.chunks(MAX_NOISE_MESSAGE_SIZE - NOISE_TAG_SIZE) {
+            let len = self.noise.write_message(chunk, &mut buf)?;
+            write_frame(&mut self.stream, &buf[..len])?;
+        }
+        Ok(self.stream.flush()?)
+    }
+
+    pub fn recv(&mut self) -> Result<Vec<u8>> {
+        let mut buf = vec![0; MAX_NOISE_MESSAGE_SIZE];
+        let mut plain = Vec::new();
+        let mut expected = None;
+        loop {
+            let frame = read_frame(&mut self.stream)?;
+            let len = self.noise.read_message(&frame, &mut buf)?;
+            plain.extend_from_slice(&buf[..len]);
+
+            if expected.is_none() && plain.len() >= 4 {
+                let size = u32::from_be_bytes([plain[0], plain[1], plain[2], plain[3]]) as usize;
+                if size > MAX_MESSAGE_SIZE {
+                    return Err(Error::SignerError("message is too big".to_string()));
+                }
+                expected = Some(size + 4);
+            }
+            if let Some(expected) = expected {
+                if plain.len() == expected {
+                    return Ok(plain.split_off(4));
+                }
+                if plain.len() > expected {
+                    return Err(Error::SignerError("invalid message length".to_string()));
+                }
+            }
+        }
+    }
+}
+
+fn send_handshake(stream: &mut Box<dyn Stream>, noise: &mut HandshakeState) -> Result<()> {
+    let mut buf = vec![0; MAX_NOISE_MESSAGE_SIZE];
+    let len = noise.write_message(&[], &mut buf)?;
+    write_frame(stream, &buf[..len])?;
+    Ok(stream.flush()?)
+}
+
+fn recv_handshake(stream: &mut Box<dyn Stream>, noise: &mut HandshakeState) -> Result<()> {
+    let mut buf = vec![0; MAX_NOISE_MESSAGE_SIZE];
+    let frame = read_frame(stream)?;
+    noise.read_message(&frame, &mut buf)?;
+    Ok(())
+}
+
+/// Frames are prefixed by their length, as a big-endian u16.
+fn write_frame(stream: &mut Box<dyn Stream>, data: &[u8]) -> Result<()> {
+    stream.write_all(&(data.len() as u16).to_be_bytes())?;
+    stream.write_all(data)?;
+    Ok(())
+}
+
+fn read_frame(stream: &mut Box<dyn Stream>) -> Result<Vec<u8>> {
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+    let mut data = vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_endpoint() {
+        assert_eq!(
+            Endpoint::parse("unix:///tmp/signer.sock"),
+            Endpoint::Unix(PathBuf::from("/tmp/signer.sock"))
+        );
+        assert_eq!(
+            Endpoint::parse("tcp://127.0.0.1:7000"),
+            Endpoint::Tcp("127.0.0.1:7000".to_string())
+        );
+        assert_eq!(
+            Endpoint::parse("127.0.0.1:7000"),
+            Endpoint::Tcp("127.0.0.1:7000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_secure_stream() {
+        let node_key = generate_auth_keypair().unwrap();
+        let signer_key = generate_auth_keypair().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+
+        let signer_secret = signer_key.secret.clone();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let (mut secure, remote) =
+                SecureStream::respond(Box::new(stream), &signer_secret).unwrap();
+            let data = secure.recv().unwrap();
+            secure.send(&data).unwrap();
+            remote
+        });
+
+        let stream = endpoint.connect(Duration::from_secs(5)).unwrap();
+        let mut secure =
+            SecureStream::initiate(stream, &node_key.secret, &signer_key.public).unwrap();
+        // Bigger than a Noise message
+        let data = vec![7; 200_000];
+        secure.send(&data).unwrap();
+        assert_eq!(secure.recv().unwrap(), data);
+        assert_eq!(handle.join().unwrap(), node_key.public);
+
+        // The node refuses a signer with another key
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let other = generate_auth_keypair().unwrap();
+            let _ = SecureStream::respond(Box::new(stream), &other.secret);
+        });
+        let stream = endpoint.connect(Duration::from_secs(5)).unwrap();
+        assert!(SecureStream::initiate(stream, &node_key.secret, &signer_key.public).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use super::protocol::{Endpoint, Request, Response, SecureStream};
+use super::Signer;
+use crate::error::{Error, Result};
+use crate::sync::bundle::message::hello::HelloMessage;
+use log::warn;
+use pactus_types::address::Address;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::crypto::signature::Signature;
+use pactus_types::crypto::signer::Signable;
+use pactus_types::crypto::KeyPairType;
+use pactus_types::proposal::Proposal;
+use pactus_types::sortition::seed::Seed;
+use pactus_types::vote::Vote;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Asks a separate signer process to sign the messages.
+/// The signer process enforces the slashing protection.
+pub(super) struct RemoteSigner {
+    endpoint: Endpoint,
+    auth_key: Vec<u8>,
+    signer_public_key: Vec<u8>,
+    public_key: PublicKey,
+    /// A request fails if the signer doesn't respond in time.
+    timeout: Duration,
+    connection: Mutex<Option<SecureStream>>,
+}
+
+impl RemoteSigner {
+    pub fn new(
+        endpoint: Endpoint,
+        auth_key: Vec<u8>,
+        signer_public_key: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let mut connection =
+            SecureStream::initiate(endpoint.connect(timeout)?, &auth_key, &signer_public_key)?;
+        let public_key = match exchange(&mut connection, &Request::PublicKey)? {
+            Response::PublicKey(data) => PublicKey::from_bytes(KeyPairType::KeyPairBLS, &data)?,
+            response => return Err(unexpected(response)),
+        };
+
+        Ok(Self {
+            endpoint,
+            auth_key,
+            signer_public_key,
+            public_key,
+            timeout,
+            connection: Mutex::new(Some(connection)),
+        })
+    }
+
+    /// Sends the request, reconnecting once if the connection is lost.
+    fn request(&self, request: &Request) -> Result<Response> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut retried = false;
+        loop {
+            if connection.is_none() {
+                *connection = Some(SecureStream::initiate(
+                    self.endpoint.connect(self.timeout)?,
+                    &self.auth_key,
+                    &self.signer_public_key,
+                )?);
+            }
+            match exchange(connection.as_mut().unwrap(), request) {
+                Ok(Response::Error(err)) => return Err(Error::SignerError(err)),
+                Ok(response) => return Ok(response),
+                Err(err) => {
+                    *connection = None;
+                    if retried {
+                        return Err(err);
+                    }
+                    warn!("connection to the signer is lost: {}", err);
+                    retried = true;
+                }
+            }
+        }
+    }
+
+    fn request_signature(&self, request: &Request) -> Result<Signature> {
+        match self.request(request)? {
+            Response::Signature(data) => Ok(Signature::from_bytes(KeyPairType::KeyPairBLS, &data)?),
+            response => Err(unexpected(response)),
+        }
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    fn address(&self) -> Address {
+        self.public_key.address()
+    }
+
+    fn sign_vote(&self, vote: &mut Vote) -> Result<()> {
+        let sig = self.request_signature(&Request::SignVote(vote.clone()))?;
+        vote.set_signature(sig);
+        Ok(())
+    }
+
+    fn sign_proposal(&self, proposal: &mut Proposal) -> Result<()> {
+        let sig = self.request_signature(&Request::SignProposal(Box::new(proposal.clone())))?;
+        proposal.set_signature(sig);
+        Ok(())
+    }
+
+    fn generate_seed(&self, prev_seed: &Seed) -> Result<Seed> {
+        match self.request(&Request::GenerateSeed(prev_seed.clone()))? {
+            Response::Seed(seed) => Ok(seed),
+            response => Err(unexpected(response)),
+        }
+    }
+
+    fn sign_hello(&self, msg: &mut HelloMessage) -> Result<()> {
+        let request = Request::SignHello {
+            agent: msg.agent.clone(),
+            peer_id: msg.peer_id_data.clone(),
+        };
+        let sig = self.request_signature(&request)?;
+        msg.set_signature(sig);
+        msg.set_public_key(self.public_key.clone());
+        Ok(())
+    }
+}
+
+fn exchange(connection: &mut SecureStream, request: &Request) -> Result<Response> {
+    connection.send(&minicbor::to_vec(request)?)?;
+    Ok(minicbor::decode(&connection.recv()?)?)
+}
+
+fn unexpected(response: Response) -> Error {
+    Error::SignerError(format!("unexpected response: {:?}", response))
+}
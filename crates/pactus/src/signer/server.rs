@@ -0,0 +1,332 @@
+use super::protocol::{Endpoint, Request, Response, SecureStream, Stream};
+use super::Signer;
+use crate::error::{Error, Result};
+use crate::sync::bundle::message::hello::HelloMessage;
+use libp2p::PeerId;
+use log::{info, warn};
+use pactus_types::hash::Hash32;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Maximum number of connections that are served at once.
+const MAX_CONNECTIONS: usize = 16;
+/// The node should complete the Noise handshake in this time.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves the signing requests of the authorized nodes.
+/// It runs in the signer process, next to the validator key.
+pub struct SignerServer {
+    signer: Arc<dyn Signer>,
+    auth_key: Vec<u8>,
+    authorized_keys: Vec<Vec<u8>>,
+    connections: AtomicUsize,
+}
+
+impl SignerServer {
+    pub fn new(
+        signer: Arc<dyn Signer>,
+        auth_key: Vec<u8>,
+        authorized_keys: Vec<Vec<u8>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            signer,
+            auth_key,
+            authorized_keys,
+            connections: AtomicUsize::new(0),
+        })
+    }
+
+    /// Accepts the connections and serves each of them in its own thread.
+    pub fn serve(self: Arc<Self>, endpoint: &Endpoint) -> Result<()> {
+        match endpoint {
+            Endpoint::Tcp(address) => {
+                let listener = TcpListener::bind(address)?;
+                info!("signer is listening on {}", listener.local_addr()?);
+                for stream in listener.incoming() {
+                    self.clone().spawn(Box::new(stream?));
+                }
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                // Remove the socket of the previous run
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                let listener = std::os::unix::net::UnixListener::bind(path)?;
+                info!("signer is listening on {}", path.display());
+                for stream in listener.incoming() {
+                    self.clone().spawn(Box::new(stream?));
+                }
+            }
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => {
+                return Err(Error::SignerError(
+                    "unix sockets are not supported".to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn spawn(self: Arc<Self>, stream: Box<dyn Stream>) {
+        if self.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            self.connections.fetch_sub(1, Ordering::SeqCst);
+            warn!("too many connections, the signer refused a connection");
+            return;
+        }
+        thread::spawn(move || {
+            if let Err(err) = self.handle_connection(stream) {
+                warn!("signer connection closed: {}", err);
+            }
+            self.connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    pub(super) fn handle_connection(&self, stream: Box<dyn Stream>) -> Result<()> {
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let (mut connection, remote_key) = SecureStream::respond(stream, &self.auth_key)?;
+        if !self.authorized_keys.contains(&remote_key) {
+            return Err(Error::SignerError(format!(
+                "unauthorized node: {}",
+                hex::encode(remote_key)
+            )));
+        }
+        // The node keeps the connection open between its requests
+        connection.set_read_timeout(None)?;
+
+        loop {
+            let data = match connection.recv() {
+                Ok(data) => data,
+                // The node closed the connection
+                Err(Error::IoError(_)) => return Ok(()),
+                Err(err) => return Err(err),
+            };
+            let response = match minicbor::decode(&data) {
+                Ok(request) => self.respond(request),
+                Err(err) => Response::Error(err.to_string()),
+            };
+            connection.send(&minicbor::to_vec(response)?)?;
+        }
+    }
+
+    fn respond(&self, request: Request) -> Response {
+        let result = match request {
+            Request::PublicKey => Ok(Response::PublicKey(
+                self.signer.public_key().to_bytes().into(),
+            )),
+            Request::SignVote(mut vote) => self
+                .signer
+                .sign_vote(&mut vote)
+                .and_then(|_| Ok(signature_response(vote.signature()?.to_bytes()))),
+            Request::SignProposal(mut proposal) => self
+                .signer
+                .sign_proposal(&mut proposal)
+                .and_then(|_| Ok(signature_response(proposal.signature()?.to_bytes()))),
+            Request::GenerateSeed(prev_seed) => {
+                self.signer.generate_seed(&prev_seed).map(Response::Seed)
+            }
+            Request::SignHello { agent, peer_id } => self.sign_hello(agent, &peer_id),
+        };
+
+        result.unwrap_or_else(|err| {
+            warn!("refused to sign: {}", err);
+            Response::Error(err.to_string())
+        })
+    }
+
+    /// Builds the hello message from its fields, so nothing but a hello message is signed.
+    fn sign_hello(&self, agent: String, peer_id: &[u8]) -> Result<Response> {
+        let peer_id =
+            PeerId::from_bytes(peer_id).map_err(|err| Error::SignerError(err.to_string()))?;
+        let mut hello = HelloMessage::new(peer_id, String::new(), 0, 0, Hash32::UNDEF);
+        hello.agent = agent;
+        self.signer.sign_hello(&mut hello)?;
+        Ok(signature_response(hello.signature()?.to_bytes()))
+    }
+}
+
+fn signature_response(data: Vec<u8>) -> Response {
+    Response::Signature(data.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::create_local_signer;
+    use crate::signer::protocol::generate_auth_keypair;
+    use crate::signer::remote::RemoteSigner;
+    use crate::slashing_protection::create_slashing_protection;
+    use crate::sync::bundle::message::Message;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer as KeySigner;
+    use pactus_types::sortition::seed::Seed;
+    use pactus_types::vote::{Vote, VoteType};
+    use std::time::Instant;
+
+    #[test]
+    fn test_remote_signer() {
+        let key = KeySigner::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let local = create_local_signer(
+            key.clone(),
+            create_slashing_protection(Hash32::UNDEF, None).unwrap(),
+        );
+        let node_key = generate_auth_keypair().unwrap();
+        let signer_key = generate_auth_keypair().unwrap();
+        let server = SignerServer::new(local, signer_key.secret, vec![node_key.public.clone()]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                server.clone().spawn(Box::new(stream.unwrap()));
+            }
+        });
+
+        let remote = RemoteSigner::new(
+            endpoint.clone(),
+            node_key.secret,
+            signer_key.public.clone(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(remote.address(), key.address());
+
+        let mut vote = Vote::new(
+            VoteType::Prepare,
+            1,
+            0,
+            Hash32::calculate(b"block"),
+            key.address(),
+        );
+        remote.sign_vote(&mut vote).unwrap();
+        assert!(vote.verify(&key.public_key()).is_ok());
+
+        // The signer refuses to sign a conflicting vote
+        let mut conflicting = Vote::new(
+            VoteType::Prepare,
+            1,
+            0,
+            Hash32::calculate(b"other"),
+            key.address(),
+        );
+        assert!(remote.sign_vote(&mut conflicting).is_err());
+
+        assert_eq!(
+            remote.generate_seed(&Seed::UNDEF).unwrap(),
+            Seed::UNDEF.generate(&key)
+        );
+
+        let mut hello = HelloMessage::new(PeerId::random(), String::new(), 1, 0, Hash32::UNDEF);
+        remote.sign_hello(&mut hello).unwrap();
+        assert!(hello.basic_check().is_ok());
+
+        // The signer doesn't sign a hello without a valid peer ID
+        let mut hello = HelloMessage::new(PeerId::random(), String::new(), 1, 0, Hash32::UNDEF);
+        hello.peer_id_data = b"vote".to_vec().into();
+        assert!(remote.sign_hello(&mut hello).is_err());
+
+        // Unknown nodes are rejected
+        let stranger = generate_auth_keypair().unwrap();
+        let result = RemoteSigner::new(
+            endpoint,
+            stranger.secret,
+            signer_key.public,
+            Duration::from_secs(5),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unresponsive_signer() {
+        let key = KeySigner::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let node_key = generate_auth_keypair().unwrap();
+        let signer_key = generate_auth_keypair().unwrap();
+
+        // The signer tells its public key, then it stops responding
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+        let signer_secret = signer_key.secret.clone();
+        let public_key = key.public_key().to_bytes();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let signer_secret = signer_secret.clone();
+                let public_key = public_key.clone();
+                thread::spawn(move || {
+                    let (mut connection, _) =
+                        SecureStream::respond(Box::new(stream.unwrap()), &signer_secret).unwrap();
+                    if let Ok(Request::PublicKey) = minicbor::decode(&connection.recv().unwrap()) {
+                        let response = Response::PublicKey(public_key.into());
+                        connection.send(&minicbor::to_vec(response).unwrap()).unwrap();
+                    }
+                    let _ = connection.recv();
+                    thread::sleep(Duration::from_secs(10));
+                });
+            }
+        });
+
+        let remote = RemoteSigner::new(
+            endpoint,
+            node_key.secret,
+            signer_key.public,
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        let mut vote = Vote::new(
+            VoteType::Prepare,
+            1,
+            0,
+            Hash32::calculate(b"block"),
+            key.address(),
+        );
+        let started = Instant::now();
+        assert!(remote.sign_vote(&mut vote).is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let key = KeySigner::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let local = create_local_signer(
+            key,
+            create_slashing_protection(Hash32::UNDEF, None).unwrap(),
+        );
+        let node_key = generate_auth_keypair().unwrap();
+        let signer_key = generate_auth_keypair().unwrap();
+        let server = SignerServer::new(local, signer_key.secret, vec![node_key.public.clone()]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                server.clone().spawn(Box::new(stream.unwrap()));
+            }
+        });
+
+        // The idle connections hold the server until their handshake times out
+        let idle: Vec<Box<dyn Stream>> = (0..MAX_CONNECTIONS)
+            .map(|_| endpoint.connect(Duration::from_secs(5)).unwrap())
+            .collect();
+        thread::sleep(Duration::from_millis(100));
+        let result = RemoteSigner::new(
+            endpoint.clone(),
+            node_key.secret.clone(),
+            signer_key.public.clone(),
+            Duration::from_secs(5),
+        );
+        assert!(result.is_err());
+
+        drop(idle);
+        thread::sleep(Duration::from_millis(100));
+        let result = RemoteSigner::new(
+            endpoint,
+            node_key.secret,
+            signer_key.public,
+            Duration::from_secs(5),
+        );
+        assert!(result.is_ok());
+    }
+}
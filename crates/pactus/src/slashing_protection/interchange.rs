@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+pub const INTERCHANGE_FORMAT_VERSION: u32 = 1;
+
+/// The signing steps, in the order they happen in a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Proposal,
+    Prepare,
+    Precommit,
+    ChangeProposer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub interchange_format_version: u32,
+    /// Hex encoded genesis hash, to prevent importing the history of another network.
+    pub genesis_hash: String,
+}
+
+/// The highest message that the key has signed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRecord {
+    /// Hex encoded public key of the validator.
+    pub public_key: String,
+    pub height: i32,
+    pub round: i32,
+    pub step: Step,
+    /// Hex encoded hash of the signed bytes.
+    pub sign_bytes_hash: String,
+}
+
+impl SignedRecord {
+    pub fn position(&self) -> (i32, i32, Step) {
+        (self.height, self.round, self.step)
+    }
+}
+
+/// Portable signing history, for moving validators between hosts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interchange {
+    pub metadata: Metadata,
+    pub data: Vec<SignedRecord>,
+}
@@ -0,0 +1,34 @@
+pub mod interchange;
+#[allow(clippy::module_inception)]
+mod slashing_protection;
+
+use self::interchange::Interchange;
+use self::slashing_protection::PactusSlashingProtection;
+use crate::error::Result;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::hash::Hash32;
+use pactus_types::proposal::Proposal;
+use pactus_types::vote::Vote;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Keeps the signing history of the local validators, so they never sign conflicting messages.
+pub trait SlashingProtection: Send + Sync {
+    /// Checks the vote against the signing history of the key and records it.
+    /// It returns an error if signing the vote may get the validator slashed.
+    fn check_vote(&self, public_key: &PublicKey, vote: &Vote) -> Result<()>;
+    /// Checks the proposal against the signing history of the key and records it.
+    fn check_proposal(&self, public_key: &PublicKey, proposal: &Proposal) -> Result<()>;
+    fn export(&self) -> Interchange;
+    /// Merges the history of another host into this one.
+    fn import(&self, interchange: Interchange) -> Result<()>;
+}
+
+/// Creates the slashing protection. If a file is given, the history is persisted there.
+/// The file is locked, so it fails if another process uses the same history.
+pub fn create_slashing_protection(
+    genesis_hash: Hash32,
+    file: Option<PathBuf>,
+) -> Result<Arc<dyn SlashingProtection>> {
+    Ok(Arc::new(PactusSlashingProtection::new(genesis_hash, file)?))
+}
@@ -0,0 +1,275 @@
+use super::interchange::{Interchange, Metadata, SignedRecord, Step, INTERCHANGE_FORMAT_VERSION};
+use super::SlashingProtection;
+use crate::error::{Error, Result};
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::crypto::signer::Signable;
+use pactus_types::hash::Hash32;
+use pactus_types::proposal::Proposal;
+use pactus_types::vote::{Vote, VoteType};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs::{self, File, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub(super) struct PactusSlashingProtection {
+    genesis_hash: String,
+    file: Option<PathBuf>,
+    /// Held while the history is in use, so no other process can change it.
+    _lock: Option<File>,
+    records: Mutex<BTreeMap<String, SignedRecord>>,
+}
+
+impl PactusSlashingProtection {
+    pub fn new(genesis_hash: Hash32, file: Option<PathBuf>) -> Result<Self> {
+        let lock = match &file {
+            Some(path) => Some(lock_file(path)?),
+            None => None,
+        };
+        let protection = Self {
+            genesis_hash: hex::encode(genesis_hash.as_bytes()),
+            file,
+            _lock: lock,
+            records: Mutex::new(BTreeMap::new()),
+        };
+        if let Some(path) = &protection.file {
+            if path.exists() {
+                let data = fs::read(path)?;
+                let interchange: Interchange = serde_json::from_slice(&data)
+                    .map_err(|err| Error::DecodeError(err.to_string()))?;
+                protection.import(interchange)?;
+            }
+        }
+        Ok(protection)
+    }
+
+    fn check(
+        &self,
+        public_key: &PublicKey,
+        height: i32,
+        round: i32,
+        step: Step,
+        sign_bytes: &[u8],
+    ) -> Result<()> {
+        let record = SignedRecord {
+            public_key: hex::encode(public_key.to_bytes()),
+            height,
+            round,
+            step,
+            sign_bytes_hash: hex::encode(Hash32::calculate(sign_bytes).as_bytes()),
+        };
+        let mut records = self.records.lock().unwrap();
+        if let Some(last) = records.get(&record.public_key) {
+            match record.position().cmp(&last.position()) {
+                Ordering::Less => {
+                    return Err(Error::SlashingProtection(format!(
+                        "already signed {:?} at {}/{}",
+                        last.step, last.height, last.round
+                    )));
+                }
+                Ordering::Equal => {
+                    if last.sign_bytes_hash != record.sign_bytes_hash {
+                        return Err(Error::SlashingProtection(format!(
+                            "already signed another message for {:?} at {}/{}",
+                            last.step, last.height, last.round
+                        )));
+                    }
+                    // Signing the same message again is safe
+                    return Ok(());
+                }
+                Ordering::Greater => {}
+            }
+        }
+        records.insert(record.public_key.clone(), record);
+        self.save(&records)
+    }
+
+    fn save(&self, records: &BTreeMap<String, SignedRecord>) -> Result<()> {
+        let path = match &self.file {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let data = serde_json::to_vec_pretty(&self.interchange(records))
+            .map_err(|err| Error::EncodeError(err.to_string()))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first, so a crash never leaves a broken history behind
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    fn interchange(&self, records: &BTreeMap<String, SignedRecord>) -> Interchange {
+        Interchange {
+            metadata: Metadata {
+                interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+                genesis_hash: self.genesis_hash.clone(),
+            },
+            data: records.values().cloned().collect(),
+        }
+    }
+}
+
+impl SlashingProtection for PactusSlashingProtection {
+    fn check_vote(&self, public_key: &PublicKey, vote: &Vote) -> Result<()> {
+        let step = match vote.vote_type() {
+            VoteType::Prepare => Step::Prepare,
+            VoteType::Precommit => Step::Precommit,
+            VoteType::ChangeProposer => Step::ChangeProposer,
+        };
+        self.check(
+            public_key,
+            vote.height(),
+            vote.round(),
+            step,
+            &vote.sign_bytes(),
+        )
+    }
+
+    fn check_proposal(&self, public_key: &PublicKey, proposal: &Proposal) -> Result<()> {
+        self.check(
+            public_key,
+            proposal.height(),
+            proposal.round(),
+            Step::Proposal,
+            &proposal.sign_bytes(),
+        )
+    }
+
+    fn export(&self) -> Interchange {
+        self.interchange(&self.records.lock().unwrap())
+    }
+
+    fn import(&self, interchange: Interchange) -> Result<()> {
+        if interchange.metadata.interchange_format_version != INTERCHANGE_FORMAT_VERSION {
+            return Err(Error::SlashingProtection(format!(
+                "unsupported interchange format: {}",
+                interchange.metadata.interchange_format_version
+            )));
+        }
+        if interchange.metadata.genesis_hash != self.genesis_hash {
+            return Err(Error::SlashingProtection(
+                "interchange file belongs to another network".to_string(),
+            ));
+        }
+
+        let mut records = self.records.lock().unwrap();
+        for record in interchange.data {
+            let is_higher = match records.get(&record.public_key) {
+                Some(last) => record.position() > last.position(),
+                None => true,
+            };
+            if is_higher {
+                records.insert(record.public_key.clone(), record);
+            }
+        }
+        self.save(&records)
+    }
+}
+
+/// Takes an exclusive lock next to the history file. It fails if another process holds it.
+fn lock_file(path: &Path) -> Result<File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let lock = File::create(path.with_extension("lock"))?;
+    match lock.try_lock() {
+        Ok(()) => Ok(lock),
+        Err(TryLockError::WouldBlock) => Err(Error::SlashingProtection(format!(
+            "{} is in use by another process",
+            path.display()
+        ))),
+        Err(TryLockError::Error(err)) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+
+    fn vote(signer: &Signer, vote_type: VoteType, height: i32, round: i32, data: &[u8]) -> Vote {
+        Vote::new(
+            vote_type,
+            height,
+            round,
+            Hash32::calculate(data),
+            signer.address(),
+        )
+    }
+
+    #[test]
+    fn test_check_vote() {
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let pk = signer.public_key();
+        let protection = PactusSlashingProtection::new(Hash32::UNDEF, None).unwrap();
+
+        let prepare = vote(&signer, VoteType::Prepare, 2, 1, b"block");
+        protection.check_vote(&pk, &prepare).unwrap();
+        // Signing the same vote again is allowed
+        protection.check_vote(&pk, &prepare).unwrap();
+        assert!(protection
+            .check_vote(&pk, &vote(&signer, VoteType::Prepare, 2, 1, b"other"))
+            .is_err());
+        assert!(protection
+            .check_vote(&pk, &vote(&signer, VoteType::Precommit, 2, 0, b"block"))
+            .is_err());
+
+        protection
+            .check_vote(&pk, &vote(&signer, VoteType::Precommit, 2, 1, b"block"))
+            .unwrap();
+        assert!(protection.check_vote(&pk, &prepare).is_err());
+        protection
+            .check_vote(&pk, &vote(&signer, VoteType::Prepare, 3, 0, b"next"))
+            .unwrap();
+
+        let other = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        protection
+            .check_vote(
+                &other.public_key(),
+                &vote(&other, VoteType::Prepare, 1, 0, b"block"),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_persistence_and_interchange() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("slashing_protection.json");
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let pk = signer.public_key();
+        let genesis_hash = Hash32::calculate(b"genesis");
+
+        let protection =
+            PactusSlashingProtection::new(genesis_hash.clone(), Some(file.clone())).unwrap();
+        protection
+            .check_vote(&pk, &vote(&signer, VoteType::Precommit, 5, 0, b"block"))
+            .unwrap();
+
+        // The history can't be used by two processes at once
+        assert!(PactusSlashingProtection::new(genesis_hash.clone(), Some(file.clone())).is_err());
+        drop(protection);
+
+        let restarted = PactusSlashingProtection::new(genesis_hash.clone(), Some(file)).unwrap();
+        assert!(restarted
+            .check_vote(&pk, &vote(&signer, VoteType::Precommit, 5, 0, b"other"))
+            .is_err());
+
+        // Moving the validator to another host
+        let json = serde_json::to_string(&restarted.export()).unwrap();
+        let interchange: Interchange = serde_json::from_str(&json).unwrap();
+        let other_host = PactusSlashingProtection::new(genesis_hash, None).unwrap();
+        other_host.import(interchange.clone()).unwrap();
+        assert_eq!(other_host.export(), interchange);
+        assert!(other_host
+            .check_vote(&pk, &vote(&signer, VoteType::Prepare, 5, 0, b"block"))
+            .is_err());
+
+        let other_network = PactusSlashingProtection::new(Hash32::UNDEF, None).unwrap();
+        assert!(other_network.import(interchange).is_err());
+    }
+}
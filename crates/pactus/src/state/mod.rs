@@ -0,0 +1,57 @@
+pub mod sandbox;
+#[allow(clippy::module_inception)]
+pub mod state;
+
+use self::state::PactusState;
+use crate::error::Result;
+use crate::event::EventBus;
+use pactus_types::account::Account;
+use pactus_types::address::Address;
+use pactus_types::block::Block;
+use pactus_types::certificate::Certificate;
+use pactus_types::hash::Hash32;
+use pactus_types::transaction::Transaction;
+use pactus_types::validator::Validator;
+use std::sync::Arc;
+
+pub trait State: Send + Sync {
+    fn genesis_hash(&self) -> Hash32;
+    fn last_block_height(&self) -> i32;
+    fn last_block_hash(&self) -> Hash32;
+    fn last_certificate(&self) -> Option<Certificate>;
+    /// Returns the encoded block at the given height.
+    fn block_data(&self, height: i32) -> Option<Vec<u8>>;
+    /// Returns the certificate of the block at the given height.
+    fn certificate(&self, height: i32) -> Option<Certificate>;
+    /// Returns the height of the block with the given hash.
+    fn block_height(&self, hash: &Hash32) -> Option<i32>;
+    /// Returns the height of the block that includes the transaction.
+    fn transaction_height(&self, id: &Hash32) -> Option<i32>;
+    /// Checks that the transaction stamp refers to one of the recent blocks.
+    fn check_stamp(&self, tx: &Transaction) -> Result<()>;
+    /// Checks that the transaction is signed by the signer of the payload and its stamp is valid.
+    /// The sequence and the balance are checked when the transaction is executed in a sandbox.
+    fn check_transaction(&self, tx: &Transaction) -> Result<()>;
+    /// Checks the transactions of the block against the state, before it is committed.
+    fn validate_block(&self, block: &Block) -> Result<()>;
+    /// Commits the block at the given height. The certificate should certify the block.
+    fn commit_block(&self, height: i32, block: Block, cert: Certificate) -> Result<()>;
+    /// Returns the validators of the current committee.
+    fn committee_validators(&self) -> Vec<Validator>;
+    /// Returns the account with the given address.
+    fn account(&self, address: &Address) -> Option<Account>;
+}
+
+pub fn create_state(
+    genesis_hash: Hash32,
+    committee: Vec<Validator>,
+    accounts: Vec<Account>,
+    event_bus: Arc<dyn EventBus>,
+) -> Result<Arc<dyn State>> {
+    Ok(Arc::new(PactusState::new(
+        genesis_hash,
+        committee,
+        accounts,
+        event_bus,
+    )?))
+}
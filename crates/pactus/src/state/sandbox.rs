@@ -0,0 +1,67 @@
+use super::State;
+use crate::error::{Error, Result};
+use pactus_types::address::Address;
+use pactus_types::transaction::Transaction;
+use std::collections::HashMap;
+
+/// Executes the transactions on top of the state, without changing it.
+/// It keeps the sequence and the balance of the accounts that the transactions touch.
+pub struct Sandbox<'a> {
+    state: &'a dyn State,
+    accounts: HashMap<Address, (i32, i64)>,
+}
+
+impl<'a> Sandbox<'a> {
+    pub fn new(state: &'a dyn State) -> Self {
+        Self {
+            state,
+            accounts: HashMap::new(),
+        }
+    }
+
+    fn account(&mut self, address: &Address) -> Option<&mut (i32, i64)> {
+        if !self.accounts.contains_key(address) {
+            let acc = self.state.account(address)?;
+            self.accounts
+                .insert(address.clone(), (acc.sequence, acc.balance));
+        }
+        self.accounts.get_mut(address)
+    }
+
+    /// Checks the sequence and the balance of the signer, then moves the value and the fee.
+    pub fn execute(&mut self, tx: &Transaction) -> Result<()> {
+        let value = tx.payload.value();
+        if value < 0 || tx.fee < 0 {
+            return Err(Error::InvalidTransaction(
+                "negative value or fee".to_string(),
+            ));
+        }
+        let cost = value
+            .checked_add(tx.fee)
+            .ok_or_else(|| Error::InvalidTransaction("value overflow".to_string()))?;
+
+        let (sequence, balance) = self
+            .account(tx.payload.signer())
+            .ok_or_else(|| Error::InvalidTransaction("unknown signer".to_string()))?;
+        if tx.sequence != *sequence + 1 {
+            return Err(Error::InvalidTransaction(format!(
+                "invalid sequence, expected: {}, got: {}",
+                *sequence + 1,
+                tx.sequence
+            )));
+        }
+        if *balance < cost {
+            return Err(Error::InvalidTransaction("insufficient balance".to_string()));
+        }
+        *sequence = tx.sequence;
+        *balance -= cost;
+
+        if let Some(receiver) = tx.payload.receiver() {
+            // Unknown receivers get a new account when the block is committed
+            self.account(receiver);
+            let (_, balance) = self.accounts.entry(receiver.clone()).or_insert((0, 0));
+            *balance += value;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,545 @@
+use super::sandbox::Sandbox;
+use super::State;
+use crate::error::{Error, Result};
+use crate::event::{Event, EventBus};
+use pactus_types::account::Account;
+use pactus_types::address::Address;
+use pactus_types::block::Block;
+use pactus_types::certificate::Certificate;
+use pactus_types::crypto::bls::public_key::PublicKey;
+use pactus_types::hash::Hash32;
+use pactus_types::params::Params;
+use pactus_types::stamp::StampIndex;
+use pactus_types::transaction::Transaction;
+use pactus_types::validator::Validator;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+struct LastInfo {
+    height: i32,
+    block_hash: Hash32,
+    certificate: Option<Certificate>,
+}
+
+pub(super) struct PactusState {
+    genesis_hash: Hash32,
+    committee: Vec<Validator>,
+    last_info: RwLock<LastInfo>,
+    blocks: RwLock<Vec<Vec<u8>>>,
+    certificates: RwLock<Vec<Certificate>>,
+    block_heights: RwLock<HashMap<Hash32, i32>>,
+    tx_heights: RwLock<HashMap<Hash32, i32>>,
+    accounts: RwLock<HashMap<Address, Account>>,
+    stamps: RwLock<StampIndex>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl PactusState {
+    pub fn new(
+        genesis_hash: Hash32,
+        committee: Vec<Validator>,
+        accounts: Vec<Account>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Result<Self> {
+        let accounts = accounts
+            .into_iter()
+            .map(|acc| (acc.address.clone(), acc))
+            .collect();
+        // The genesis has no block, so the first transactions are stamped with the undefined hash
+        let mut stamps = StampIndex::new(Params::default().transaction_to_live_interval);
+        stamps.add_block_hash(0, &Hash32::UNDEF);
+        Ok(Self {
+            genesis_hash,
+            committee,
+            last_info: RwLock::new(LastInfo {
+                height: 0,
+                block_hash: Hash32::UNDEF,
+                certificate: None,
+            }),
+            blocks: RwLock::new(Vec::new()),
+            certificates: RwLock::new(Vec::new()),
+            block_heights: RwLock::new(HashMap::new()),
+            tx_heights: RwLock::new(HashMap::new()),
+            accounts: RwLock::new(accounts),
+            stamps: RwLock::new(stamps),
+            event_bus,
+        })
+    }
+
+    /// Checks that the certificate is signed by more than two-thirds of the committee power.
+    fn check_certificate(&self, cert: &Certificate) -> Result<()> {
+        cert.basic_check()?;
+
+        let mut numbers: Vec<i32> = self.committee.iter().map(|val| val.number()).collect();
+        numbers.sort_unstable();
+        if cert.committers() != numbers.as_slice() {
+            return Err(Error::InvalidBlock(
+                "certificate committers are not the committee".to_string(),
+            ));
+        }
+
+        let mut total_power = 0;
+        let mut signed_power = 0;
+        let mut public_keys = Vec::new();
+        for val in &self.committee {
+            total_power += val.stake();
+            if !cert.absentees().contains(&val.number()) {
+                signed_power += val.stake();
+                public_keys.push(val.public_key()?);
+            }
+        }
+        if 3 * signed_power <= 2 * total_power {
+            return Err(Error::InvalidBlock(format!(
+                "certificate has no quorum, power: {} of {}",
+                signed_power, total_power
+            )));
+        }
+
+        let sign_bytes = Certificate::sign_bytes(cert.block_hash(), cert.round());
+        if !PublicKey::aggregate(&public_keys).verify(&cert.signature()?, &sign_bytes) {
+            return Err(Error::InvalidBlock(
+                "invalid certificate signature".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Moves the value and the fee of the transactions between the accounts.
+    /// The block is validated before, so the balances are not checked here.
+    fn apply_transactions(&self, block: &Block) {
+        let mut accounts = self.accounts.write().unwrap();
+        for tx in block.transactions().iter() {
+            let signer = account_mut(&mut accounts, tx.payload.signer());
+            signer.sequence = tx.sequence;
+            signer.balance -= tx.payload.value() + tx.fee;
+            if let Some(receiver) = tx.payload.receiver() {
+                account_mut(&mut accounts, receiver).balance += tx.payload.value();
+            }
+        }
+    }
+}
+
+/// Returns the account, or a new one that is numbered after the existing accounts.
+fn account_mut<'a>(
+    accounts: &'a mut HashMap<Address, Account>,
+    address: &Address,
+) -> &'a mut Account {
+    let number = accounts.len() as i32;
+    accounts.entry(address.clone()).or_insert_with(|| Account {
+        address: address.clone(),
+        number,
+        sequence: 0,
+        balance: 0,
+    })
+}
+
+impl State for PactusState {
+    fn genesis_hash(&self) -> Hash32 {
+        self.genesis_hash.clone()
+    }
+
+    fn last_block_height(&self) -> i32 {
+        self.last_info.read().unwrap().height
+    }
+
+    fn last_block_hash(&self) -> Hash32 {
+        self.last_info.read().unwrap().block_hash.clone()
+    }
+
+    fn last_certificate(&self) -> Option<Certificate> {
+        self.last_info.read().unwrap().certificate.clone()
+    }
+
+    fn block_data(&self, height: i32) -> Option<Vec<u8>> {
+        if height < 1 {
+            return None;
+        }
+        self.blocks.read().unwrap().get(height as usize - 1).cloned()
+    }
+
+    fn certificate(&self, height: i32) -> Option<Certificate> {
+        if height < 1 {
+            return None;
+        }
+        self.certificates
+            .read()
+            .unwrap()
+            .get(height as usize - 1)
+            .cloned()
+    }
+
+    fn block_height(&self, hash: &Hash32) -> Option<i32> {
+        self.block_heights.read().unwrap().get(hash).copied()
+    }
+
+    fn transaction_height(&self, id: &Hash32) -> Option<i32> {
+        self.tx_heights.read().unwrap().get(id).copied()
+    }
+
+    fn check_stamp(&self, tx: &Transaction) -> Result<()> {
+        tx.check_stamp(&self.stamps.read().unwrap())
+            .map_err(|err| Error::InvalidTransaction(err.to_string()))
+    }
+
+    fn check_transaction(&self, tx: &Transaction) -> Result<()> {
+        let invalid = |msg: &str| Error::InvalidTransaction(msg.to_string());
+        let public_key = match (&tx.public_key, &tx.signature) {
+            (Some(public_key), Some(_)) => public_key,
+            _ => return Err(invalid("transaction is not signed")),
+        };
+        tx.payload
+            .basic_check()
+            .map_err(|err| Error::InvalidTransaction(err.to_string()))?;
+        if &public_key.address() != tx.payload.signer() {
+            return Err(invalid("public key doesn't belong to the signer"));
+        }
+        if !tx.check_signature() {
+            return Err(invalid("invalid signature"));
+        }
+        self.check_stamp(tx)
+    }
+
+    fn validate_block(&self, block: &Block) -> Result<()> {
+        let mut sandbox = Sandbox::new(self);
+        for tx in block.transactions().iter() {
+            self.check_transaction(tx)
+                .and_then(|_| sandbox.execute(tx))
+                .map_err(|err| Error::InvalidBlock(err.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn commit_block(&self, height: i32, block: Block, cert: Certificate) -> Result<()> {
+        let mut last_info = self.last_info.write().unwrap();
+        if height != last_info.height + 1 {
+            return Err(Error::InvalidBlock(format!(
+                "unexpected height, expected: {}, got: {}",
+                last_info.height + 1,
+                height
+            )));
+        }
+
+        let block_hash = block.hash()?;
+        if cert.block_hash() != &block_hash {
+            return Err(Error::InvalidBlock(
+                "certificate is not for this block".to_string(),
+            ));
+        }
+        self.check_certificate(&cert)?;
+        self.validate_block(&block)?;
+
+        // The previous certificate is checked only if we have committed a block before
+        if let Some(last_cert) = &last_info.certificate {
+            if block.header().prev_block_hash() != &last_info.block_hash {
+                return Err(Error::InvalidBlock("invalid previous block hash".to_string()));
+            }
+            match block.prev_cert() {
+                Some(prev_cert) if prev_cert.block_hash() == last_cert.block_hash() => {}
+                _ => {
+                    return Err(Error::InvalidBlock("invalid previous certificate".to_string()));
+                }
+            }
+        }
+
+        let data = block.to_bytes()?;
+        let tx_ids = block
+            .transactions()
+            .iter()
+            .map(|tx| tx.id())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let mut tx_heights = self.tx_heights.write().unwrap();
+        for id in tx_ids {
+            tx_heights.insert(id, height);
+        }
+        self.apply_transactions(&block);
+        self.stamps
+            .write()
+            .unwrap()
+            .add_block_hash(height, &block_hash);
+        self.blocks.write().unwrap().push(data);
+        self.certificates.write().unwrap().push(cert.clone());
+        self.block_heights
+            .write()
+            .unwrap()
+            .insert(block_hash.clone(), height);
+        last_info.height = height;
+        last_info.block_hash = block_hash;
+        last_info.certificate = Some(cert);
+        drop(last_info);
+
+        self.event_bus.publish(Event::NewBlock { height, block });
+        Ok(())
+    }
+
+    fn committee_validators(&self) -> Vec<Validator> {
+        self.committee.clone()
+    }
+
+    fn account(&self, address: &Address) -> Option<Account> {
+        self.accounts.read().unwrap().get(address).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use pactus_types::block::{BlockHeader, Transactions};
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::bls::signature::Signature;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::sortition::seed::Seed;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+    use pactus_types::transaction::Transaction;
+
+    fn setup(count: usize) -> (Vec<SecretKey>, PactusState) {
+        let keys: Vec<SecretKey> = (0..count).map(|_| SecretKey::random()).collect();
+        let validators = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| Validator::new(&key.public_key(), i as i32, 1))
+            .collect();
+        let state =
+            PactusState::new(Hash32::UNDEF, validators, Vec::new(), create_event_bus()).unwrap();
+        (keys, state)
+    }
+
+    fn block(state: &PactusState) -> Block {
+        block_with_txs(state, Vec::new())
+    }
+
+    fn block_with_txs(state: &PactusState, txs: Vec<Transaction>) -> Block {
+        let proposer = state.committee[0].address().unwrap();
+        let header = BlockHeader::new(
+            1,
+            0,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Seed::UNDEF,
+            proposer,
+        );
+        Block::new(header, None, Transactions::from(txs))
+    }
+
+    /// Makes a certificate for the block, signed by the keys that are not absent.
+    fn certificate(
+        keys: &[SecretKey],
+        block: &Block,
+        round: i32,
+        absentees: Vec<i32>,
+    ) -> Certificate {
+        let block_hash = block.hash().unwrap();
+        let sign_bytes = Certificate::sign_bytes(&block_hash, round);
+        let sigs: Vec<Signature> = keys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !absentees.contains(&(*i as i32)))
+            .map(|(_, key)| key.sign(&sign_bytes))
+            .collect();
+        let committers = (0..keys.len() as i32).collect();
+        Certificate::new(
+            block_hash,
+            round,
+            committers,
+            absentees,
+            &Signature::aggregate(&sigs),
+        )
+    }
+
+    #[test]
+    fn test_commit_with_quorum() {
+        let (keys, state) = setup(4);
+        let block = block(&state);
+        let cert = certificate(&keys, &block, 0, vec![3]);
+
+        state.commit_block(1, block, cert.clone()).unwrap();
+        assert_eq!(state.last_block_height(), 1);
+        assert_eq!(state.certificate(1).unwrap().hash().unwrap(), cert.hash().unwrap());
+        assert!(state.certificate(2).is_none());
+    }
+
+    #[test]
+    fn test_commit_without_quorum() {
+        let (keys, state) = setup(4);
+        let block = block(&state);
+        let cert = certificate(&keys, &block, 0, vec![2, 3]);
+
+        assert!(state.commit_block(1, block, cert).is_err());
+        assert_eq!(state.last_block_height(), 0);
+    }
+
+    #[test]
+    fn test_commit_invalid_signature() {
+        let (keys, state) = setup(4);
+        let block = block(&state);
+
+        // The committers have signed another round
+        let other = certificate(&keys, &block, 1, vec![3]);
+        let cert = Certificate::new(
+            block.hash().unwrap(),
+            0,
+            other.committers().to_vec(),
+            other.absentees().to_vec(),
+            &other.signature().unwrap(),
+        );
+        assert!(state.commit_block(1, block.clone(), cert).is_err());
+
+        // An absentee is claimed to have signed
+        let cert = certificate(&keys, &block, 0, vec![3]);
+        let cert = Certificate::new(
+            block.hash().unwrap(),
+            0,
+            cert.committers().to_vec(),
+            Vec::new(),
+            &cert.signature().unwrap(),
+        );
+        assert!(state.commit_block(1, block, cert).is_err());
+    }
+
+    #[test]
+    fn test_commit_unknown_committers() {
+        let (keys, state) = setup(4);
+        let block = block(&state);
+        let cert = certificate(&keys, &block, 0, Vec::new());
+        let cert = Certificate::new(
+            block.hash().unwrap(),
+            0,
+            vec![0, 1, 2, 3, 4],
+            Vec::new(),
+            &cert.signature().unwrap(),
+        );
+
+        assert!(state.commit_block(1, block, cert).is_err());
+    }
+
+    fn setup_funded(count: usize, funded: &[Address]) -> (Vec<SecretKey>, PactusState) {
+        let (keys, state) = setup(count);
+        let accounts = funded
+            .iter()
+            .enumerate()
+            .map(|(number, address)| Account {
+                address: address.clone(),
+                number: number as i32,
+                sequence: 0,
+                balance: 5000,
+            })
+            .collect();
+        let state = PactusState::new(
+            Hash32::UNDEF,
+            state.committee_validators(),
+            accounts,
+            create_event_bus(),
+        )
+        .unwrap();
+        (keys, state)
+    }
+
+    /// Makes a transaction that sends from `sender`, signed by `signer`.
+    fn send_tx(signer: &Signer, sender: &Address, sequence: i32, amount: i64) -> Transaction {
+        let receiver = Address::from_bytes(&[3; 21]).unwrap();
+        let payload = SendPayload::new(sender.clone(), receiver, amount);
+        let mut tx = Transaction::new(
+            Stamp::from_bytes(&[0; 4]).unwrap(),
+            sequence,
+            10,
+            String::new(),
+            Box::new(payload),
+            None,
+            None,
+        );
+        signer.sign(&mut tx);
+        tx
+    }
+
+    fn random_signer() -> Signer {
+        Signer::new(CryptoSecretKey::BLS(SecretKey::random()))
+    }
+
+    #[test]
+    fn test_accounts() {
+        let signer = random_signer();
+        let (keys, state) = setup_funded(4, &[signer.address()]);
+        let sender = signer.address();
+        let receiver = Address::from_bytes(&[3; 21]).unwrap();
+        assert!(state.account(&receiver).is_none());
+
+        let tx = send_tx(&signer, &sender, 1, 1000);
+        let block = block_with_txs(&state, vec![tx]);
+        let cert = certificate(&keys, &block, 0, Vec::new());
+        state.commit_block(1, block, cert).unwrap();
+
+        let acc = state.account(&sender).unwrap();
+        assert_eq!(acc.sequence, 1);
+        assert_eq!(acc.balance, 3990);
+        let acc = state.account(&receiver).unwrap();
+        assert_eq!(acc.number, 1);
+        assert_eq!(acc.balance, 1000);
+    }
+
+    #[test]
+    fn test_commit_invalid_stamp() {
+        let signer = random_signer();
+        let (keys, state) = setup_funded(4, &[signer.address()]);
+        let mut tx = send_tx(&signer, &signer.address(), 1, 1000);
+        tx.stamp = Stamp::from_bytes(&[1, 2, 3, 4]).unwrap();
+        signer.sign(&mut tx);
+        assert!(state.check_stamp(&tx).is_err());
+
+        let block = block_with_txs(&state, vec![tx]);
+        let cert = certificate(&keys, &block, 0, Vec::new());
+        assert!(state.validate_block(&block).is_err());
+        assert!(state.commit_block(1, block, cert).is_err());
+        assert_eq!(state.last_block_height(), 0);
+    }
+
+    #[test]
+    fn test_validate_transactions() {
+        let victim = random_signer();
+        let attacker = random_signer();
+        let (keys, state) = setup_funded(4, &[victim.address(), attacker.address()]);
+
+        // Signed by the attacker, but it spends the funds of the victim
+        let tx = send_tx(&attacker, &victim.address(), 1, 1000);
+        assert!(state.check_transaction(&tx).is_err());
+        let block = block_with_txs(&state, vec![tx]);
+        let cert = certificate(&keys, &block, 0, Vec::new());
+        assert!(state.commit_block(1, block, cert).is_err());
+        assert_eq!(state.account(&victim.address()).unwrap().balance, 5000);
+
+        let invalid_blocks = [
+            // Not signed
+            vec![{
+                let mut tx = send_tx(&victim, &victim.address(), 1, 1000);
+                tx.signature = None;
+                tx
+            }],
+            // More than the balance, with the fee
+            vec![send_tx(&victim, &victim.address(), 1, 4995)],
+            // Invalid sequence
+            vec![send_tx(&victim, &victim.address(), 2, 1000)],
+            // The second transaction spends the rest of the balance
+            vec![
+                send_tx(&victim, &victim.address(), 1, 3000),
+                send_tx(&victim, &victim.address(), 2, 3000),
+            ],
+            // Unknown signer
+            vec![send_tx(&random_signer(), &Address::from_bytes(&[4; 21]).unwrap(), 1, 0)],
+        ];
+        for txs in invalid_blocks {
+            assert!(state.validate_block(&block_with_txs(&state, txs)).is_err());
+        }
+
+        let block = block_with_txs(
+            &state,
+            vec![
+                send_tx(&victim, &victim.address(), 1, 3000),
+                send_tx(&victim, &victim.address(), 2, 1000),
+            ],
+        );
+        assert!(state.validate_block(&block).is_ok());
+    }
+}
@@ -1,18 +1,35 @@
 use super::message::*;
 use crate::error::{Error, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use libp2p::PeerId;
 use minicbor::{bytes::ByteVec, Decode, Encode};
+use std::io::{Read, Write};
 use std::vec::Vec;
 
+pub const FLAG_NETWORK_MAINNET: i32 = 0x0001;
+pub const FLAG_NETWORK_TESTNET: i32 = 0x0002;
+pub const FLAG_NETWORK_MASK: i32 = FLAG_NETWORK_MAINNET | FLAG_NETWORK_TESTNET;
+pub const FLAG_COMPRESSED: i32 = 0x0100;
+
+/// Messages larger than this are compressed before sending.
+const COMPRESSION_THRESHOLD: usize = 1024;
+/// Maximum size of a decompressed message, to prevent decompression bombs.
+const MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+/// Bundle wraps an encoded message. The message itself is decoded by its handler.
 #[derive(Debug)]
 pub struct Bundle {
+    pub flags: i32,
     pub initiator: PeerId,
-    pub message: Box<dyn Message>,
+    pub message_type: Type,
+    pub message_data: Vec<u8>,
 }
 
 #[derive(Encode, Decode)]
 #[cbor(map)]
-struct RawBundle {
+pub(crate) struct RawBundle {
     #[n(1)]
     pub version: i32,
     #[n(2)]
@@ -25,57 +42,107 @@ struct RawBundle {
     pub message_data: ByteVec,
 }
 
+impl RawBundle {
+    /// Decodes the bundle envelope without decoding the message itself.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        Ok(minicbor::decode(data)?)
+    }
+}
+
 impl Bundle {
-    pub fn new(initiator: PeerId, message: Box<dyn Message>) -> Result<Self> {
-        Ok(Self { initiator, message })
+    pub fn new(initiator: PeerId, message: &dyn Message) -> Result<Self> {
+        message.basic_check()?;
+        Ok(Self {
+            flags: 0,
+            initiator,
+            message_type: message.message_type(),
+            message_data: message.to_bytes()?,
+        })
     }
 
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
-        let raw: RawBundle = minicbor::decode(data)?;
+        Self::from_raw(RawBundle::from_bytes(data)?)
+    }
+
+    /// Opens a raw bundle, decompressing its message if needed.
+    pub(crate) fn from_raw(raw: RawBundle) -> Result<Self> {
         let initiator = PeerId::from_bytes(&raw.initiator_data)
             .map_err(|err| Error::DecodeError(err.to_string()))?;
-        let msg: Result<Box<dyn Message>> = match raw.message_type {
-            Type::Hello => Ok(Box::new(minicbor::decode::<hello::HelloMessage>(
-                raw.message_data.as_ref(),
-            )?)),
-            Type::Heartbeat => Ok(Box::new(minicbor::decode::<heartbeat::HeartbeatMessage>(
-                raw.message_data.as_ref(),
-            )?)),
-            _ => Err(Error::InvalidMessage(format!(
-                "message type {} not supported yet",
-                raw.message_type
-            ))),
+        let message_data = if raw.flags & FLAG_COMPRESSED == FLAG_COMPRESSED {
+            decompress(raw.message_data.as_ref())?
+        } else {
+            raw.message_data.to_vec()
         };
 
-        Self::new(initiator, msg?)
+        Ok(Self {
+            flags: raw.flags & !FLAG_COMPRESSED,
+            initiator,
+            message_type: raw.message_type,
+            message_data,
+        })
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         let initiator_data = self.initiator.to_bytes().into();
-        let message_data = self.message.to_bytes()?.into();
+        let mut flags = self.flags & !FLAG_COMPRESSED;
+        let message_data = if self.message_data.len() > COMPRESSION_THRESHOLD {
+            flags |= FLAG_COMPRESSED;
+            compress(&self.message_data)?
+        } else {
+            self.message_data.clone()
+        };
         let raw = RawBundle {
             version: 1,
-            flags: 0,
+            flags,
             initiator_data,
-            message_type: self.message.message_type(),
-            message_data,
+            message_type: self.message_type,
+            message_data: message_data.into(),
         };
 
         Ok(minicbor::to_vec(raw)?)
     }
+}
 
-    pub fn basic_check(&self) -> Result<()> {
-        self.message.basic_check()
+/// Returns the bundle flag for the network, based on its name.
+/// Local networks have no network flag.
+pub fn network_flag(network_name: &str) -> i32 {
+    if network_name == "pactus" {
+        FLAG_NETWORK_MAINNET
+    } else if network_name.contains("testnet") {
+        FLAG_NETWORK_TESTNET
+    } else {
+        0
     }
+}
 
-    pub fn message_type(&self) -> Type {
-        self.message.message_type()
+fn compress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|err| Error::EncodeError(err.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|err| Error::EncodeError(err.to_string()))
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    GzDecoder::new(data)
+        .take(MAX_DECOMPRESSED_SIZE as u64 + 1)
+        .read_to_end(&mut buf)
+        .map_err(|err| Error::DecodeError(err.to_string()))?;
+    if buf.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(Error::DecodeError(
+            "decompressed message is too big".to_string(),
+        ));
     }
+    Ok(buf)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pactus_types::hash::Hash32;
 
     #[test]
     fn test_decoding() {
@@ -86,4 +153,56 @@ mod tests {
         let msg = Bundle::from_bytes(buf.as_slice()).unwrap();
         assert_eq!(buf, msg.to_bytes().unwrap());
     }
+
+    #[test]
+    fn test_network_flag() {
+        assert_eq!(network_flag("pactus"), FLAG_NETWORK_MAINNET);
+        assert_eq!(network_flag("pactus_testnet"), FLAG_NETWORK_TESTNET);
+        assert_eq!(network_flag("pactus_localnet"), 0);
+
+        let msg = heartbeat::HeartbeatMessage::new(1, 0, Hash32::calculate(b"block"));
+        let mut bdl = Bundle::new(PeerId::random(), &msg).unwrap();
+        bdl.flags |= FLAG_NETWORK_TESTNET;
+        let decoded = Bundle::from_bytes(&bdl.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.flags & FLAG_NETWORK_MASK, FLAG_NETWORK_TESTNET);
+    }
+
+    #[test]
+    fn test_compression() {
+        let msg = blocks_response::BlocksResponseMessage::new(
+            RESPONSE_CODE_MOREBLOCKS,
+            1,
+            1,
+            vec![vec![0; 4096]],
+            None,
+            "".to_string(),
+        );
+        let bdl = Bundle::new(PeerId::random(), &msg).unwrap();
+        let data = bdl.to_bytes().unwrap();
+        assert!(data.len() < 4096);
+
+        let raw = RawBundle::from_bytes(&data).unwrap();
+        assert_eq!(raw.flags & FLAG_COMPRESSED, FLAG_COMPRESSED);
+
+        let decoded = Bundle::from_bytes(&data).unwrap();
+        assert_eq!(decoded.flags, 0);
+        assert_eq!(decoded.initiator, bdl.initiator);
+        assert_eq!(decoded.to_bytes().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decompression_bomb() {
+        let raw = RawBundle {
+            version: 1,
+            flags: FLAG_COMPRESSED,
+            initiator_data: PeerId::random().to_bytes().into(),
+            message_type: Type::BlocksResponse,
+            message_data: compress(&vec![0; MAX_DECOMPRESSED_SIZE + 1])
+                .unwrap()
+                .into(),
+        };
+        let data = minicbor::to_vec(raw).unwrap();
+
+        assert!(Bundle::from_bytes(&data).is_err());
+    }
 }
@@ -0,0 +1,47 @@
+use super::Message;
+use crate::error::{Error, Result};
+use minicbor::{Decode, Encode};
+use pactus_types::block::Block;
+use pactus_types::certificate::Certificate;
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct BlockAnnounceMessage {
+    #[n(1)]
+    pub height: i32,
+    #[n(2)]
+    pub block: Block,
+    #[n(3)]
+    pub certificate: Certificate,
+}
+
+impl BlockAnnounceMessage {
+    pub fn new(height: i32, block: Block, certificate: Certificate) -> Self {
+        BlockAnnounceMessage {
+            height,
+            block,
+            certificate,
+        }
+    }
+}
+
+impl Message for BlockAnnounceMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        if self.height <= 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid height: {}",
+                self.height
+            )));
+        }
+        self.certificate.basic_check()?;
+        Ok(())
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::BlockAnnounce
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
@@ -0,0 +1,74 @@
+use super::Message;
+use crate::error::{Error, Result};
+use minicbor::{Decode, Encode};
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct BlocksRequestMessage {
+    #[n(1)]
+    pub session_id: i32,
+    #[n(2)]
+    pub from: i32,
+    #[n(3)]
+    pub count: i32,
+}
+
+impl BlocksRequestMessage {
+    pub fn new(session_id: i32, from: i32, count: i32) -> Self {
+        BlocksRequestMessage {
+            session_id,
+            from,
+            count,
+        }
+    }
+
+    /// Returns the last height that is requested.
+    pub fn to(&self) -> i32 {
+        self.from.saturating_add(self.count.saturating_sub(1))
+    }
+}
+
+impl Message for BlocksRequestMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        if self.from <= 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid height: {}",
+                self.from
+            )));
+        }
+        if self.count <= 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid count: {}",
+                self.count
+            )));
+        }
+        Ok(())
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::BlocksRequest
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_check() {
+        assert!(BlocksRequestMessage::new(1, 1, 10).basic_check().is_ok());
+        assert!(BlocksRequestMessage::new(1, 0, 10).basic_check().is_err());
+        assert!(BlocksRequestMessage::new(1, 1, 0).basic_check().is_err());
+        assert!(BlocksRequestMessage::new(1, 1, -1).basic_check().is_err());
+    }
+
+    #[test]
+    fn test_to() {
+        assert_eq!(BlocksRequestMessage::new(1, 10, 5).to(), 14);
+        assert_eq!(BlocksRequestMessage::new(1, i32::MAX - 1, i32::MAX).to(), i32::MAX);
+    }
+}
@@ -0,0 +1,78 @@
+use super::Message;
+use crate::error::{Error, Result};
+use minicbor::{bytes::ByteVec, Decode, Encode};
+use pactus_types::certificate::Certificate;
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct BlocksResponseMessage {
+    #[n(1)]
+    pub response_code: i32,
+    #[n(2)]
+    pub session_id: i32,
+    #[n(3)]
+    pub from: i32,
+    #[n(4)]
+    pub blocks_data: Vec<ByteVec>,
+    #[n(6)]
+    pub last_certificate: Option<Certificate>,
+    #[n(7)]
+    pub reason: String,
+}
+
+impl BlocksResponseMessage {
+    pub fn new(
+        response_code: i32,
+        session_id: i32,
+        from: i32,
+        blocks_data: Vec<Vec<u8>>,
+        last_certificate: Option<Certificate>,
+        reason: String,
+    ) -> Self {
+        BlocksResponseMessage {
+            response_code,
+            session_id,
+            from,
+            blocks_data: blocks_data.into_iter().map(ByteVec::from).collect(),
+            last_certificate,
+            reason,
+        }
+    }
+
+    /// Returns the last height in this response.
+    pub fn to(&self) -> i32 {
+        if self.blocks_data.is_empty() {
+            return 0;
+        }
+        self.from + self.blocks_data.len() as i32 - 1
+    }
+}
+
+impl Message for BlocksResponseMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        if self.from < 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid height: {}",
+                self.from
+            )));
+        }
+        if !(super::RESPONSE_CODE_OK..=super::RESPONSE_CODE_SYNCED).contains(&self.response_code) {
+            return Err(Error::InvalidMessage(format!(
+                "invalid response code: {}",
+                self.response_code
+            )));
+        }
+        if let Some(cert) = &self.last_certificate {
+            cert.basic_check()?;
+        }
+        Ok(())
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::BlocksResponse
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
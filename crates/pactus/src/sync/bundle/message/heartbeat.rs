@@ -1,19 +1,27 @@
 use super::Message;
 use crate::error::{Error, Result};
 use minicbor::{Decode, Encode};
-use std::any::Any;
 use pactus_types::hash::Hash32;
 
 #[derive(Debug, Encode, Decode)]
 #[cbor(map)]
-
 pub struct HeartbeatMessage {
     #[n(1)]
-    height: i32,
+    pub height: i32,
     #[n(2)]
-    round: i32,
+    pub round: i32,
     #[n(3)]
-    prev_block_hash: Hash32,
+    pub prev_block_hash: Hash32,
+}
+
+impl HeartbeatMessage {
+    pub fn new(height: i32, round: i32, prev_block_hash: Hash32) -> Self {
+        HeartbeatMessage {
+            height,
+            round,
+            prev_block_hash,
+        }
+    }
 }
 
 impl Message for HeartbeatMessage {
@@ -27,7 +35,7 @@ impl Message for HeartbeatMessage {
         if self.round < 0 {
             return Err(Error::InvalidMessage(format!(
                 "invalid round: {}",
-                self.height
+                self.round
             )));
         }
         Ok(())
@@ -40,12 +48,4 @@ impl Message for HeartbeatMessage {
     fn to_bytes(&self) -> Result<Vec<u8>> {
         Ok(minicbor::to_vec(self)?)
     }
-
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
 }
@@ -2,13 +2,16 @@ use super::Message;
 use crate::error::{Error, Result};
 use libp2p::PeerId;
 use minicbor::{bytes::ByteVec, Decode, Encode};
-use std::any::Any;
+use pactus_types::crypto::bls;
 use pactus_types::crypto::public_key::PublicKey;
 use pactus_types::crypto::signature::Signature;
 use pactus_types::crypto::signer::Signable;
 use pactus_types::crypto::KeyPairType;
 use pactus_types::hash::Hash32;
 
+/// The peer should respond to this hello message.
+pub const FLAG_NEED_RESPONSE: u32 = 0x01;
+
 #[derive(Debug, Encode, Decode)]
 #[cbor(map)]
 pub struct HelloMessage {
@@ -18,8 +21,9 @@ pub struct HelloMessage {
     pub agent: String,
     #[n(3)]
     pub moniker: String,
+    /// The validator keys of the node. The signature is aggregated from all of them.
     #[n(4)]
-    pub public_key_data: ByteVec,
+    pub public_keys_data: Vec<ByteVec>,
     #[n(5)]
     pub signature_data: ByteVec,
     #[n(6)]
@@ -41,7 +45,7 @@ impl HelloMessage {
         HelloMessage {
             peer_id_data: ByteVec::from(peer_id.to_bytes()),
             agent: crate::agent(),
-            public_key_data: ByteVec::from(Vec::new()),
+            public_keys_data: Vec::new(),
             signature_data: ByteVec::from(Vec::new()),
             moniker,
             height,
@@ -50,18 +54,23 @@ impl HelloMessage {
         }
     }
 
-    pub fn peer_id(&self) -> PeerId {
-        PeerId::from_bytes(&self.peer_id_data).unwrap()
+    pub fn peer_id(&self) -> Result<PeerId> {
+        PeerId::from_bytes(&self.peer_id_data).map_err(|err| Error::DecodeError(err.to_string()))
+    }
+
+    pub fn need_response(&self) -> bool {
+        self.flags & FLAG_NEED_RESPONSE == FLAG_NEED_RESPONSE
     }
 
-    fn public_key(&self) -> PublicKey {
-        PublicKey::from_bytes(KeyPairType::KeyPairBLS, &self.public_key_data).unwrap()
-        // TODO; no unwrap here
+    pub fn public_keys(&self) -> Result<Vec<PublicKey>> {
+        self.public_keys_data
+            .iter()
+            .map(|data| Ok(PublicKey::from_bytes(KeyPairType::KeyPairBLS, data)?))
+            .collect()
     }
 
-    fn signature(&self) -> Signature {
-        Signature::from_bytes(KeyPairType::KeyPairBLS, &self.signature_data).unwrap()
-        // TODO; no unwrap here
+    pub fn signature(&self) -> Result<Signature> {
+        Ok(Signature::from_bytes(KeyPairType::KeyPairBLS, &self.signature_data)?)
     }
 }
 
@@ -74,11 +83,22 @@ impl Message for HelloMessage {
             )));
         }
 
-        if !self
-            .public_key()
-            .verify(&self.signature(), &self.sign_bytes())
-        {
-            return Err(Error::InvalidMessage("invalid public key".to_string()));
+        // The signature covers the peer ID, so it should be valid
+        self.peer_id()?;
+        let public_keys: Vec<bls::public_key::PublicKey> = self
+            .public_keys()?
+            .into_iter()
+            .map(|pk| match pk {
+                PublicKey::BLS(pk) => pk,
+            })
+            .collect();
+        // Nodes without a validator key don't sign the message
+        if public_keys.is_empty() {
+            return Ok(());
+        }
+        let Signature::BLS(sig) = self.signature()?;
+        if !bls::public_key::PublicKey::aggregate(&public_keys).verify(&sig, &self.sign_bytes()) {
+            return Err(Error::InvalidMessage("invalid signature".to_string()));
         }
         Ok(())
     }
@@ -90,24 +110,90 @@ impl Message for HelloMessage {
     fn to_bytes(&self) -> Result<Vec<u8>> {
         Ok(minicbor::to_vec(self)?)
     }
-
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
 }
 
 impl Signable for HelloMessage {
     fn sign_bytes(&self) -> Vec<u8> {
-        format!("{}:{}:{}", self.message_type(), self.agent, self.peer_id()).into_bytes()
+        let peer_id = self
+            .peer_id()
+            .map(|peer_id| peer_id.to_string())
+            .unwrap_or_default();
+        format!("{}:{}:{}", self.message_type(), self.agent, peer_id).into_bytes()
     }
     fn set_public_key(&mut self, pk: PublicKey) {
-        self.public_key_data = ByteVec::from(pk.to_bytes())
+        self.public_keys_data.push(ByteVec::from(pk.to_bytes()))
     }
+    /// Signing the message with several keys aggregates the signatures.
     fn set_signature(&mut self, sig: Signature) {
+        let Signature::BLS(sig) = sig;
+        let sig = match self.signature() {
+            Ok(Signature::BLS(existing)) => bls::signature::Signature::aggregate(&[existing, sig]),
+            Err(_) => sig,
+        };
         self.signature_data = ByteVec::from(sig.to_bytes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::crypto::secret_key::SecretKey;
+    use pactus_types::crypto::signer::Signer;
+
+    fn genesis_hash() -> Hash32 {
+        Hash32::calculate("genesis".as_bytes())
+    }
+
+    #[test]
+    fn test_signature() {
+        let signer = Signer::new(SecretKey::BLS(bls::secret_key::SecretKey::random()));
+        let mut msg = HelloMessage::new(PeerId::random(), "alice".to_string(), 1, 0, genesis_hash());
+        assert!(msg.basic_check().is_ok());
+
+        signer.sign(&mut msg);
+        assert!(msg.basic_check().is_ok());
+
+        // A public key without its signature
+        let peer_id = msg.peer_id().unwrap();
+        let mut unsigned = HelloMessage::new(peer_id, "alice".to_string(), 1, 0, genesis_hash());
+        unsigned.set_public_key(signer.public_key());
+        assert!(unsigned.basic_check().is_err());
+
+        // The signature doesn't cover another peer ID
+        msg.peer_id_data = ByteVec::from(PeerId::random().to_bytes());
+        assert!(msg.basic_check().is_err());
+    }
+
+    #[test]
+    fn test_multiple_keys() {
+        let signers: Vec<Signer> = (0..3)
+            .map(|_| Signer::new(SecretKey::BLS(bls::secret_key::SecretKey::random())))
+            .collect();
+        let mut msg = HelloMessage::new(PeerId::random(), "alice".to_string(), 1, 0, genesis_hash());
+        for signer in &signers {
+            signer.sign(&mut msg);
+        }
+        assert!(msg.basic_check().is_ok());
+        let public_keys: Vec<PublicKey> = signers.iter().map(|s| s.public_key()).collect();
+        assert_eq!(msg.public_keys().unwrap(), public_keys);
+
+        // All the keys should sign the message
+        msg.public_keys_data.pop();
+        assert!(msg.basic_check().is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        let msg = HelloMessage::new(PeerId::random(), "bob".to_string(), 1, 0, genesis_hash());
+        assert!(!msg.need_response());
+
+        let msg = HelloMessage::new(
+            PeerId::random(),
+            "bob".to_string(),
+            1,
+            FLAG_NEED_RESPONSE,
+            genesis_hash(),
+        );
+        assert!(msg.need_response());
+    }
+}
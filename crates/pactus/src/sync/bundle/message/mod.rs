@@ -1,12 +1,23 @@
+pub mod block_announce;
+pub mod blocks_request;
+pub mod blocks_response;
 pub mod heartbeat;
 pub mod hello;
+pub mod proposal;
+pub mod query_proposal;
+pub mod query_votes;
+pub mod transactions;
+pub mod vote;
 
 pub use crate::error::Result;
 use core::fmt::Debug;
 use minicbor::{Decode, Encode};
-use std::{any::Any, fmt};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, Decode, Encode, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 #[cbor(index_only)]
 pub enum Type {
     #[n(1)]
@@ -35,9 +46,6 @@ pub trait Message: Debug {
     fn basic_check(&self) -> Result<()>;
     fn message_type(&self) -> Type;
     fn to_bytes(&self) -> Result<Vec<u8>>;
-
-    fn as_any(&self) -> &dyn Any;
-    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 impl fmt::Display for Type {
@@ -57,10 +65,10 @@ impl fmt::Display for Type {
     }
 }
 
-//const RESPONSE_CODE_NONE: i32 = -1;
-//const RESPONSE_CODE_OK: i32 = 0;
-//const RESPONSE_CODE_REJECTED: i32 = 1;
-//const RESPONSE_CODE_BUSY: i32 = 2;
-//const RESPONSE_CODE_MOREBLOCKS: i32 = 3;
-//const RESPONSE_CODE_NOMOREBLOCKS: i32 = 4;
-//const RESPONSE_CODE_SYNCED: i32 = 5;
+pub const RESPONSE_CODE_NONE: i32 = -1;
+pub const RESPONSE_CODE_OK: i32 = 0;
+pub const RESPONSE_CODE_REJECTED: i32 = 1;
+pub const RESPONSE_CODE_BUSY: i32 = 2;
+pub const RESPONSE_CODE_MOREBLOCKS: i32 = 3;
+pub const RESPONSE_CODE_NOMOREBLOCKS: i32 = 4;
+pub const RESPONSE_CODE_SYNCED: i32 = 5;
@@ -0,0 +1,31 @@
+use super::Message;
+use crate::error::Result;
+use minicbor::{Decode, Encode};
+use pactus_types::proposal::Proposal;
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct ProposalMessage {
+    #[n(1)]
+    pub proposal: Proposal,
+}
+
+impl ProposalMessage {
+    pub fn new(proposal: Proposal) -> Self {
+        ProposalMessage { proposal }
+    }
+}
+
+impl Message for ProposalMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        Ok(self.proposal.basic_check()?)
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::Proposal
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
@@ -0,0 +1,44 @@
+use super::Message;
+use crate::error::{Error, Result};
+use minicbor::{Decode, Encode};
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct QueryProposalMessage {
+    #[n(1)]
+    pub height: i32,
+    #[n(2)]
+    pub round: i32,
+}
+
+impl QueryProposalMessage {
+    pub fn new(height: i32, round: i32) -> Self {
+        QueryProposalMessage { height, round }
+    }
+}
+
+impl Message for QueryProposalMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        if self.height <= 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid height: {}",
+                self.height
+            )));
+        }
+        if self.round < 0 {
+            return Err(Error::InvalidMessage(format!(
+                "invalid round: {}",
+                self.round
+            )));
+        }
+        Ok(())
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::QueryProposal
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
@@ -0,0 +1,38 @@
+use super::Message;
+use crate::error::{Error, Result};
+use minicbor::{Decode, Encode};
+use pactus_types::block::Transactions;
+use pactus_types::transaction::Transaction;
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct TransactionsMessage {
+    #[n(1)]
+    #[cbor(with = "Transactions")]
+    pub transactions: Transactions,
+}
+
+impl TransactionsMessage {
+    pub fn new(txs: Vec<Transaction>) -> Self {
+        TransactionsMessage {
+            transactions: Transactions::from(txs),
+        }
+    }
+}
+
+impl Message for TransactionsMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        if self.transactions.is_empty() {
+            return Err(Error::InvalidMessage("no transactions".to_string()));
+        }
+        Ok(())
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::Transactions
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
@@ -0,0 +1,31 @@
+use super::Message;
+use crate::error::Result;
+use minicbor::{Decode, Encode};
+use pactus_types::vote::Vote;
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+pub struct VoteMessage {
+    #[n(1)]
+    pub vote: Vote,
+}
+
+impl VoteMessage {
+    pub fn new(vote: Vote) -> Self {
+        VoteMessage { vote }
+    }
+}
+
+impl Message for VoteMessage {
+    fn basic_check(&self) -> super::Result<()> {
+        Ok(self.vote.basic_check()?)
+    }
+
+    fn message_type(&self) -> super::Type {
+        super::Type::Vote
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(minicbor::to_vec(self)?)
+    }
+}
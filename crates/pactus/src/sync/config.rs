@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use crate::sync::bundle::message::Type as MessageType;
 use crate::sync::firewall;
 use serde::{Deserialize, Serialize};
 
@@ -8,7 +9,21 @@ pub struct Config {
     pub moniker: String,
     // TODO: save/load as string like: "5s"
     pub heartbeat_timeout: Duration,
+    pub block_per_message: i32,
+    pub latest_block_interval: i32,
+    pub max_open_sessions: usize,
+    pub session_timeout: Duration,
     pub firewall: firewall::config::Config,
+    pub rate_limits: Vec<RateLimit>,
+}
+
+/// Token bucket limit for one message type: each peer can send `burst` messages at once,
+/// and `per_second` messages per second after that.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub message_type: MessageType,
+    pub burst: u32,
+    pub per_second: f64,
 }
 
 impl Default for Config {
@@ -16,7 +31,63 @@ impl Default for Config {
         Self {
             moniker: "".to_string(),
             heartbeat_timeout: Duration::from_secs(5),
+            block_per_message: 10,
+            latest_block_interval: 720,
+            max_open_sessions: 8,
+            session_timeout: Duration::from_secs(10),
             firewall: firewall::config::Config::default(),
+            rate_limits: vec![
+                RateLimit {
+                    message_type: MessageType::Hello,
+                    burst: 3,
+                    per_second: 0.1,
+                },
+                RateLimit {
+                    message_type: MessageType::Heartbeat,
+                    burst: 5,
+                    per_second: 1.0,
+                },
+                RateLimit {
+                    message_type: MessageType::BlockAnnounce,
+                    burst: 10,
+                    per_second: 2.0,
+                },
+                RateLimit {
+                    message_type: MessageType::BlocksRequest,
+                    burst: 10,
+                    per_second: 1.0,
+                },
+                RateLimit {
+                    message_type: MessageType::BlocksResponse,
+                    burst: 100,
+                    per_second: 50.0,
+                },
+                RateLimit {
+                    message_type: MessageType::Proposal,
+                    burst: 5,
+                    per_second: 1.0,
+                },
+                RateLimit {
+                    message_type: MessageType::QueryProposal,
+                    burst: 5,
+                    per_second: 1.0,
+                },
+                RateLimit {
+                    message_type: MessageType::QueryVotes,
+                    burst: 5,
+                    per_second: 1.0,
+                },
+                RateLimit {
+                    message_type: MessageType::Transactions,
+                    burst: 20,
+                    per_second: 5.0,
+                },
+                RateLimit {
+                    message_type: MessageType::Vote,
+                    burst: 20,
+                    per_second: 5.0,
+                },
+            ],
         }
     }
 }
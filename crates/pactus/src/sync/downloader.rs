@@ -0,0 +1,303 @@
+use libp2p::PeerId;
+use pactus_types::block::Block;
+use pactus_types::certificate::Certificate;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// An open download session with a peer.
+#[derive(Debug)]
+pub(super) struct Session {
+    pub peer_id: PeerId,
+    pub from: i32,
+    pub count: i32,
+    pub started: Instant,
+}
+
+impl Session {
+    /// Returns true if the heights `[from, from + count)` are requested in this session.
+    pub fn covers(&self, from: i32, count: i32) -> bool {
+        let end = self.from as i64 + self.count as i64;
+        from >= self.from && count >= 0 && from as i64 + count as i64 <= end
+    }
+}
+
+/// Downloader schedules the block requests and keeps the downloaded blocks
+/// until they can be committed in order.
+pub(super) struct Downloader {
+    block_per_session: i32,
+    max_open_sessions: usize,
+    session_timeout: Duration,
+    next_session_id: i32,
+    sessions: HashMap<i32, Session>,
+    blocks: BTreeMap<i32, Block>,
+    certificates: BTreeMap<i32, Certificate>,
+}
+
+impl Downloader {
+    pub fn new(block_per_session: i32, max_open_sessions: usize, session_timeout: Duration) -> Self {
+        Self {
+            block_per_session,
+            max_open_sessions,
+            session_timeout,
+            next_session_id: 0,
+            sessions: HashMap::new(),
+            blocks: BTreeMap::new(),
+            certificates: BTreeMap::new(),
+        }
+    }
+
+    /// Opens new sessions with the peers that are ahead of us.
+    /// It returns the session ID, the target peer, and the range of heights to request.
+    pub fn schedule(
+        &mut self,
+        our_height: i32,
+        peer_heights: &HashMap<PeerId, i32>,
+    ) -> Vec<(i32, PeerId, i32, i32)> {
+        let timeout = self.session_timeout;
+        self.sessions
+            .retain(|_, session| session.started.elapsed() < timeout);
+        self.blocks = self.blocks.split_off(&(our_height + 1));
+        self.certificates = self.certificates.split_off(&(our_height + 1));
+
+        let mut requests = Vec::new();
+        let mut peers: Vec<(&PeerId, &i32)> = peer_heights.iter().collect();
+        // Ask the peers with the highest heights first
+        peers.sort_by(|a, b| b.1.cmp(a.1));
+
+        let mut next_height = our_height + 1;
+        while self.sessions.len() < self.max_open_sessions {
+            let (from, end) = self.next_gap(next_height);
+            // Spread the sessions between the peers that have the next block
+            let candidate = peers
+                .iter()
+                .filter(|(_, height)| **height >= from)
+                .min_by_key(|(peer_id, _)| self.session_count(peer_id));
+            let (peer_id, peer_height) = match candidate {
+                Some((peer_id, peer_height)) => (**peer_id, **peer_height),
+                None => break,
+            };
+
+            let count = self
+                .block_per_session
+                .min(peer_height - from + 1)
+                .min(end - from);
+            let session_id = self.open_session(peer_id, from, count);
+            requests.push((session_id, peer_id, from, count));
+            next_height = from + count;
+        }
+
+        requests
+    }
+
+    /// Returns the first range of heights, starting from `height`, that is neither
+    /// downloaded nor requested. The end of the range is exclusive.
+    fn next_gap(&self, mut height: i32) -> (i32, i32) {
+        loop {
+            if self.blocks.contains_key(&height) {
+                height += 1;
+                continue;
+            }
+            match self
+                .sessions
+                .values()
+                .find(|s| s.from <= height && height < s.from + s.count)
+            {
+                Some(session) => height = session.from + session.count,
+                None => break,
+            }
+        }
+
+        let mut end = i32::MAX;
+        if let Some((next, _)) = self.blocks.range(height..).next() {
+            end = *next;
+        }
+        for session in self.sessions.values() {
+            if session.from > height {
+                end = end.min(session.from);
+            }
+        }
+        (height, end)
+    }
+
+    fn session_count(&self, peer_id: &PeerId) -> usize {
+        self.sessions
+            .values()
+            .filter(|s| &s.peer_id == peer_id)
+            .count()
+    }
+
+    fn open_session(&mut self, peer_id: PeerId, from: i32, count: i32) -> i32 {
+        self.next_session_id += 1;
+        let session_id = self.next_session_id;
+        self.sessions.insert(
+            session_id,
+            Session {
+                peer_id,
+                from,
+                count,
+                started: Instant::now(),
+            },
+        );
+        session_id
+    }
+
+    pub fn session(&self, session_id: i32) -> Option<&Session> {
+        self.sessions.get(&session_id)
+    }
+
+    pub fn close_session(&mut self, session_id: i32) {
+        self.sessions.remove(&session_id);
+    }
+
+    pub fn has_open_session(&self) -> bool {
+        !self.sessions.is_empty()
+    }
+
+    pub fn add_block(&mut self, height: i32, block: Block) {
+        self.blocks.insert(height, block);
+    }
+
+    pub fn add_certificate(&mut self, height: i32, cert: Certificate) {
+        self.certificates.insert(height, cert);
+    }
+
+    /// Adds an announced block, unless the block or the certificate of the height is known.
+    pub fn add_announced_block(&mut self, height: i32, block: Block, cert: Certificate) {
+        self.blocks.entry(height).or_insert(block);
+        self.certificates.entry(height).or_insert(cert);
+    }
+
+    /// Returns the number of heights after our height that can be downloaded at once.
+    pub fn window(&self) -> i32 {
+        self.block_per_session.saturating_mul(self.max_open_sessions as i32)
+    }
+
+    /// Removes the block at the given height, if it can't be committed.
+    pub fn remove_block(&mut self, height: i32) {
+        self.blocks.remove(&height);
+        self.certificates.remove(&height);
+    }
+
+    /// Returns the next block to commit with its certificate.
+    /// The certificate of a block is either known or it is the previous certificate of the next block.
+    pub fn next_committable(&mut self, our_height: i32) -> Option<(Block, Certificate)> {
+        let height = our_height + 1;
+        if !self.blocks.contains_key(&height) {
+            return None;
+        }
+        let cert = match self.certificates.get(&height) {
+            Some(cert) => cert.clone(),
+            None => self.blocks.get(&(height + 1))?.prev_cert()?.clone(),
+        };
+        let block = self.blocks.remove(&height)?;
+        self.certificates.remove(&height);
+        Some((block, cert))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pactus_types::address::Address;
+    use pactus_types::block::{BlockHeader, Transactions};
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::hash::Hash32;
+    use pactus_types::sortition::seed::Seed;
+
+    fn block(time: i64) -> (Block, Certificate) {
+        let key = SecretKey::random();
+        let header = BlockHeader::new(
+            1,
+            time,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Seed::UNDEF,
+            Address::from_bytes(&[1; 21]).unwrap(),
+        );
+        let block = Block::new(header, None, Transactions::from(Vec::new()));
+        let block_hash = block.hash().unwrap();
+        let sig = key.sign(&Certificate::sign_bytes(&block_hash, 0));
+        let cert = Certificate::new(block_hash, 0, vec![0], Vec::new(), &sig);
+        (block, cert)
+    }
+
+    #[test]
+    fn test_schedule() {
+        let mut downloader = Downloader::new(10, 2, Duration::from_secs(10));
+        let peer_1 = PeerId::random();
+        let peer_2 = PeerId::random();
+        let peer_3 = PeerId::random();
+        let mut peer_heights = HashMap::new();
+        peer_heights.insert(peer_1, 25);
+        peer_heights.insert(peer_2, 15);
+        peer_heights.insert(peer_3, 5);
+
+        let requests = downloader.schedule(5, &peer_heights);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0], (1, peer_1, 6, 10));
+        assert_eq!(requests[1], (2, peer_1, 16, 10));
+
+        // No more sessions can be opened
+        assert!(downloader.schedule(5, &peer_heights).is_empty());
+
+        downloader.close_session(2);
+        peer_heights.insert(peer_2, 30);
+        let requests = downloader.schedule(5, &peer_heights);
+        assert_eq!(requests, vec![(3, peer_2, 16, 10)]);
+
+        // The gap should be requested again
+        downloader.close_session(1);
+        let requests = downloader.schedule(5, &peer_heights);
+        assert_eq!(requests, vec![(4, peer_1, 6, 10)]);
+    }
+
+    #[test]
+    fn test_session_covers() {
+        let mut downloader = Downloader::new(10, 2, Duration::from_secs(10));
+        let peer_1 = PeerId::random();
+        let mut peer_heights = HashMap::new();
+        peer_heights.insert(peer_1, 25);
+
+        downloader.schedule(5, &peer_heights);
+        let session = downloader.session(1).unwrap();
+        assert!(session.covers(6, 10));
+        assert!(session.covers(10, 6));
+        assert!(!session.covers(5, 1));
+        assert!(!session.covers(10, 7));
+        assert!(!session.covers(16, 1));
+    }
+
+    #[test]
+    fn test_session_timeout() {
+        let mut downloader = Downloader::new(10, 2, Duration::from_secs(0));
+        let peer_1 = PeerId::random();
+        let mut peer_heights = HashMap::new();
+        peer_heights.insert(peer_1, 25);
+
+        let requests = downloader.schedule(0, &peer_heights);
+        assert_eq!(requests, vec![(1, peer_1, 1, 10), (2, peer_1, 11, 10)]);
+
+        // The previous sessions are expired, so they are requested again
+        let requests = downloader.schedule(0, &peer_heights);
+        assert_eq!(requests, vec![(3, peer_1, 1, 10), (4, peer_1, 11, 10)]);
+        assert!(downloader.session(1).is_none());
+    }
+
+    #[test]
+    fn test_announced_block() {
+        let mut downloader = Downloader::new(10, 2, Duration::from_secs(10));
+        assert_eq!(downloader.window(), 20);
+
+        let (first, first_cert) = block(1);
+        let (second, second_cert) = block(2);
+        downloader.add_announced_block(1, first.clone(), first_cert);
+        // The announced block doesn't overwrite the known one
+        downloader.add_announced_block(1, second, second_cert);
+
+        let (block, cert) = downloader.next_committable(0).unwrap();
+        assert_eq!(block.hash().unwrap(), first.hash().unwrap());
+        assert_eq!(cert.block_hash(), &first.hash().unwrap());
+    }
+}
@@ -1,12 +1,24 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub enabled: bool,
+    /// Number of invalid bundles that a peer can send before being banned.
+    pub ban_threshold: usize,
+    /// How long a banned peer stays banned.
+    pub ban_duration: Duration,
+    /// Maximum size of a bundle in bytes.
+    pub max_bundle_size: usize,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            ban_threshold: 10,
+            ban_duration: Duration::from_secs(60 * 60),
+            max_bundle_size: 4 * 1024 * 1024,
+        }
     }
 }
@@ -1,18 +1,322 @@
-use super::{config::Config};
-use crate::{error::Result, sync::bundle::bundle::Bundle};
+use super::config::Config;
+use super::rate_limit::RateLimiter;
+use crate::error::{Error, Result};
+use crate::metrics::Metrics;
+use crate::network::NetworkMessage;
+use crate::sync::bundle::bundle::{Bundle, RawBundle, FLAG_NETWORK_MASK};
+use crate::sync::config::RateLimit;
+use crate::sync::peerset::peerset::PeerSet;
+use async_std::channel::Sender;
+use libp2p::PeerId;
+use log::warn;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
 
+/// Firewall checks the received bundles and bans the peers that send invalid ones.
 pub(crate) struct Firewall {
     config: Config,
+    network_flag: i32,
+    peer_set: Arc<RwLock<PeerSet>>,
+    rate_limiter: Mutex<RateLimiter>,
+    network_message_sender: Sender<NetworkMessage>,
+    metrics: Arc<Metrics>,
 }
 
 impl Firewall {
-    pub fn new(config: &Config) -> Result<Self> {
+    pub fn new(
+        config: &Config,
+        network_flag: i32,
+        rate_limits: &[RateLimit],
+        peer_set: Arc<RwLock<PeerSet>>,
+        network_message_sender: Sender<NetworkMessage>,
+        metrics: Arc<Metrics>,
+    ) -> Result<Self> {
         Ok(Firewall {
             config: config.clone(),
+            network_flag,
+            peer_set,
+            rate_limiter: Mutex::new(RateLimiter::new(rate_limits)),
+            network_message_sender,
+            metrics,
         })
     }
 
-    pub fn open_bundle(&self, data: &[u8]) -> Result<Bundle> {
-        Bundle::from_bytes(data)
+    /// Opens the bundle published by `source`.
+    pub fn open_bundle(&self, data: &[u8], source: PeerId) -> Result<Bundle> {
+        if !self.config.enabled {
+            return Bundle::from_bytes(data);
+        }
+
+        if self.is_banned(&source) {
+            self.close_connection(source);
+            return Err(Error::InvalidMessage(format!("peer {} is banned", source)));
+        }
+
+        match self.check_bundle(data, source) {
+            Ok(bdl) => Ok(bdl),
+            Err(err) => {
+                self.increase_invalid_messages(source);
+                Err(err)
+            }
+        }
+    }
+
+    fn check_bundle(&self, data: &[u8], source: PeerId) -> Result<Bundle> {
+        if data.len() > self.config.max_bundle_size {
+            return Err(Error::InvalidMessage(format!(
+                "bundle is too big: {} bytes",
+                data.len()
+            )));
+        }
+
+        let raw = RawBundle::from_bytes(data)?;
+        if !self
+            .rate_limiter
+            .lock()
+            .unwrap()
+            .allow(source, raw.message_type)
+        {
+            return Err(Error::InvalidMessage(format!(
+                "rate limit exceeded for {} messages",
+                raw.message_type
+            )));
+        }
+
+        if raw.flags & FLAG_NETWORK_MASK != self.network_flag {
+            return Err(Error::InvalidMessage(format!(
+                "bundle belongs to another network, flags: {:#06x}",
+                raw.flags
+            )));
+        }
+
+        let bdl = Bundle::from_raw(raw)?;
+        if bdl.initiator != source {
+            return Err(Error::InvalidMessage(format!(
+                "bundle initiator doesn't match the source: {}",
+                bdl.initiator
+            )));
+        }
+        Ok(bdl)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.is_banned_at(peer_id, Instant::now())
+    }
+
+    fn is_banned_at(&self, peer_id: &PeerId, now: Instant) -> bool {
+        let mut peer_set = self.peer_set.write().unwrap();
+        peer_set.lift_expired_ban(peer_id, now);
+        peer_set
+            .get_peer(peer_id)
+            .is_some_and(|peer| peer.is_banned())
+    }
+
+    /// Reports a peer that sent an invalid message, it counts toward the peer's ban score.
+    pub fn report_invalid_message(&self, peer_id: PeerId) {
+        if self.config.enabled {
+            self.increase_invalid_messages(peer_id);
+        }
+    }
+
+    fn increase_invalid_messages(&self, peer_id: PeerId) {
+        let mut peer_set = self.peer_set.write().unwrap();
+        peer_set.increase_invalid_messages(&peer_id);
+
+        let invalid_messages = peer_set
+            .get_peer(&peer_id)
+            .map_or(0, |peer| peer.invalid_messages);
+        if invalid_messages >= self.config.ban_threshold {
+            warn!(
+                "peer {} is banned, invalid messages: {}",
+                peer_id, invalid_messages
+            );
+            drop(peer_set);
+            self.ban(peer_id);
+        }
+    }
+
+    /// Bans the peer for the ban duration and closes its connection.
+    pub fn ban(&self, peer_id: PeerId) {
+        self.peer_set
+            .write()
+            .unwrap()
+            .ban_peer(&peer_id, Instant::now() + self.config.ban_duration);
+        self.metrics.inc_firewall_bans();
+        self.close_connection(peer_id);
+    }
+
+    fn close_connection(&self, peer_id: PeerId) {
+        let msg = NetworkMessage::CloseConnection { target: peer_id };
+        if self.network_message_sender.try_send(msg).is_err() {
+            warn!("unable to close the connection with {}", peer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::create_metrics;
+    use crate::sync::bundle::bundle::{FLAG_COMPRESSED, FLAG_NETWORK_MAINNET, FLAG_NETWORK_TESTNET};
+    use crate::sync::bundle::message::blocks_response::BlocksResponseMessage;
+    use crate::sync::bundle::message::heartbeat::HeartbeatMessage;
+    use crate::sync::bundle::message::{Message, RESPONSE_CODE_MOREBLOCKS};
+    use async_std::channel::{unbounded, Receiver};
+    use pactus_types::hash::Hash32;
+    use std::time::Duration;
+
+    fn setup_network(config: Config, network_flag: i32) -> (Firewall, Receiver<NetworkMessage>) {
+        let (sender, receiver) = unbounded();
+        let peer_set = Arc::new(RwLock::new(PeerSet::new()));
+        let firewall =
+            Firewall::new(&config, network_flag, &[], peer_set, sender, create_metrics()).unwrap();
+        (firewall, receiver)
+    }
+
+    fn setup(config: Config) -> (Firewall, Receiver<NetworkMessage>) {
+        setup_network(config, 0)
+    }
+
+    fn bundle_with_flags(initiator: PeerId, msg: &dyn Message, flags: i32) -> Vec<u8> {
+        let mut bdl = Bundle::new(initiator, msg).unwrap();
+        bdl.flags = flags;
+        bdl.to_bytes().unwrap()
+    }
+
+    fn bundle(initiator: PeerId) -> Vec<u8> {
+        let msg = HeartbeatMessage::new(1, 0, Hash32::calculate(b"block"));
+        bundle_with_flags(initiator, &msg, 0)
+    }
+
+    fn invalid_messages(firewall: &Firewall, peer_id: &PeerId) -> usize {
+        let peer_set = firewall.peer_set.read().unwrap();
+        peer_set.get_peer(peer_id).map_or(0, |peer| peer.invalid_messages)
+    }
+
+    #[test]
+    fn test_ban_threshold() {
+        let config = Config {
+            ban_threshold: 3,
+            ..Config::default()
+        };
+        let (firewall, receiver) = setup(config);
+        let source = PeerId::random();
+
+        assert!(firewall.open_bundle(&bundle(source), source).is_ok());
+        for _ in 0..2 {
+            assert!(firewall.open_bundle(&[1, 2, 3], source).is_err());
+        }
+        assert!(!firewall.is_banned(&source));
+        assert!(receiver.try_recv().is_err());
+
+        assert!(firewall.open_bundle(&[1, 2, 3], source).is_err());
+        assert!(firewall.is_banned(&source));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(NetworkMessage::CloseConnection { target }) if target == source
+        ));
+
+        // Valid bundles of a banned peer are dropped too
+        assert!(firewall.open_bundle(&bundle(source), source).is_err());
+        assert!(!firewall.is_banned(&PeerId::random()));
+    }
+
+    #[test]
+    fn test_initiator() {
+        let (firewall, _receiver) = setup(Config::default());
+        let initiator = PeerId::random();
+        let source = PeerId::random();
+
+        assert!(firewall.open_bundle(&bundle(initiator), source).is_err());
+        assert_eq!(invalid_messages(&firewall, &source), 1);
+        assert_eq!(invalid_messages(&firewall, &initiator), 0);
+
+        let bdl = firewall.open_bundle(&bundle(initiator), initiator).unwrap();
+        assert_eq!(bdl.initiator, initiator);
+    }
+
+    #[test]
+    fn test_ban_expiry() {
+        let config = Config {
+            ban_threshold: 1,
+            ban_duration: Duration::from_secs(60),
+            ..Config::default()
+        };
+        let (firewall, _receiver) = setup(config);
+        let source = PeerId::random();
+
+        assert!(firewall.open_bundle(&[1, 2, 3], source).is_err());
+        let now = Instant::now();
+        assert!(firewall.is_banned_at(&source, now));
+        assert!(firewall.is_banned_at(&source, now + Duration::from_secs(30)));
+
+        assert!(!firewall.is_banned_at(&source, now + Duration::from_secs(61)));
+        assert_eq!(invalid_messages(&firewall, &source), 0);
+        assert!(firewall.open_bundle(&bundle(source), source).is_ok());
+    }
+
+    #[test]
+    fn test_ban() {
+        let (firewall, receiver) = setup(Config::default());
+        let source = PeerId::random();
+
+        firewall.ban(source);
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(NetworkMessage::CloseConnection { target }) if target == source
+        ));
+        assert!(firewall.is_banned(&source));
+        let later = Instant::now() + Config::default().ban_duration + Duration::from_secs(1);
+        assert!(!firewall.is_banned_at(&source, later));
+    }
+
+    #[test]
+    fn test_network_flag() {
+        let (mainnet, _receiver) = setup_network(Config::default(), FLAG_NETWORK_MAINNET);
+        let (testnet, _receiver) = setup_network(Config::default(), FLAG_NETWORK_TESTNET);
+        let (localnet, _receiver) = setup(Config::default());
+        let source = PeerId::random();
+        let msg = HeartbeatMessage::new(1, 0, Hash32::calculate(b"block"));
+
+        let mainnet_bundle = bundle_with_flags(source, &msg, FLAG_NETWORK_MAINNET);
+        let testnet_bundle = bundle_with_flags(source, &msg, FLAG_NETWORK_TESTNET);
+        let local_bundle = bundle_with_flags(source, &msg, 0);
+
+        assert!(mainnet.open_bundle(&mainnet_bundle, source).is_ok());
+        assert!(mainnet.open_bundle(&testnet_bundle, source).is_err());
+        assert!(mainnet.open_bundle(&local_bundle, source).is_err());
+        assert_eq!(invalid_messages(&mainnet, &source), 2);
+
+        assert!(testnet.open_bundle(&testnet_bundle, source).is_ok());
+        assert!(testnet.open_bundle(&mainnet_bundle, source).is_err());
+
+        assert!(localnet.open_bundle(&local_bundle, source).is_ok());
+        assert!(localnet.open_bundle(&mainnet_bundle, source).is_err());
+        assert!(localnet.open_bundle(&testnet_bundle, source).is_err());
+    }
+
+    #[test]
+    fn test_compressed_network_flag() {
+        let (mainnet, _receiver) = setup_network(Config::default(), FLAG_NETWORK_MAINNET);
+        let source = PeerId::random();
+        let msg = BlocksResponseMessage::new(
+            RESPONSE_CODE_MOREBLOCKS,
+            1,
+            1,
+            vec![vec![0; 4096]],
+            None,
+            "".to_string(),
+        );
+
+        let data = bundle_with_flags(source, &msg, FLAG_NETWORK_TESTNET);
+        let raw = RawBundle::from_bytes(&data).unwrap();
+        assert_eq!(raw.flags, FLAG_NETWORK_TESTNET | FLAG_COMPRESSED);
+        assert!(mainnet.open_bundle(&data, source).is_err());
+        assert_eq!(invalid_messages(&mainnet, &source), 1);
+
+        let data = bundle_with_flags(source, &msg, FLAG_NETWORK_MAINNET);
+        let bdl = mainnet.open_bundle(&data, source).unwrap();
+        assert_eq!(bdl.flags, FLAG_NETWORK_MAINNET);
+        assert_eq!(bdl.message_data, msg.to_bytes().unwrap());
     }
 }
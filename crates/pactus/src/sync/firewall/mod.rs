@@ -1,2 +1,3 @@
 pub mod config;
 pub mod firewall;
+mod rate_limit;
@@ -0,0 +1,165 @@
+use crate::sync::bundle::message::Type as MessageType;
+use crate::sync::config::RateLimit;
+use libp2p::PeerId;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Buckets are kept by peer ID, so reconnecting doesn't reset the limits.
+/// A bucket is dropped when it has been idle for this long and is full again.
+const BUCKET_TTL: Duration = Duration::from_secs(600);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A token bucket that holds up to `burst` tokens and refills `per_second` tokens every second.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    burst: f64,
+    per_second: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        TokenBucket {
+            tokens: limit.burst as f64,
+            burst: limit.burst as f64,
+            per_second: limit.per_second,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.burst);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    /// A bucket that is idle long enough to be refilled is the same as a new one.
+    fn is_expired(&self, now: Instant) -> bool {
+        let idle = now.saturating_duration_since(self.last_refill);
+        let refill_secs = if self.per_second > 0.0 {
+            self.burst / self.per_second
+        } else {
+            f64::INFINITY
+        };
+        idle >= BUCKET_TTL && idle.as_secs_f64() >= refill_secs
+    }
+}
+
+/// Limits the number of messages that each peer can send, per message type.
+/// Message types without a configured limit are not limited.
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    limits: HashMap<MessageType, RateLimit>,
+    buckets: HashMap<(PeerId, MessageType), TokenBucket>,
+    last_prune: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &[RateLimit]) -> Self {
+        RateLimiter {
+            limits: limits
+                .iter()
+                .map(|limit| (limit.message_type, limit.clone()))
+                .collect(),
+            buckets: HashMap::new(),
+            last_prune: None,
+        }
+    }
+
+    /// Returns false if the peer has exceeded the limit for this message type.
+    pub fn allow(&mut self, peer_id: PeerId, message_type: MessageType) -> bool {
+        self.allow_at(peer_id, message_type, Instant::now())
+    }
+
+    fn allow_at(&mut self, peer_id: PeerId, message_type: MessageType, now: Instant) -> bool {
+        let limit = match self.limits.get(&message_type) {
+            Some(limit) => limit,
+            None => return true,
+        };
+        let allowed = self
+            .buckets
+            .entry((peer_id, message_type))
+            .or_insert_with(|| TokenBucket::new(limit, now))
+            .try_take(now);
+        self.prune(now);
+        allowed
+    }
+
+    fn prune(&mut self, now: Instant) {
+        if let Some(last_prune) = self.last_prune {
+            if now.saturating_duration_since(last_prune) < PRUNE_INTERVAL {
+                return;
+            }
+        }
+        self.last_prune = Some(now);
+        self.buckets.retain(|_, bucket| !bucket.is_expired(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let limits = vec![RateLimit {
+            message_type: MessageType::Hello,
+            burst: 2,
+            per_second: 1.0,
+        }];
+        let mut limiter = RateLimiter::new(&limits);
+        let pid1 = PeerId::random();
+        let pid2 = PeerId::random();
+        let now = Instant::now();
+
+        assert!(limiter.allow_at(pid1, MessageType::Hello, now));
+        assert!(limiter.allow_at(pid1, MessageType::Hello, now));
+        assert!(!limiter.allow_at(pid1, MessageType::Hello, now));
+
+        // Other peers and unlimited types are not affected
+        assert!(limiter.allow_at(pid2, MessageType::Hello, now));
+        assert!(limiter.allow_at(pid1, MessageType::Heartbeat, now));
+
+        // One token is refilled after a second
+        let later = now + Duration::from_secs(1);
+        assert!(limiter.allow_at(pid1, MessageType::Hello, later));
+        assert!(!limiter.allow_at(pid1, MessageType::Hello, later));
+    }
+
+    #[test]
+    fn test_bucket_ttl() {
+        let limits = vec![RateLimit {
+            message_type: MessageType::Hello,
+            burst: 2,
+            per_second: 0.001,
+        }];
+        let mut limiter = RateLimiter::new(&limits);
+        let pid1 = PeerId::random();
+        let pid2 = PeerId::random();
+        let now = Instant::now();
+
+        assert!(limiter.allow_at(pid1, MessageType::Hello, now));
+        assert!(limiter.allow_at(pid1, MessageType::Hello, now));
+        assert!(!limiter.allow_at(pid1, MessageType::Hello, now));
+
+        // The bucket outlives the TTL until it is refilled
+        let later = now + BUCKET_TTL;
+        assert!(limiter.allow_at(pid2, MessageType::Hello, later));
+        assert!(limiter.buckets.contains_key(&(pid1, MessageType::Hello)));
+
+        // Refilling two tokens takes 2000 seconds
+        let later = now + Duration::from_secs(2000);
+        assert!(limiter.allow_at(pid2, MessageType::Hello, later));
+        assert!(!limiter.buckets.contains_key(&(pid1, MessageType::Hello)));
+        assert!(limiter.buckets.contains_key(&(pid2, MessageType::Hello)));
+    }
+}
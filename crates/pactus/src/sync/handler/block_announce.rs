@@ -0,0 +1,38 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::block_announce::BlockAnnounceMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::{debug, trace};
+
+pub struct BlockAnnounceHandler {}
+
+impl BlockAnnounceHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for BlockAnnounceHandler {
+    type Message = BlockAnnounceMessage;
+
+    fn pars_message(&self, msg: BlockAnnounceMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Block announce message: {:?}", msg);
+
+        {
+            let mut downloader = sync.downloader.lock().unwrap();
+            let our_height = sync.state().last_block_height();
+            let max_height = our_height.saturating_add(downloader.window());
+            if msg.height <= our_height || msg.height > max_height {
+                debug!("block announce out of the download window: {}", msg.height);
+                return Ok(());
+            }
+            downloader.add_announced_block(msg.height, msg.block, msg.certificate);
+        }
+
+        sync.peer_set.write().unwrap().update_height(&initiator, msg.height);
+        sync.commit_downloaded_blocks();
+
+        Ok(())
+    }
+}
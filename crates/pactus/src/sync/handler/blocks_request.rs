@@ -0,0 +1,93 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::blocks_request::BlocksRequestMessage;
+use crate::sync::bundle::message::blocks_response::BlocksResponseMessage;
+use crate::sync::bundle::message::{
+    RESPONSE_CODE_MOREBLOCKS, RESPONSE_CODE_NOMOREBLOCKS, RESPONSE_CODE_REJECTED,
+    RESPONSE_CODE_SYNCED,
+};
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::trace;
+
+pub struct BlocksRequestHandler {}
+
+impl BlocksRequestHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for BlocksRequestHandler {
+    type Message = BlocksRequestMessage;
+
+    fn pars_message(&self, msg: BlocksRequestMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Blocks request message: {:?}", msg);
+
+        let our_height = sync.state().last_block_height();
+        if msg.from > our_height {
+            let response = BlocksResponseMessage::new(
+                RESPONSE_CODE_REJECTED,
+                msg.session_id,
+                msg.from,
+                Vec::new(),
+                None,
+                format!("we don't have block at height {}", msg.from),
+            );
+            sync.send_to(&response, initiator);
+            return Ok(());
+        }
+        // Like the Go implementation, we serve at most the latest block interval at once.
+        // The peer requests the rest after the no-more-blocks response.
+        let count = msg.count.min(sync.config.latest_block_interval);
+        // The count is positive after the basic check
+        let to = msg.from.saturating_add(count - 1).min(our_height);
+        let mut height = msg.from;
+        while height <= to {
+            let mut blocks_data = Vec::new();
+            while height <= to && blocks_data.len() < sync.config.block_per_message as usize {
+                match sync.state().block_data(height) {
+                    Some(data) => blocks_data.push(data),
+                    None => break,
+                }
+                height += 1;
+            }
+            if blocks_data.is_empty() {
+                break;
+            }
+            let from = height - blocks_data.len() as i32;
+            let response = BlocksResponseMessage::new(
+                RESPONSE_CODE_MOREBLOCKS,
+                msg.session_id,
+                from,
+                blocks_data,
+                None,
+                "".to_string(),
+            );
+            sync.send_to(&response, initiator);
+        }
+
+        let response = if to == our_height {
+            BlocksResponseMessage::new(
+                RESPONSE_CODE_SYNCED,
+                msg.session_id,
+                our_height,
+                Vec::new(),
+                sync.state().last_certificate(),
+                "".to_string(),
+            )
+        } else {
+            BlocksResponseMessage::new(
+                RESPONSE_CODE_NOMOREBLOCKS,
+                msg.session_id,
+                to,
+                Vec::new(),
+                None,
+                "".to_string(),
+            )
+        };
+        sync.send_to(&response, initiator);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use super::HandlerStrategy;
+use crate::error::{Error, Result};
+use crate::sync::bundle::message::blocks_response::BlocksResponseMessage;
+use crate::sync::bundle::message::{
+    RESPONSE_CODE_MOREBLOCKS, RESPONSE_CODE_SYNCED,
+};
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::{debug, trace, warn};
+use pactus_types::block::Block;
+
+pub struct BlocksResponseHandler {}
+
+impl BlocksResponseHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for BlocksResponseHandler {
+    type Message = BlocksResponseMessage;
+
+    fn pars_message(&self, msg: BlocksResponseMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Blocks response message: {:?}", msg);
+
+        {
+            let mut downloader = sync.downloader.lock().unwrap();
+            match downloader.session(msg.session_id) {
+                Some(session) if session.peer_id == initiator => {
+                    // The synced response only carries the last certificate of the peer
+                    let count = msg.blocks_data.len() as i32;
+                    if msg.response_code == RESPONSE_CODE_MOREBLOCKS
+                        && !session.covers(msg.from, count)
+                    {
+                        downloader.close_session(msg.session_id);
+                        return Err(Error::InvalidMessage(format!(
+                            "blocks {}..{} are not requested in session {}",
+                            msg.from,
+                            msg.from + count,
+                            msg.session_id
+                        )));
+                    }
+                }
+                _ => {
+                    debug!("session {} is not open with {}", msg.session_id, initiator);
+                    return Ok(());
+                }
+            }
+
+            match msg.response_code {
+                RESPONSE_CODE_MOREBLOCKS => {
+                    for (i, data) in msg.blocks_data.iter().enumerate() {
+                        match Block::from_bytes(data) {
+                            Ok(block) => downloader.add_block(msg.from + i as i32, block),
+                            Err(err) => {
+                                warn!("invalid block received from {}: {}", initiator, err);
+                                downloader.close_session(msg.session_id);
+                                break;
+                            }
+                        }
+                    }
+                }
+                RESPONSE_CODE_SYNCED => {
+                    if let Some(cert) = &msg.last_certificate {
+                        downloader.add_certificate(msg.from, cert.clone());
+                    }
+                    downloader.close_session(msg.session_id);
+                }
+                _ => {
+                    if !msg.reason.is_empty() {
+                        debug!("session {} closed: {}", msg.session_id, msg.reason);
+                    }
+                    downloader.close_session(msg.session_id);
+                }
+            }
+        }
+
+        sync.commit_downloaded_blocks();
+        if !sync.downloader.lock().unwrap().has_open_session() {
+            sync.download_blocks();
+        }
+
+        Ok(())
+    }
+}
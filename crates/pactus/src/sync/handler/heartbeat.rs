@@ -1,3 +1,10 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::heartbeat::HeartbeatMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::{info, trace};
+
 pub struct HeartbeatHandler {}
 
 impl HeartbeatHandler {
@@ -5,3 +12,24 @@ impl HeartbeatHandler {
         Self {}
     }
 }
+
+impl HandlerStrategy for HeartbeatHandler {
+    type Message = HeartbeatMessage;
+
+    fn pars_message(&self, msg: HeartbeatMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Heartbeat message: {:?}", msg);
+
+        let (our_height, our_round) = sync.height_round();
+        if msg.height == our_height && msg.round > our_round {
+            info!(
+                "our consensus is behind of this peer, ours: {}, peer: {}",
+                our_round, msg.round
+            );
+        }
+
+        // The heartbeat carries the consensus height,
+        // so the last committed block of the peer is one less.
+        sync.peer_set.write().unwrap().update_height(&initiator, msg.height - 1);
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use super::HandlerStrategy;
-use crate::error::Result;
-use crate::sync::bundle::bundle::Bundle;
+use crate::error::{Error, Result};
 use crate::sync::bundle::message::hello::HelloMessage;
-use crate::sync::bundle::message::Message;
+use crate::sync::peerset::peer::StatusCode;
 use crate::sync::service::PactusSync;
+use libp2p::PeerId;
 use log::info;
 
 pub struct HelloHandler {}
@@ -15,17 +15,43 @@ impl HelloHandler {
 }
 
 impl HandlerStrategy for HelloHandler {
-    fn pars_message(&self, msg: Box<dyn Message>, sync: &PactusSync) -> Result<()> {
-        let msg = msg.as_any().downcast_ref::<HelloMessage>().unwrap();
+    type Message = HelloMessage;
+
+    fn pars_message(&self, msg: HelloMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
         info!("Hello message: {}", msg.moniker);
 
-        sync.say_hello(false);
-        Ok(())
-    }
+        if msg.genesis_hash != sync.state().genesis_hash() {
+            // The peer belongs to another network, like a peer with an invalid bundle
+            sync.firewall.ban(initiator);
+            return Err(Error::InvalidMessage(format!(
+                "invalid genesis hash: {}",
+                hex::encode(msg.genesis_hash.as_bytes())
+            )));
+        }
 
-    fn prepare_bundle(&self, mut msg: Box<dyn Message>, sync: &PactusSync) -> Result<Bundle> {
-        let hello_msg = msg.as_any_mut().downcast_mut::<HelloMessage>().unwrap();
-        sync.signer.sign(hello_msg);
-        Bundle::new(sync.self_id, msg)
+        // The signature is checked before, here we make sure that it is signed for the initiator
+        if msg.peer_id()? != initiator {
+            return Err(Error::InvalidMessage(format!(
+                "peer ID doesn't match the initiator: {}",
+                initiator
+            )));
+        }
+
+        {
+            let mut peer_set = sync.peer_set.write().unwrap();
+            peer_set.update_info(
+                &initiator,
+                msg.moniker.clone(),
+                msg.agent.clone(),
+                msg.public_keys().unwrap_or_default(),
+                msg.genesis_hash.clone(),
+            );
+            peer_set.update_height(&initiator, msg.height);
+            peer_set.update_status(&initiator, StatusCode::Known);
+        }
+        if msg.need_response() {
+            sync.send_to(&sync.hello_message(false), initiator);
+        }
+        Ok(())
     }
 }
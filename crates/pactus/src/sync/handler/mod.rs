@@ -1,43 +1,127 @@
+pub mod block_announce;
+pub mod blocks_request;
+pub mod blocks_response;
 pub mod heartbeat;
 pub mod hello;
-
+pub mod proposal;
+pub mod query_proposal;
+pub mod query_votes;
+pub mod transactions;
+pub mod vote;
 
 use super::{
-    bundle::{bundle::Bundle, message::Message},
+    bundle::{
+        bundle::Bundle,
+        message::{Message, Type as MessageType},
+    },
     service::PactusSync,
 };
-use crate::error::Result;
+use crate::error::{Error, Result};
+use libp2p::PeerId;
 use minicbor::Decode;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+pub(super) trait HandlerStrategy: Send + Sync {
+    type Message: Message + for<'b> Decode<'b, ()>;
 
+    fn pars_message(&self, msg: Self::Message, initiator: PeerId, sync: &PactusSync) -> Result<()>;
+}
 
-pub(super) trait HandlerStrategy: Send {
-    fn pars_message(&self, msg: Box<dyn Message>, sync: &PactusSync) -> Result<()>;
-    fn prepare_bundle(&self, msg: Box<dyn Message>, sync: &PactusSync) -> Result<Bundle>;
+/// Decodes the message of a bundle and passes it to its handler.
+trait Dispatcher: Send + Sync {
+    fn dispatch(&self, bdl: &Bundle, sync: &PactusSync) -> Result<()>;
 }
 
-pub(super) struct Handler {
-    strategy: Box<dyn HandlerStrategy>,
+struct Handler<H: HandlerStrategy> {
+    strategy: H,
+    _message: PhantomData<fn() -> H::Message>,
 }
 
-impl Handler {
-    pub fn new(strategy: Box<dyn HandlerStrategy>) -> Self {
-        Self { strategy }
+impl<H: HandlerStrategy> Dispatcher for Handler<H> {
+    fn dispatch(&self, bdl: &Bundle, sync: &PactusSync) -> Result<()> {
+        let msg: H::Message = decode_message(&bdl.message_data)?;
+        if msg.message_type() != bdl.message_type {
+            return Err(Error::InvalidMessage(format!(
+                "expected {} message, got {}",
+                bdl.message_type,
+                msg.message_type()
+            )));
+        }
+        msg.basic_check()?;
+
+        self.strategy.pars_message(msg, bdl.initiator, sync)
     }
+}
+
+/// Registry of the message handlers, keyed by message type.
+#[derive(Default)]
+pub(super) struct Handlers {
+    handlers: BTreeMap<MessageType, Box<dyn Dispatcher>>,
+}
 
-    pub fn do_pars_message(&self, msg: Box<dyn Message>, sync: &PactusSync) {
-        self.strategy.pars_message(msg, sync).unwrap();
+impl Handlers {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    pub fn do_prepare_bundle(&self, msg: Box<dyn Message>, sync: &PactusSync) -> Result<Bundle> {
-        let bdl = self.strategy.prepare_bundle(msg, sync)?;
-        bdl.basic_check()?;
-        Ok(bdl)
+    pub fn register<H>(&mut self, message_type: MessageType, strategy: H)
+    where
+        H: HandlerStrategy + 'static,
+    {
+        let handler = Handler {
+            strategy,
+            _message: PhantomData,
+        };
+        self.handlers.insert(message_type, Box::new(handler));
     }
+
+    /// Handles the bundle. It returns an error if the message is invalid or not supported.
+    pub fn dispatch(&self, bdl: &Bundle, sync: &PactusSync) -> Result<()> {
+        match self.handlers.get(&bdl.message_type) {
+            Some(handler) => handler.dispatch(bdl, sync),
+            None => Err(Error::InvalidMessage(format!(
+                "no handler for {} messages",
+                bdl.message_type
+            ))),
+        }
+    }
+}
+
+/// Returns true if the error should count against the sender of the message.
+/// Only the decoding, signature and protocol errors do. The peers relay the votes and
+/// the proposals of others as the initiator, so double signing and stale data are not
+/// their fault.
+pub(super) fn is_sender_fault(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::DecodeError(_)
+            | Error::InvalidMessage(_)
+            | Error::InvalidVote(_)
+            | Error::InvalidProposal(_)
+            | Error::TypesError(_)
+    )
 }
 
-fn decode_message<T, C>(data: &[u8]) -> Result<T>
+fn decode_message<T>(data: &[u8]) -> Result<T>
 where
     T: for<'r> Decode<'r, ()>,
 {
     Ok(minicbor::decode::<T>(data)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_fault() {
+        assert!(is_sender_fault(&Error::DecodeError("".to_string())));
+        assert!(is_sender_fault(&Error::InvalidMessage("".to_string())));
+        assert!(is_sender_fault(&Error::InvalidVote("".to_string())));
+        assert!(is_sender_fault(&Error::TypesError("".to_string())));
+        assert!(!is_sender_fault(&Error::DoubleSign("".to_string())));
+        assert!(!is_sender_fault(&Error::InvalidBlock("".to_string())));
+        assert!(!is_sender_fault(&Error::InvalidTransaction("".to_string())));
+    }
+}
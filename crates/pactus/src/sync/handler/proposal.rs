@@ -0,0 +1,29 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::proposal::ProposalMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::trace;
+
+pub struct ProposalHandler {}
+
+impl ProposalHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for ProposalHandler {
+    type Message = ProposalMessage;
+
+    fn pars_message(
+        &self,
+        msg: ProposalMessage,
+        _initiator: PeerId,
+        sync: &PactusSync,
+    ) -> Result<()> {
+        trace!("Proposal message: {:?}", msg);
+
+        sync.consensus().set_proposal(msg.proposal)
+    }
+}
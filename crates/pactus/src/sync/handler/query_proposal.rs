@@ -0,0 +1,34 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::proposal::ProposalMessage;
+use crate::sync::bundle::message::query_proposal::QueryProposalMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::trace;
+
+pub struct QueryProposalHandler {}
+
+impl QueryProposalHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for QueryProposalHandler {
+    type Message = QueryProposalMessage;
+
+    fn pars_message(
+        &self,
+        msg: QueryProposalMessage,
+        _initiator: PeerId,
+        sync: &PactusSync,
+    ) -> Result<()> {
+        trace!("Query proposal message: {:?}", msg);
+
+        // Like the Go node, the proposal is broadcast so that other peers that missed it can use it too.
+        if let Some(proposal) = sync.consensus().proposal(msg.height, msg.round) {
+            sync.broadcast_consensus(&ProposalMessage::new(proposal));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::query_votes::QueryVotesMessage;
+use crate::sync::bundle::message::vote::VoteMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::trace;
+
+pub struct QueryVotesHandler {}
+
+impl QueryVotesHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for QueryVotesHandler {
+    type Message = QueryVotesMessage;
+
+    fn pars_message(&self, msg: QueryVotesMessage, initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Query votes message: {:?}", msg);
+
+        // The votes are sent directly to the peer that is catching up.
+        for vote in sync.consensus().votes(msg.height, msg.round) {
+            sync.send_to(&VoteMessage::new(vote), initiator);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::transactions::TransactionsMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::{debug, trace};
+
+pub struct TransactionsHandler {}
+
+impl TransactionsHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for TransactionsHandler {
+    type Message = TransactionsMessage;
+
+    fn pars_message(
+        &self,
+        msg: TransactionsMessage,
+        _initiator: PeerId,
+        sync: &PactusSync,
+    ) -> Result<()> {
+        trace!("Transactions message: {:?}", msg);
+
+        // The transactions may be committed or expired by the time they reach us,
+        // so the rejected ones don't count against the sender.
+        for tx in msg.transactions.iter() {
+            if let Err(err) = sync.tx_pool().append_tx(tx.clone()) {
+                debug!("unable to append the transaction: {}", err);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use super::HandlerStrategy;
+use crate::error::Result;
+use crate::sync::bundle::message::vote::VoteMessage;
+use crate::sync::service::PactusSync;
+use libp2p::PeerId;
+use log::trace;
+
+pub struct VoteHandler {}
+
+impl VoteHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HandlerStrategy for VoteHandler {
+    type Message = VoteMessage;
+
+    fn pars_message(&self, msg: VoteMessage, _initiator: PeerId, sync: &PactusSync) -> Result<()> {
+        trace!("Vote message: {:?}", msg);
+
+        sync.consensus().add_vote(msg.vote)
+    }
+}
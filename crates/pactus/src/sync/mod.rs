@@ -2,19 +2,50 @@ pub mod config;
 pub mod firewall;
 pub mod handler;
 pub mod bundle;
+pub mod peerset;
 pub mod service;
 
-use pactus_types::crypto::signer::Signer;
+mod downloader;
+
+use crate::signer::Signer;
 use self::service::PactusSync;
 use crate::error::Result;
+use crate::consensus::{Broadcast, Consensus};
+use crate::event::EventBus;
+use crate::metrics::Metrics;
 use crate::network::NetworkService;
+use async_std::channel::Receiver;
+use crate::state::State;
+use crate::txpool::TxPool;
+use peerset::peerset::PeerSet;
+use std::sync::{Arc, RwLock};
 
-pub trait SyncService: crate::Service {}
+pub trait SyncService: crate::Service {
+    /// Returns the set of peers that we have seen.
+    fn peer_set(&self) -> Arc<RwLock<PeerSet>>;
+}
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_sync_service(
     config: config::Config,
-    signer: Signer,
+    signers: Vec<Arc<dyn Signer>>,
+    state: Arc<dyn State>,
+    consensus: Arc<dyn Consensus>,
+    tx_pool: Arc<dyn TxPool>,
+    consensus_receiver: Receiver<Broadcast>,
     network: &mut dyn NetworkService,
+    metrics: Arc<Metrics>,
+    event_bus: Arc<dyn EventBus>,
 ) -> Result<impl SyncService> {
-    PactusSync::new(config, signer, network)
+    PactusSync::new(
+        config,
+        signers,
+        state,
+        consensus,
+        tx_pool,
+        consensus_receiver,
+        network,
+        metrics,
+        event_bus,
+    )
 }
@@ -0,0 +1,61 @@
+use crate::network::Direction;
+use libp2p::PeerId;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::hash::Hash32;
+use std::time::{Instant, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCode {
+    Unknown,
+    Connected,
+    Disconnected,
+    Known,
+    Banned,
+}
+
+#[derive(Debug, Clone)]
+pub struct Peer {
+    pub peer_id: PeerId,
+    pub status: StatusCode,
+    pub moniker: String,
+    pub agent: String,
+    pub consensus_keys: Vec<PublicKey>,
+    pub height: i32,
+    pub genesis_hash: Option<Hash32>,
+    pub direction: Option<Direction>,
+    pub last_seen: Option<SystemTime>,
+    pub received_bytes: usize,
+    pub sent_bytes: usize,
+    pub received_messages: usize,
+    pub invalid_messages: usize,
+    pub banned_until: Option<Instant>,
+}
+
+impl Peer {
+    pub fn new(peer_id: PeerId) -> Self {
+        Self {
+            peer_id,
+            status: StatusCode::Unknown,
+            moniker: "".to_string(),
+            agent: "".to_string(),
+            consensus_keys: Vec::new(),
+            height: 0,
+            genesis_hash: None,
+            direction: None,
+            last_seen: None,
+            received_bytes: 0,
+            sent_bytes: 0,
+            received_messages: 0,
+            invalid_messages: 0,
+            banned_until: None,
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.status == StatusCode::Known
+    }
+
+    pub fn is_banned(&self) -> bool {
+        self.status == StatusCode::Banned
+    }
+}
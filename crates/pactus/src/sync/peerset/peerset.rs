@@ -0,0 +1,188 @@
+use super::peer::{Peer, StatusCode};
+use crate::network::Direction;
+use libp2p::PeerId;
+use pactus_types::crypto::public_key::PublicKey;
+use pactus_types::hash::Hash32;
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime};
+
+/// PeerSet keeps the information of the peers that we have seen.
+#[derive(Debug, Default)]
+pub struct PeerSet {
+    peers: HashMap<PeerId, Peer>,
+}
+
+impl PeerSet {
+    pub fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+        }
+    }
+
+    fn must_get_peer(&mut self, peer_id: &PeerId) -> &mut Peer {
+        self.peers
+            .entry(*peer_id)
+            .or_insert_with(|| Peer::new(*peer_id))
+    }
+
+    pub fn get_peer(&self, peer_id: &PeerId) -> Option<&Peer> {
+        self.peers.get(peer_id)
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = &Peer> {
+        self.peers.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Returns the number of peers with the given status.
+    pub fn count_by_status(&self, status: StatusCode) -> usize {
+        self.peers.values().filter(|p| p.status == status).count()
+    }
+
+    /// Returns the maximum height claimed by the known peers.
+    pub fn max_claimed_height(&self) -> i32 {
+        self.peers
+            .values()
+            .filter(|p| p.is_known())
+            .map(|p| p.height)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the heights of the known peers.
+    pub fn known_peer_heights(&self) -> HashMap<PeerId, i32> {
+        self.peers
+            .values()
+            .filter(|p| p.is_known())
+            .map(|p| (p.peer_id, p.height))
+            .collect()
+    }
+
+    pub fn update_status(&mut self, peer_id: &PeerId, status: StatusCode) {
+        let peer = self.must_get_peer(peer_id);
+        // A banned peer stays banned
+        if !peer.is_banned() {
+            peer.status = status;
+        }
+    }
+
+    /// Bans the peer until the given time.
+    pub fn ban_peer(&mut self, peer_id: &PeerId, until: Instant) {
+        let peer = self.must_get_peer(peer_id);
+        peer.status = StatusCode::Banned;
+        peer.banned_until = Some(until);
+    }
+
+    /// Lifts the ban of the peer if it has expired. Its invalid messages are forgotten,
+    /// so it starts with a clean ban score.
+    pub fn lift_expired_ban(&mut self, peer_id: &PeerId, now: Instant) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if peer.is_banned() && peer.banned_until.is_some_and(|until| until <= now) {
+                peer.status = StatusCode::Unknown;
+                peer.banned_until = None;
+                peer.invalid_messages = 0;
+            }
+        }
+    }
+
+    pub fn update_info(
+        &mut self,
+        peer_id: &PeerId,
+        moniker: String,
+        agent: String,
+        consensus_keys: Vec<PublicKey>,
+        genesis_hash: Hash32,
+    ) {
+        let peer = self.must_get_peer(peer_id);
+        peer.moniker = moniker;
+        peer.agent = agent;
+        peer.consensus_keys = consensus_keys;
+        peer.genesis_hash = Some(genesis_hash);
+    }
+
+    /// Updates the last block height of the peer. The height never goes back.
+    pub fn update_height(&mut self, peer_id: &PeerId, height: i32) {
+        let peer = self.must_get_peer(peer_id);
+        peer.height = peer.height.max(height);
+    }
+
+    pub fn update_direction(&mut self, peer_id: &PeerId, direction: Direction) {
+        self.must_get_peer(peer_id).direction = Some(direction);
+    }
+
+    pub fn increase_received_bytes(&mut self, peer_id: &PeerId, len: usize) {
+        let peer = self.must_get_peer(peer_id);
+        peer.received_bytes += len;
+        peer.received_messages += 1;
+        peer.last_seen = Some(SystemTime::now());
+    }
+
+    pub fn increase_sent_bytes(&mut self, peer_id: &PeerId, len: usize) {
+        self.must_get_peer(peer_id).sent_bytes += len;
+    }
+
+    pub fn increase_invalid_messages(&mut self, peer_id: &PeerId) {
+        self.must_get_peer(peer_id).invalid_messages += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_height() {
+        let mut ps = PeerSet::new();
+        let pid = PeerId::random();
+
+        ps.update_height(&pid, 10);
+        ps.update_height(&pid, 5);
+        assert_eq!(ps.get_peer(&pid).unwrap().height, 10);
+
+        // Only known peers are counted
+        assert_eq!(ps.max_claimed_height(), 0);
+        ps.update_status(&pid, StatusCode::Known);
+        assert_eq!(ps.max_claimed_height(), 10);
+        assert_eq!(ps.known_peer_heights().get(&pid), Some(&10));
+    }
+
+    #[test]
+    fn test_banned_peer() {
+        let mut ps = PeerSet::new();
+        let pid = PeerId::random();
+
+        ps.update_status(&pid, StatusCode::Banned);
+        ps.update_status(&pid, StatusCode::Known);
+        assert!(ps.get_peer(&pid).unwrap().is_banned());
+        assert_eq!(ps.count_by_status(StatusCode::Banned), 1);
+    }
+
+    #[test]
+    fn test_counters() {
+        let mut ps = PeerSet::new();
+        let pid = PeerId::random();
+
+        ps.increase_received_bytes(&pid, 100);
+        ps.increase_received_bytes(&pid, 20);
+        ps.increase_sent_bytes(&pid, 30);
+        ps.increase_invalid_messages(&pid);
+
+        let peer = ps.get_peer(&pid).unwrap();
+        assert_eq!(peer.received_bytes, 120);
+        assert_eq!(peer.received_messages, 2);
+        assert_eq!(peer.sent_bytes, 30);
+        assert_eq!(peer.invalid_messages, 1);
+        assert!(peer.last_seen.is_some());
+    }
+}
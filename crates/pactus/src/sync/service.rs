@@ -1,90 +1,310 @@
+use super::downloader::Downloader;
+use super::handler::block_announce::BlockAnnounceHandler;
+use super::handler::blocks_request::BlocksRequestHandler;
+use super::handler::blocks_response::BlocksResponseHandler;
+use super::handler::heartbeat::HeartbeatHandler;
 use super::handler::hello::HelloHandler;
-use super::handler::Handler;
-use super::bundle::bundle::Bundle;
-use super::bundle::message::hello::HelloMessage;
+use super::handler::proposal::ProposalHandler;
+use super::handler::query_proposal::QueryProposalHandler;
+use super::handler::query_votes::QueryVotesHandler;
+use super::handler::transactions::TransactionsHandler;
+use super::handler::vote::VoteHandler;
+use super::handler::{is_sender_fault, Handlers};
+use super::bundle::bundle::{network_flag, Bundle};
+use super::bundle::message::block_announce::BlockAnnounceMessage;
+use super::bundle::message::blocks_request::BlocksRequestMessage;
+use super::bundle::message::heartbeat::HeartbeatMessage;
+use super::bundle::message::hello::{HelloMessage, FLAG_NEED_RESPONSE};
+use super::bundle::message::proposal::ProposalMessage;
+use super::bundle::message::query_proposal::QueryProposalMessage;
+use super::bundle::message::query_votes::QueryVotesMessage;
+use super::bundle::message::transactions::TransactionsMessage;
+use super::bundle::message::vote::VoteMessage;
 use super::bundle::message::{Message, Type as MessageType};
+use super::peerset::peer::StatusCode;
+use super::peerset::peerset::PeerSet;
 use super::SyncService;
 use super::{config::Config, firewall::firewall::Firewall};
+use crate::consensus::{Broadcast, Consensus};
 use crate::error::{Result};
-use crate::network::NetworkEvent;
+use crate::event::{Event, EventBus};
+use crate::metrics::Metrics;
+use crate::network::{Direction, NetworkEvent};
 use crate::network::{NetworkMessage, NetworkService};
+use crate::state::State;
+use crate::txpool::TxPool;
 use async_std::channel::{Receiver, Sender};
 use async_std::stream;
 use async_trait::async_trait;
 use futures::select;
 use futures_util::stream::StreamExt;
-use log::{error, info, warn};
+use log::{debug, info, warn};
 
-use pactus_types::crypto::signer::Signer;
+use pactus_types::block::Block;
+use crate::signer::Signer;
 
-use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
 
-
-use pactus_types::hash::Hash32;
 use libp2p::{PeerId};
 
 pub(super) struct PactusSync {
     pub config: Config,
     pub self_id: PeerId,
-    pub signer: Signer,
-    firewall: Firewall,
-    handlers: BTreeMap<MessageType, Handler>,
+    pub signers: Vec<Arc<dyn Signer>>,
+    state: Arc<dyn State>,
+    consensus: Arc<dyn Consensus>,
+    tx_pool: Arc<dyn TxPool>,
+    pub(super) firewall: Firewall,
+    network_flag: i32,
+    pub(super) peer_set: Arc<RwLock<PeerSet>>,
+    pub(super) downloader: Mutex<Downloader>,
+    handlers: Handlers,
     network_message_sender: Sender<NetworkMessage>,
     network_event_receiver: Receiver<NetworkEvent>,
+    consensus_receiver: Receiver<Broadcast>,
+    metrics: Arc<Metrics>,
+    event_bus: Arc<dyn EventBus>,
 }
 
-impl SyncService for PactusSync {}
+impl SyncService for PactusSync {
+    fn peer_set(&self) -> Arc<RwLock<PeerSet>> {
+        self.peer_set.clone()
+    }
+}
 
 impl PactusSync {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: Config,
-        signer: Signer,
+        signers: Vec<Arc<dyn Signer>>,
+        state: Arc<dyn State>,
+        consensus: Arc<dyn Consensus>,
+        tx_pool: Arc<dyn TxPool>,
+        consensus_receiver: Receiver<Broadcast>,
         network: &mut dyn NetworkService,
+        metrics: Arc<Metrics>,
+        event_bus: Arc<dyn EventBus>,
     ) -> Result<Self> {
-        let mut handlers: BTreeMap<MessageType, Handler> = BTreeMap::new();
+        let mut handlers = Handlers::new();
 
-        let slm = HelloHandler::new();
+        handlers.register(MessageType::Hello, HelloHandler::new());
+        handlers.register(MessageType::Heartbeat, HeartbeatHandler::new());
+        handlers.register(MessageType::BlockAnnounce, BlockAnnounceHandler::new());
+        handlers.register(MessageType::BlocksRequest, BlocksRequestHandler::new());
+        handlers.register(MessageType::BlocksResponse, BlocksResponseHandler::new());
+        handlers.register(MessageType::Proposal, ProposalHandler::new());
+        handlers.register(MessageType::Vote, VoteHandler::new());
+        handlers.register(MessageType::QueryProposal, QueryProposalHandler::new());
+        handlers.register(MessageType::QueryVotes, QueryVotesHandler::new());
+        handlers.register(MessageType::Transactions, TransactionsHandler::new());
 
-        handlers.insert(MessageType::Hello, Handler::new(Box::new(slm)));
+        let downloader = Downloader::new(
+            config.block_per_message,
+            config.max_open_sessions,
+            config.session_timeout,
+        );
+
+        let peer_set = Arc::new(RwLock::new(PeerSet::new()));
+        let network_message_sender = network.message_sender();
+        let network_flag = network_flag(&network.network_name());
+        let firewall = Firewall::new(
+            &config.firewall,
+            network_flag,
+            &config.rate_limits,
+            peer_set.clone(),
+            network_message_sender.clone(),
+            metrics.clone(),
+        )?;
 
         Ok(Self {
             self_id: network.self_id(),
-            signer,
-            firewall: Firewall::new(&config.firewall)?,
+            signers,
+            state,
+            consensus,
+            tx_pool,
+            firewall,
+            network_flag,
+            peer_set,
+            downloader: Mutex::new(downloader),
             config,
             handlers,
-            network_message_sender: network.message_sender(),
+            network_message_sender,
             network_event_receiver: network.event_receiver(),
+            consensus_receiver,
+            metrics,
+            event_bus,
         })
     }
 
     pub fn say_hello(&self, need_response: bool) {
-        let h = hex::decode("2b1187d5f470de8d186634e3e227c528a292c0a3ba95fae924be27e8e7f46fa0").unwrap();
+        self.broadcast(&self.hello_message(need_response));
+    }
+
+    pub fn hello_message(&self, need_response: bool) -> HelloMessage {
         let mut flags = 0;
         if need_response {
-            flags |= 1;
+            flags |= FLAG_NEED_RESPONSE;
         }
-        let msg = HelloMessage::new(
+        let mut msg = HelloMessage::new(
             self.self_id,
             self.config.moniker.clone(),
-            0,
+            self.state.last_block_height(),
             flags,
-            Hash32::from_bytes(&h).unwrap(),
+            self.state.genesis_hash(),
         );
-        self.broadcast(Box::new(msg));
+        // Advertise all of our validator keys
+        for signer in &self.signers {
+            if let Err(err) = signer.sign_hello(&mut msg) {
+                warn!("unable to sign the hello message: {}", err);
+            }
+        }
+        msg
+    }
+
+    pub fn broadcast_heartbeat(&self) {
+        let (height, round) = self.height_round();
+        let msg = HeartbeatMessage::new(height, round, self.state.last_block_hash());
+        self.broadcast(&msg);
+    }
+
+    pub fn state(&self) -> &dyn State {
+        self.state.as_ref()
+    }
+
+    pub fn consensus(&self) -> &dyn Consensus {
+        self.consensus.as_ref()
+    }
+
+    pub fn tx_pool(&self) -> &dyn TxPool {
+        self.tx_pool.as_ref()
+    }
+
+    /// Asks the peers that are ahead of us for the blocks we don't have.
+    pub fn download_blocks(&self) {
+        let our_height = self.state.last_block_height();
+        let peer_heights = self.peer_set.read().unwrap().known_peer_heights();
+        let requests = self
+            .downloader
+            .lock()
+            .unwrap()
+            .schedule(our_height, &peer_heights);
+
+        for (session_id, peer_id, from, count) in requests {
+            debug!(
+                "requesting blocks {}-{} from {}",
+                from,
+                from + count - 1,
+                peer_id
+            );
+            let msg = BlocksRequestMessage::new(session_id, from, count);
+            self.send_to(&msg, peer_id);
+        }
     }
 
-    fn broadcast(&self, msg : Box<dyn Message>) {
+    /// Commits the downloaded blocks in order, as long as their certificates are known.
+    pub fn commit_downloaded_blocks(&self) {
+        let mut downloader = self.downloader.lock().unwrap();
+        let mut committed = false;
+        loop {
+            let height = self.state.last_block_height() + 1;
+            let (block, cert) = match downloader.next_committable(height - 1) {
+                Some(next) => next,
+                None => break,
+            };
+            if let Err(err) = self.state.commit_block(height, block.clone(), cert) {
+                warn!("unable to commit block {}: {}", height, err);
+                downloader.remove_block(height);
+                break;
+            }
+            self.tx_pool.handle_committed_block(&block);
+            debug!("block {} committed", height);
+            committed = true;
+        }
+        drop(downloader);
+        if committed {
+            self.consensus.move_to_new_height();
+        }
+    }
+
+    /// Returns the height and round that consensus is working on.
+    pub fn height_round(&self) -> (i32, i32) {
+        self.consensus.height_round()
+    }
+
+    /// Returns the maximum height claimed by the peers.
+    pub fn max_claimed_height(&self) -> i32 {
+        self.peer_set.read().unwrap().max_claimed_height()
+    }
+
+    /// Sends a message directly to a peer.
+    pub fn send_to(&self, msg: &dyn Message, target: PeerId) {
+        let bdl = self.prepare_bundle(msg).unwrap();
+        let data = bdl.to_bytes().unwrap();
+        self.peer_set
+            .write()
+            .unwrap()
+            .increase_sent_bytes(&target, data.len());
+        self.metrics.inc_bundles_sent(&msg.message_type());
+        let msg_data = NetworkMessage::StreamMessage { target, data };
+        self.network_message_sender.try_send(msg_data).unwrap();
+    }
+
+    fn broadcast(&self, msg: &dyn Message) {
         let bdl  = self.prepare_bundle(msg).unwrap();
+        self.metrics.inc_bundles_sent(&msg.message_type());
         let msg_data = NetworkMessage::GeneralMessage {
             data: bdl.to_bytes().unwrap(),
         };
         self.network_message_sender.try_send(msg_data).unwrap();
     }
 
-    fn prepare_bundle(&self, msg: Box<dyn Message>) -> Result<Bundle> {
-        let handler =  self.handlers.get(&msg.message_type()).unwrap();
-        handler.do_prepare_bundle(msg, self)
+    pub fn broadcast_consensus(&self, msg: &dyn Message) {
+        let bdl = self.prepare_bundle(msg).unwrap();
+        self.metrics.inc_bundles_sent(&msg.message_type());
+        let msg_data = NetworkMessage::ConsensusMessage {
+            data: bdl.to_bytes().unwrap(),
+        };
+        self.network_message_sender.try_send(msg_data).unwrap();
+    }
+
+    /// Broadcasts the messages that consensus and the transaction pool produced.
+    fn broadcast_consensus_message(&self, msg: Broadcast) {
+        match msg {
+            Broadcast::Proposal(proposal) => {
+                self.broadcast_consensus(&ProposalMessage::new(*proposal));
+            }
+            Broadcast::Vote(vote) => {
+                self.broadcast_consensus(&VoteMessage::new(vote));
+            }
+            Broadcast::QueryProposal { height, round } => {
+                self.broadcast_consensus(&QueryProposalMessage::new(height, round));
+            }
+            Broadcast::QueryVotes { height, round } => {
+                self.broadcast_consensus(&QueryVotesMessage::new(height, round));
+            }
+            Broadcast::BlockAnnounce { height } => {
+                let block = self
+                    .state
+                    .block_data(height)
+                    .and_then(|data| Block::from_bytes(&data).ok());
+                match (block, self.state.certificate(height)) {
+                    (Some(block), Some(cert)) => {
+                        self.broadcast(&BlockAnnounceMessage::new(height, block, cert));
+                    }
+                    _ => warn!("unable to announce block {}", height),
+                }
+            }
+            Broadcast::Transactions(txs) => {
+                self.broadcast(&TransactionsMessage::new(txs));
+            }
+        }
+    }
+
+    fn prepare_bundle(&self, msg: &dyn Message) -> Result<Bundle> {
+        let mut bdl = Bundle::new(self.self_id, msg)?;
+        bdl.flags |= self.network_flag;
+        Ok(bdl)
     }
 }
 
@@ -93,43 +313,78 @@ impl crate::Service for PactusSync {
     async fn start(self) {
         let mut heartbeat_ticker = stream::interval(self.config.heartbeat_timeout).fuse();
         let mut network_stream = self.network_event_receiver.clone().fuse();
+        let mut consensus_stream = self.consensus_receiver.clone().fuse();
 
         self.say_hello(true);
+        self.consensus.move_to_new_height();
 
         loop {
             select! {
                 network_event = network_stream.next() => match network_event {
                     Some(event) => match event {
-                        NetworkEvent::PeerConnected(peer_id) =>{
+                        NetworkEvent::PeerConnected{peer_id, direction} =>{
                             info!("peer connected {:?}", peer_id);
+                            {
+                                let mut peer_set = self.peer_set.write().unwrap();
+                                peer_set.update_status(&peer_id, StatusCode::Connected);
+                                peer_set.update_direction(&peer_id, direction);
+                            }
+                            // The peer becomes known after the hello handshake, we start it
+                            if direction == Direction::Outbound {
+                                self.send_to(&self.hello_message(true), peer_id);
+                            }
+                            self.event_bus.publish(Event::PeerJoined(peer_id));
                         }
                         NetworkEvent::PeerDisconnected(peer_id) =>{
                             info!("peer disconnected {:?}", peer_id);
+                            self.peer_set.write().unwrap().update_status(&peer_id, StatusCode::Disconnected);
+                            self.event_bus.publish(Event::PeerLeft(peer_id));
                         }
-                        NetworkEvent::MessageReceived{source: _, data} =>{
-                            match self.firewall.open_bundle(&data) {
+                        NetworkEvent::MessageReceived{source, from, data} =>{
+                            self.peer_set.write().unwrap().increase_received_bytes(&from, data.len());
+                            match self.firewall.open_bundle(&data, source) {
                                 Ok(bdl) => {
-                                    match self.handlers.get(&bdl.message_type()) {
-                                        Some(handler) => {
-                                            handler.do_pars_message(bdl.message, &self);
-                                        }
-                                        None => {
-                                            error!("invalid message type: {:?}", bdl.message_type())
+                                    self.metrics.inc_bundles_received(&bdl.message_type);
+                                    let started = Instant::now();
+                                    let result = self.handlers.dispatch(&bdl, &self);
+                                    self.metrics.observe_handler_latency(&bdl.message_type, started.elapsed());
+                                    if let Err(err) = result {
+                                        warn!("invalid {} message from {}: {}", bdl.message_type, source, err);
+                                        if is_sender_fault(&err) {
+                                            self.metrics.inc_invalid_bundles();
+                                            self.firewall.report_invalid_message(source);
                                         }
                                     }
                                 }
                                 Err(err) => {
-                                    warn!("invalid bundle: {}", err);
+                                    warn!("invalid bundle from {}: {}", source, err);
+                                    self.metrics.inc_invalid_bundles();
                                 }
                             };
                         }
                     }
                     None => { break; }
                 },
+                consensus_message = consensus_stream.next() => if let Some(msg) = consensus_message {
+                    self.broadcast_consensus_message(msg);
+                },
                 heartbeat_timeout = heartbeat_ticker.next() => if heartbeat_timeout.is_some() {
+                    self.broadcast_heartbeat();
+
+                    let our_height = self.state.last_block_height();
+                    self.metrics.set_sync_height(our_height);
+                    let claimed_height = self.max_claimed_height();
+                    if claimed_height > our_height {
+                        info!("we are behind the network, ours: {}, network: {}", our_height, claimed_height);
+                        self.download_blocks();
+                    }
                 }
             }
         }
     }
 }
 
+
+#[cfg(test)]
+#[path = "./service_test.rs"]
+mod service_test;
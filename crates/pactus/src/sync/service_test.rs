@@ -0,0 +1,296 @@
+mod tests {
+    use crate::consensus::{config::Config as ConsensusConfig, create_consensus};
+    use crate::event::create_event_bus;
+    use crate::evidence::create_evidence_pool;
+    use crate::metrics::create_metrics;
+    use crate::network::{Direction, NetworkEvent, NetworkMessage, NetworkService};
+    use crate::state::{create_state, State};
+    use crate::sync::{config::Config, create_sync_service};
+    use crate::txpool::{config::Config as TxPoolConfig, create_txpool, TxPool};
+    use crate::Service;
+    use async_std::channel::{unbounded, Receiver, Sender};
+    use async_std::task;
+    use async_trait::async_trait;
+    use libp2p::PeerId;
+    use pactus_types::account::Account;
+    use pactus_types::address::Address;
+    use pactus_types::block::{Block, BlockHeader, Transactions};
+    use pactus_types::certificate::Certificate;
+    use pactus_types::crypto::bls::secret_key::SecretKey;
+    use pactus_types::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::hash::Hash32;
+    use pactus_types::sortition::seed::Seed;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+    use pactus_types::transaction::Transaction;
+    use pactus_types::validator::Validator;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// A network that is wired to the other nodes of the test by the router.
+    struct MockNetwork {
+        self_id: PeerId,
+        message_sender: Sender<NetworkMessage>,
+        event_receiver: Receiver<NetworkEvent>,
+    }
+
+    #[async_trait]
+    impl Service for MockNetwork {
+        async fn start(self) {}
+    }
+
+    impl NetworkService for MockNetwork {
+        fn self_id(&self) -> PeerId {
+            self.self_id
+        }
+
+        fn message_sender(&self) -> Sender<NetworkMessage> {
+            self.message_sender.clone()
+        }
+
+        fn event_receiver(&self) -> Receiver<NetworkEvent> {
+            self.event_receiver.clone()
+        }
+
+        fn network_name(&self) -> String {
+            "pactus-test".to_string()
+        }
+    }
+
+    /// Delivers the messages between the connected nodes.
+    #[derive(Clone, Default)]
+    struct Router {
+        nodes: Arc<Mutex<HashMap<PeerId, Sender<NetworkEvent>>>>,
+        links: Arc<Mutex<HashSet<(PeerId, PeerId)>>>,
+    }
+
+    impl Router {
+        fn add_node(&self) -> MockNetwork {
+            let self_id = PeerId::random();
+            let (message_sender, message_receiver) = unbounded();
+            let (event_sender, event_receiver) = unbounded();
+            self.nodes.lock().unwrap().insert(self_id, event_sender);
+
+            let router = self.clone();
+            task::spawn(async move {
+                while let Ok(msg) = message_receiver.recv().await {
+                    router.deliver(self_id, msg);
+                }
+            });
+            MockNetwork {
+                self_id,
+                message_sender,
+                event_receiver,
+            }
+        }
+
+        fn deliver(&self, source: PeerId, msg: NetworkMessage) {
+            let links = self.links.lock().unwrap();
+            let (targets, data) = match msg {
+                NetworkMessage::GeneralMessage { data }
+                | NetworkMessage::ConsensusMessage { data } => {
+                    let targets = links
+                        .iter()
+                        .filter(|(from, _)| *from == source)
+                        .map(|(_, to)| *to)
+                        .collect();
+                    (targets, data)
+                }
+                NetworkMessage::StreamMessage { target, data } => {
+                    if !links.contains(&(source, target)) {
+                        return;
+                    }
+                    (vec![target], data)
+                }
+                NetworkMessage::CloseConnection { .. } => return,
+            };
+
+            let nodes = self.nodes.lock().unwrap();
+            for target in targets {
+                let event = NetworkEvent::MessageReceived {
+                    source,
+                    from: source,
+                    data: data.clone(),
+                };
+                nodes[&target].try_send(event).unwrap();
+            }
+        }
+
+        /// Connects the nodes, like an outbound connection of `from`.
+        fn connect(&self, from: PeerId, to: PeerId) {
+            let mut links = self.links.lock().unwrap();
+            links.insert((from, to));
+            links.insert((to, from));
+
+            let nodes = self.nodes.lock().unwrap();
+            let outbound = NetworkEvent::PeerConnected {
+                peer_id: to,
+                direction: Direction::Outbound,
+            };
+            nodes[&from].try_send(outbound).unwrap();
+            let inbound = NetworkEvent::PeerConnected {
+                peer_id: from,
+                direction: Direction::Inbound,
+            };
+            nodes[&to].try_send(inbound).unwrap();
+        }
+    }
+
+    /// Makes a chain of blocks, each one certified by the only validator of the committee.
+    fn make_chain(
+        key: &SecretKey,
+        validator: &Validator,
+        count: usize,
+    ) -> Vec<(Block, Certificate)> {
+        let mut chain: Vec<(Block, Certificate)> = Vec::new();
+        for _ in 0..count {
+            let (prev_block_hash, prev_cert) = match chain.last() {
+                Some((block, cert)) => (block.hash().unwrap(), Some(cert.clone())),
+                None => (Hash32::UNDEF, None),
+            };
+            let prev_cert_hash = prev_cert
+                .as_ref()
+                .map_or(Hash32::UNDEF, |cert| cert.hash().unwrap());
+            let header = BlockHeader::new(
+                1,
+                chain.len() as i64,
+                prev_block_hash,
+                prev_cert_hash,
+                Hash32::UNDEF,
+                Hash32::UNDEF,
+                Seed::UNDEF,
+                validator.address().unwrap(),
+            );
+            let block = Block::new(header, prev_cert, Transactions::from(Vec::new()));
+            let block_hash = block.hash().unwrap();
+            let sig = key.sign(&Certificate::sign_bytes(&block_hash, 0));
+            let cert = Certificate::new(block_hash, 0, vec![0], Vec::new(), &sig);
+            chain.push((block, cert));
+        }
+        chain
+    }
+
+    struct Node {
+        peer_id: PeerId,
+        state: Arc<dyn State>,
+        tx_pool: Arc<dyn TxPool>,
+    }
+
+    /// Starts a node without validator keys that has committed the blocks.
+    fn start_node(
+        router: &Router,
+        committee: Vec<Validator>,
+        accounts: Vec<Account>,
+        blocks: &[(Block, Certificate)],
+    ) -> Node {
+        let event_bus = create_event_bus();
+        let state = create_state(Hash32::UNDEF, committee, accounts, event_bus.clone()).unwrap();
+        for (i, (block, cert)) in blocks.iter().enumerate() {
+            state
+                .commit_block(i as i32 + 1, block.clone(), cert.clone())
+                .unwrap();
+        }
+
+        let (broadcaster, consensus_receiver) = unbounded();
+        let tx_pool = create_txpool(
+            TxPoolConfig::default(),
+            state.clone(),
+            broadcaster.clone(),
+            event_bus.clone(),
+        );
+        let consensus = create_consensus(
+            ConsensusConfig::default(),
+            Vec::new(),
+            state.clone(),
+            tx_pool.clone(),
+            create_evidence_pool(None).unwrap(),
+            broadcaster,
+            event_bus.clone(),
+        )
+        .unwrap();
+
+        let mut network = router.add_node();
+        let config = Config {
+            heartbeat_timeout: Duration::from_millis(100),
+            rate_limits: Vec::new(),
+            ..Config::default()
+        };
+        let sync = create_sync_service(
+            config,
+            Vec::new(),
+            state.clone(),
+            consensus,
+            tx_pool.clone(),
+            consensus_receiver,
+            &mut network,
+            create_metrics(),
+            event_bus,
+        )
+        .unwrap();
+        task::spawn(sync.start());
+        Node {
+            peer_id: network.self_id(),
+            state,
+            tx_pool,
+        }
+    }
+
+    async fn wait_for_height(state: &dyn State, height: i32) {
+        for _ in 0..50 {
+            if state.last_block_height() >= height {
+                return;
+            }
+            task::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    #[async_std::test]
+    async fn test_sync_after_connect() {
+        let key = SecretKey::random();
+        let committee = vec![Validator::new(&key.public_key(), 0, 1)];
+        let chain = make_chain(&key, &committee[0], 3);
+        let router = Router::default();
+        let node_a = start_node(&router, committee.clone(), Vec::new(), &chain);
+        let node_b = start_node(&router, committee, Vec::new(), &[]);
+
+        // The nodes are started before they are connected, so their first hellos reach nobody
+        task::sleep(Duration::from_millis(200)).await;
+        router.connect(node_b.peer_id, node_a.peer_id);
+
+        wait_for_height(node_b.state.as_ref(), 3).await;
+        assert_eq!(node_b.state.last_block_height(), 3);
+    }
+
+    #[async_std::test]
+    async fn test_gossip_transactions() {
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let accounts = vec![Account {
+            address: signer.address(),
+            number: 0,
+            sequence: 0,
+            balance: 5000,
+        }];
+        let router = Router::default();
+        let node_a = start_node(&router, Vec::new(), accounts.clone(), &[]);
+        let node_b = start_node(&router, Vec::new(), accounts, &[]);
+        router.connect(node_a.peer_id, node_b.peer_id);
+
+        let receiver = Address::from_bytes(&[2; 21]).unwrap();
+        let payload = SendPayload::new(signer.address(), receiver, 1000);
+        let stamp = Stamp::from_block_hash(&Hash32::UNDEF);
+        let mut tx = Transaction::new(stamp, 1, 1000, String::new(), Box::new(payload), None, None);
+        signer.sign(&mut tx);
+        let id = tx.id().unwrap();
+        node_a.tx_pool.append_tx_and_broadcast(tx).unwrap();
+
+        for _ in 0..50 {
+            if node_b.tx_pool.has_tx(&id) {
+                break;
+            }
+            task::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(node_b.tx_pool.has_tx(&id));
+    }
+}
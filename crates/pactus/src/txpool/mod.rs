@@ -0,0 +1,45 @@
+pub mod config;
+#[allow(clippy::module_inception)]
+pub mod txpool;
+
+use self::txpool::PactusTxPool;
+use crate::consensus::Broadcast;
+use crate::error::Result;
+use crate::event::EventBus;
+use crate::state::State;
+use async_std::channel::Sender;
+use pactus_types::block::Block;
+use pactus_types::hash::Hash32;
+use pactus_types::transaction::Transaction;
+use std::sync::Arc;
+
+pub trait TxPool: Send + Sync {
+    /// Appends a transaction to the pool, if it is not there already.
+    /// Transactions that are not signed by their signer, with an unknown or expired stamp,
+    /// or that can't be executed after the pending transactions of their signer are rejected.
+    fn append_tx(&self, tx: Transaction) -> Result<()>;
+    /// Appends a transaction to the pool, like `append_tx`, and broadcasts it to the network.
+    fn append_tx_and_broadcast(&self, tx: Transaction) -> Result<()>;
+    /// Returns the transactions that can be put into the next block, in the order they arrived.
+    /// Transactions that have expired or can't be executed anymore are skipped.
+    fn prepare_block_transactions(&self, max: usize) -> Vec<Transaction>;
+    fn remove_tx(&self, id: &Hash32);
+    /// Removes the transactions of the committed block, and the ones whose stamp has expired.
+    fn handle_committed_block(&self, block: &Block);
+    fn has_tx(&self, id: &Hash32) -> bool;
+    fn size(&self) -> usize;
+}
+
+pub fn create_txpool(
+    config: config::Config,
+    state: Arc<dyn State>,
+    broadcaster: Sender<Broadcast>,
+    event_bus: Arc<dyn EventBus>,
+) -> Arc<dyn TxPool> {
+    Arc::new(PactusTxPool::new(
+        config.max_size,
+        state,
+        broadcaster,
+        event_bus,
+    ))
+}
@@ -0,0 +1,297 @@
+use super::TxPool;
+use crate::consensus::Broadcast;
+use crate::error::{Error, Result};
+use crate::event::{Event, EventBus};
+use crate::state::sandbox::Sandbox;
+use crate::state::State;
+use async_std::channel::Sender;
+use log::warn;
+use pactus_types::block::Block;
+use pactus_types::hash::Hash32;
+use pactus_types::transaction::Transaction;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+#[derive(Default)]
+struct Pool {
+    order: VecDeque<Hash32>,
+    txs: HashMap<Hash32, Transaction>,
+}
+
+pub(super) struct PactusTxPool {
+    max_size: usize,
+    pool: RwLock<Pool>,
+    state: Arc<dyn State>,
+    broadcaster: Sender<Broadcast>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl PactusTxPool {
+    pub fn new(
+        max_size: usize,
+        state: Arc<dyn State>,
+        broadcaster: Sender<Broadcast>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            max_size,
+            pool: RwLock::new(Pool::default()),
+            state,
+            broadcaster,
+            event_bus,
+        }
+    }
+}
+
+impl TxPool for PactusTxPool {
+    fn append_tx(&self, tx: Transaction) -> Result<()> {
+        let id = tx.id()?;
+        self.state.check_transaction(&tx)?;
+        let mut pool = self.pool.write().unwrap();
+        if pool.txs.contains_key(&id) {
+            return Ok(());
+        }
+        if pool.txs.len() >= self.max_size {
+            return Err(Error::InvalidTransaction(
+                "transaction pool is full".to_string(),
+            ));
+        }
+        // The sequence of the signer follows its pending transactions
+        let mut sandbox = Sandbox::new(self.state.as_ref());
+        for pending in pool.order.iter().filter_map(|id| pool.txs.get(id)) {
+            if pending.payload.signer() == tx.payload.signer() {
+                let _ = sandbox.execute(pending);
+            }
+        }
+        sandbox.execute(&tx)?;
+        pool.order.push_back(id.clone());
+        pool.txs.insert(id, tx.clone());
+        drop(pool);
+
+        self.event_bus.publish(Event::NewTransaction(tx));
+        Ok(())
+    }
+
+    fn append_tx_and_broadcast(&self, tx: Transaction) -> Result<()> {
+        self.append_tx(tx.clone())?;
+        if self.broadcaster.try_send(Broadcast::Transactions(vec![tx])).is_err() {
+            warn!("unable to broadcast the transaction");
+        }
+        Ok(())
+    }
+
+    fn prepare_block_transactions(&self, max: usize) -> Vec<Transaction> {
+        let pool = self.pool.read().unwrap();
+        let mut sandbox = Sandbox::new(self.state.as_ref());
+        pool.order
+            .iter()
+            .filter_map(|id| pool.txs.get(id))
+            .filter(|tx| self.state.check_stamp(tx).is_ok() && sandbox.execute(tx).is_ok())
+            .take(max)
+            .cloned()
+            .collect()
+    }
+
+    fn remove_tx(&self, id: &Hash32) {
+        let mut pool = self.pool.write().unwrap();
+        if pool.txs.remove(id).is_some() {
+            pool.order.retain(|tx_id| tx_id != id);
+        }
+    }
+
+    fn handle_committed_block(&self, block: &Block) {
+        let mut pool = self.pool.write().unwrap();
+        for id in block.transactions().iter().filter_map(|tx| tx.id().ok()) {
+            pool.txs.remove(&id);
+        }
+        // The expired transactions can't be committed anymore, they only fill the pool
+        pool.txs.retain(|_, tx| self.state.check_stamp(tx).is_ok());
+        let Pool { order, txs } = &mut *pool;
+        order.retain(|id| txs.contains_key(id));
+    }
+
+    fn has_tx(&self, id: &Hash32) -> bool {
+        self.pool.read().unwrap().txs.contains_key(id)
+    }
+
+    fn size(&self) -> usize {
+        self.pool.read().unwrap().txs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_bus;
+    use crate::state::create_state;
+    use async_std::channel::unbounded;
+    use pactus_types::account::Account;
+    use pactus_types::address::Address;
+    use pactus_types::block::{BlockHeader, Transactions};
+    use pactus_types::certificate::Certificate;
+    use pactus_types::crypto::bls::secret_key::SecretKey as BLSSecretKey;
+    use pactus_types::crypto::secret_key::SecretKey;
+    use pactus_types::crypto::signer::Signer;
+    use pactus_types::sortition::seed::Seed;
+    use pactus_types::stamp::Stamp;
+    use pactus_types::transaction::payload::send::SendPayload;
+    use pactus_types::validator::Validator;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn setup() -> (Signer, PactusTxPool) {
+        let signer = Signer::new(SecretKey::BLS(BLSSecretKey::random()));
+        let account = Account {
+            address: signer.address(),
+            number: 0,
+            sequence: 0,
+            balance: 5000,
+        };
+        let event_bus = create_event_bus();
+        let state =
+            create_state(Hash32::UNDEF, Vec::new(), vec![account], event_bus.clone()).unwrap();
+        let (broadcaster, _) = unbounded();
+        (signer, PactusTxPool::new(10, state, broadcaster, event_bus))
+    }
+
+    fn transaction(signer: &Signer, stamp: Stamp, sequence: i32) -> Transaction {
+        transfer(signer, stamp, sequence, 1000)
+    }
+
+    fn transfer(signer: &Signer, stamp: Stamp, sequence: i32, amount: i64) -> Transaction {
+        let receiver = Address::from_bytes(&[2; 21]).unwrap();
+        let payload = SendPayload::new(signer.address(), receiver, amount);
+        let mut tx =
+            Transaction::new(stamp, sequence, 1000, String::new(), Box::new(payload), None, None);
+        signer.sign(&mut tx);
+        tx
+    }
+
+    #[test]
+    fn test_stamp() {
+        let (signer, pool) = setup();
+
+        let tx = transaction(&signer, Stamp::from_block_hash(&Hash32::UNDEF), 1);
+        pool.append_tx(tx.clone()).unwrap();
+        assert!(pool.has_tx(&tx.id().unwrap()));
+        assert_eq!(pool.prepare_block_transactions(10).len(), 1);
+
+        let tx = transaction(&signer, Stamp::from_bytes(&[1, 2, 3, 4]).unwrap(), 2);
+        assert!(pool.append_tx(tx.clone()).is_err());
+        assert!(!pool.has_tx(&tx.id().unwrap()));
+    }
+
+    #[test]
+    fn test_sequence() {
+        let (signer, pool) = setup();
+        let stamp = Stamp::from_block_hash(&Hash32::UNDEF);
+
+        // The sequence follows the pending transactions of the signer
+        pool.append_tx(transaction(&signer, stamp.clone(), 1)).unwrap();
+        assert!(pool.append_tx(transfer(&signer, stamp.clone(), 1, 500)).is_err());
+        assert!(pool.append_tx(transaction(&signer, stamp.clone(), 3)).is_err());
+        pool.append_tx(transaction(&signer, stamp.clone(), 2)).unwrap();
+        // The balance is spent by the pending transactions
+        assert!(pool.append_tx(transaction(&signer, stamp, 3)).is_err());
+        assert_eq!(pool.prepare_block_transactions(10).len(), 2);
+    }
+
+    /// Wraps the state to expire the stamps of all the transactions at once.
+    struct ExpiringState {
+        state: Arc<dyn State>,
+        expired: AtomicBool,
+    }
+
+    impl State for ExpiringState {
+        fn genesis_hash(&self) -> Hash32 {
+            self.state.genesis_hash()
+        }
+        fn last_block_height(&self) -> i32 {
+            self.state.last_block_height()
+        }
+        fn last_block_hash(&self) -> Hash32 {
+            self.state.last_block_hash()
+        }
+        fn last_certificate(&self) -> Option<Certificate> {
+            self.state.last_certificate()
+        }
+        fn block_data(&self, height: i32) -> Option<Vec<u8>> {
+            self.state.block_data(height)
+        }
+        fn certificate(&self, height: i32) -> Option<Certificate> {
+            self.state.certificate(height)
+        }
+        fn block_height(&self, hash: &Hash32) -> Option<i32> {
+            self.state.block_height(hash)
+        }
+        fn transaction_height(&self, id: &Hash32) -> Option<i32> {
+            self.state.transaction_height(id)
+        }
+        fn check_stamp(&self, tx: &Transaction) -> Result<()> {
+            if self.expired.load(Ordering::Relaxed) {
+                return Err(Error::InvalidTransaction("expired stamp".to_string()));
+            }
+            self.state.check_stamp(tx)
+        }
+        fn check_transaction(&self, tx: &Transaction) -> Result<()> {
+            self.state.check_transaction(tx)?;
+            self.check_stamp(tx)
+        }
+        fn validate_block(&self, block: &Block) -> Result<()> {
+            self.state.validate_block(block)
+        }
+        fn commit_block(&self, height: i32, block: Block, cert: Certificate) -> Result<()> {
+            self.state.commit_block(height, block, cert)
+        }
+        fn committee_validators(&self) -> Vec<Validator> {
+            self.state.committee_validators()
+        }
+        fn account(&self, address: &Address) -> Option<Account> {
+            self.state.account(address)
+        }
+    }
+
+    #[test]
+    fn test_expired_transactions() {
+        let signer = Signer::new(SecretKey::BLS(BLSSecretKey::random()));
+        let account = Account {
+            address: signer.address(),
+            number: 0,
+            sequence: 0,
+            balance: 100_000,
+        };
+        let event_bus = create_event_bus();
+        let state = Arc::new(ExpiringState {
+            state: create_state(Hash32::UNDEF, Vec::new(), vec![account], event_bus.clone())
+                .unwrap(),
+            expired: AtomicBool::new(false),
+        });
+        let (broadcaster, _) = unbounded();
+        let pool = PactusTxPool::new(10, state.clone(), broadcaster, event_bus);
+        let stamp = Stamp::from_block_hash(&Hash32::UNDEF);
+
+        for sequence in 1..=10 {
+            pool.append_tx(transaction(&signer, stamp.clone(), sequence)).unwrap();
+        }
+        assert!(pool.append_tx(transaction(&signer, stamp.clone(), 11)).is_err());
+
+        // The pool is full of expired transactions, until a block is committed
+        state.expired.store(true, Ordering::Relaxed);
+        let header = BlockHeader::new(
+            1,
+            0,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Hash32::UNDEF,
+            Seed::UNDEF,
+            signer.address(),
+        );
+        pool.handle_committed_block(&Block::new(header, None, Transactions::from(Vec::new())));
+        assert_eq!(pool.size(), 0);
+
+        // New transactions are accepted after the expired ones are removed
+        state.expired.store(false, Ordering::Relaxed);
+        pool.append_tx(transaction(&signer, stamp, 1)).unwrap();
+        assert_eq!(pool.size(), 1);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::address::Address;
 use minicbor::{Encode, Decode};
 
-#[derive(Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 #[cbor(map)]
 pub struct Account {
     #[n(1)]
@@ -1,10 +1,13 @@
 use crate::error::{Error, Result};
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, ByteIterExt, Fe32, Fe32IterExt, Hrp};
+use std::fmt;
 
 const ADDRESS_SIZE: usize = 21;
+/// The human-readable part of the address string, the same as the Go implementation.
+const ADDRESS_HRP: Hrp = Hrp::parse_unchecked("pc");
 
-
-
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct Address([u8; ADDRESS_SIZE]);
 
 impl Address {
@@ -19,6 +22,58 @@ impl Address {
     pub fn as_bytes(&self) -> &[u8; ADDRESS_SIZE] {
         &self.0
     }
+
+    /// Decodes the bech32m address string. The first byte of the address, its type,
+    /// is the first character of the data, like the witness version of a segwit address.
+    pub fn from_string(s: &str) -> Result<Self> {
+        let mut checked = CheckedHrpstring::new::<Bech32m>(s)
+            .map_err(|err| Error::InvalidAddress(err.to_string()))?;
+        if checked.hrp() != ADDRESS_HRP {
+            return Err(Error::InvalidAddress(format!("invalid hrp: {}", checked.hrp())));
+        }
+        let addr_type = checked
+            .remove_witness_version()
+            .ok_or_else(|| Error::InvalidAddress("invalid address type".to_string()))?;
+        let mut data = vec![addr_type.to_u8()];
+        data.extend(checked.byte_iter());
+        Self::from_bytes(&data)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let addr_type = Fe32::try_from(self.0[0]).map_err(|_| fmt::Error)?;
+        let chars = self.0[1..]
+            .iter()
+            .copied()
+            .bytes_to_fes()
+            .with_checksum::<Bech32m>(&ADDRESS_HRP)
+            .with_witness_version(addr_type)
+            .chars();
+        for c in chars {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl serde::Serialize for Address {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Address {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Address, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Address::from_string(&s).map_err(serde::de::Error::custom)
+    }
 }
 
 crate::impl_cbor!(Address);
@@ -31,4 +86,19 @@ mod tests {
     fn test_decoding() {
         assert!(Address::from_bytes(&[]).is_err());
     }
+
+    #[test]
+    fn test_string() {
+        let data = hex::decode("01c5e3ed7a4e5c4c9a1b2d9ec1f7e5a3a1d69bc2a6").unwrap();
+        let addr = Address::from_bytes(&data).unwrap();
+        let s = addr.to_string();
+        assert!(s.starts_with("pc1p"));
+        // Go encodes the address type like a segwit witness version
+        assert_eq!(s, bech32::segwit::encode_v1(ADDRESS_HRP, &data[1..]).unwrap());
+        assert_eq!(Address::from_string(&s).unwrap(), addr);
+
+        assert!(Address::from_string("").is_err());
+        assert!(Address::from_string(&s.to_uppercase()[..s.len() - 1]).is_err());
+        assert!(Address::from_string(&s.replace("pc1", "tpc1")).is_err());
+    }
 }
@@ -3,14 +3,13 @@ use crate::hash::Hash32;
 use crate::sortition::seed::Seed;
 use crate::transaction::transaction::RawTransaction;
 use crate::{address::Address, transaction::Transaction};
-use minicbor::bytes::ByteVec;
 use minicbor::{
     decode::{Decoder, Error as DecodeError},
     encode::{Encoder, Error as EncodeError, Write},
     Decode, Encode,
 };
 
-#[derive(Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 #[cbor(map)]
 pub struct BlockHeader {
     #[n(1)]
@@ -31,6 +30,61 @@ pub struct BlockHeader {
     proposer_address: Address,
 }
 
+impl BlockHeader {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        version: i8,
+        unix_time: i64,
+        prev_block_hash: Hash32,
+        prev_cert_hash: Hash32,
+        state_root: Hash32,
+        tx_root: Hash32,
+        sortition_seed: Seed,
+        proposer_address: Address,
+    ) -> Self {
+        BlockHeader {
+            version,
+            unix_time,
+            prev_block_hash,
+            prev_cert_hash,
+            state_root,
+            tx_root,
+            sortition_seed,
+            proposer_address,
+        }
+    }
+
+    pub fn prev_block_hash(&self) -> &Hash32 {
+        &self.prev_block_hash
+    }
+
+    pub fn prev_cert_hash(&self) -> &Hash32 {
+        &self.prev_cert_hash
+    }
+
+    pub fn proposer_address(&self) -> &Address {
+        &self.proposer_address
+    }
+
+    pub fn unix_time(&self) -> i64 {
+        self.unix_time
+    }
+
+    pub fn tx_root(&self) -> &Hash32 {
+        &self.tx_root
+    }
+
+    pub fn sortition_seed(&self) -> &Seed {
+        &self.sortition_seed
+    }
+
+    /// Returns the hash of the header, which is the hash of the block.
+    pub fn hash(&self) -> crate::error::Result<Hash32> {
+        Ok(Hash32::calculate(&minicbor::to_vec(self)?))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Transactions(Vec<Transaction>);
 
 impl Transactions {
@@ -42,7 +96,21 @@ impl Transactions {
         self.0.push(tx)
     }
 
-    pub(crate) fn decode<'b, C>(
+    pub fn iter(&self) -> std::slice::Iter<'_, Transaction> {
+        self.0.iter()
+    }
+
+    /// Returns the root hash of the transactions.
+    // TODO: use a merkle tree, like the Go implementation
+    pub fn root(&self) -> crate::error::Result<Hash32> {
+        let mut data = Vec::with_capacity(self.0.len() * 32);
+        for tx in &self.0 {
+            data.extend_from_slice(tx.id()?.as_bytes());
+        }
+        Ok(Hash32::calculate(&data))
+    }
+
+    pub fn decode<'b, C>(
         d: &mut Decoder<'b>,
         _ctx: &mut C,
     ) -> std::result::Result<Transactions, DecodeError> {
@@ -51,13 +119,13 @@ impl Transactions {
         let mut txs = Transactions::with_capacity(raw_txs.len());
         for raw_tx in raw_txs {
             txs.push(Transaction::from_raw_transaction(raw_tx)
-                .map_err(|_| DecodeError::message(&"decoding error"))?);
+                .map_err(|_| DecodeError::message("decoding error"))?);
         }
 
         Ok(txs)
     }
 
-    pub(crate) fn encode<W: Write, C>(
+    pub fn encode<W: Write, C>(
         txs: &Transactions,
         e: &mut Encoder<W>,
         _ctx: &mut C,
@@ -66,31 +134,66 @@ impl Transactions {
         let mut raw_txs = Vec::<RawTransaction>::with_capacity(txs.len());
         for tx in &txs.0 {
             raw_txs.push(tx.to_raw_transaction()
-            .map_err(|_| EncodeError::message(&"encoding error"))?);
+            .map_err(|_| EncodeError::message("encoding error"))?);
         }
 
         e.encode(raw_txs)?;
         Ok(())
     }
 
-    pub fn len(&self) ->usize{
+    pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
-#[derive(Encode, Decode)]
+impl From<Vec<Transaction>> for Transactions {
+    fn from(txs: Vec<Transaction>) -> Self {
+        Transactions(txs)
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
 #[cbor(map)]
 pub struct Block {
     #[n(1)]
     header: BlockHeader,
+    /// The certificate of the previous block. The first block has no previous certificate.
     #[n(2)]
-    prev_cert: Certificate,
+    prev_cert: Option<Certificate>,
     #[n(3)]
     #[cbor(with = "Transactions")]
     txs: Transactions,
 }
 
 impl Block {
+    pub fn new(header: BlockHeader, prev_cert: Option<Certificate>, txs: Transactions) -> Self {
+        Block {
+            header,
+            prev_cert,
+            txs,
+        }
+    }
+
+    pub fn header(&self) -> &BlockHeader {
+        &self.header
+    }
+
+    pub fn prev_cert(&self) -> Option<&Certificate> {
+        self.prev_cert.as_ref()
+    }
+
+    pub fn transactions(&self) -> &Transactions {
+        &self.txs
+    }
+
+    pub fn hash(&self) -> crate::error::Result<Hash32> {
+        self.header.hash()
+    }
+
     crate::impl_from_to_bytes!(Block);
 }
 
@@ -1,8 +1,9 @@
 use crate::crypto::bls::signature::Signature;
+use crate::error::{Error, Result};
 use crate::hash::Hash32;
 use minicbor::{Decode, Encode, bytes::ByteVec};
 
-#[derive(Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 #[cbor(map)]
 pub struct Certificate {
     #[n(1)]
@@ -18,8 +19,74 @@ pub struct Certificate {
 }
 
 impl Certificate {
-    pub fn signature(&self) -> Signature {
-        todo!()
+    pub fn new(
+        block_hash: Hash32,
+        round: i32,
+        committers: Vec<i32>,
+        absentees: Vec<i32>,
+        signature: &Signature,
+    ) -> Self {
+        Certificate {
+            block_hash,
+            round,
+            committers,
+            absentees,
+            signature_data: ByteVec::from(signature.to_bytes()),
+        }
+    }
+
+    /// Returns the bytes that the committers sign for a block.
+    /// Precommit votes sign the same bytes, so their signatures can be aggregated.
+    pub fn sign_bytes(block_hash: &Hash32, round: i32) -> Vec<u8> {
+        let mut sb = block_hash.as_bytes().to_vec();
+        sb.extend_from_slice(&(round as i16).to_le_bytes());
+        sb
+    }
+
+    pub fn block_hash(&self) -> &Hash32 {
+        &self.block_hash
+    }
+
+    pub fn round(&self) -> i32 {
+        self.round
+    }
+
+    pub fn committers(&self) -> &[i32] {
+        &self.committers
+    }
+
+    pub fn absentees(&self) -> &[i32] {
+        &self.absentees
+    }
+
+    pub fn signature(&self) -> Result<Signature> {
+        Signature::from_bytes(&self.signature_data)
+    }
+
+    pub fn hash(&self) -> Result<Hash32> {
+        Ok(Hash32::calculate(&self.to_bytes()?))
+    }
+
+    pub fn basic_check(&self) -> Result<()> {
+        if self.round < 0 {
+            return Err(Error::InvalidCertificate(format!(
+                "invalid round: {}",
+                self.round
+            )));
+        }
+        if self.committers.is_empty() {
+            return Err(Error::InvalidCertificate("no committers".to_string()));
+        }
+        for num in &self.absentees {
+            if !self.committers.contains(num) {
+                return Err(Error::InvalidCertificate(format!(
+                    "absentee {} is not a committer",
+                    num
+                )));
+            }
+        }
+        self.signature()?;
+        Ok(())
     }
 
     crate::impl_from_to_bytes!(Certificate);
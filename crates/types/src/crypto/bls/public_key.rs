@@ -17,8 +17,14 @@ impl PublicKey {
             expected: PUBLIC_KEY_SIZE,
             found: data.len(),
         })?;
-        let key_opt = G2Affine::from_compressed(bytes);
-        Ok(Self(G2Projective::from(&key_opt.unwrap())))
+        let key_opt: Option<G2Affine> = G2Affine::from_compressed(bytes).into();
+        let key = key_opt.ok_or_else(|| Error::DecodeError("invalid public key".to_string()))?;
+        Ok(Self(G2Projective::from(&key)))
+    }
+
+    /// Aggregates the public keys into one public key.
+    pub fn aggregate(pks: &[PublicKey]) -> Self {
+        Self(pks.iter().map(|pk| pk.0).sum())
     }
 
     pub fn to_fixed_bytes(&self) -> [u8; PUBLIC_KEY_SIZE] {
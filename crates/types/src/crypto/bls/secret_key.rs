@@ -12,7 +12,7 @@ pub struct SecretKey(pub(super) Scalar);
 
 impl SecretKey {
     pub fn random() -> Self {
-        let rng = &mut OsRng::default();
+        let rng = &mut OsRng;
         Self(Scalar::random(rng))
     }
 
@@ -16,8 +16,14 @@ impl Signature {
                 expected: SIGNATURE_KEY_SIZE,
                 found: data.len(),
             })?;
-        let key_opt = G1Affine::from_compressed(bytes);
-        Ok(Self(G1Projective::from(&key_opt.unwrap())))
+        let key_opt: Option<G1Affine> = G1Affine::from_compressed(bytes).into();
+        let key = key_opt.ok_or_else(|| Error::DecodeError("invalid signature".to_string()))?;
+        Ok(Self(G1Projective::from(&key)))
+    }
+
+    /// Aggregates the signatures into one signature.
+    pub fn aggregate(sigs: &[Signature]) -> Self {
+        Self(sigs.iter().map(|sig| sig.0).sum())
     }
 
     pub fn to_fixed_bytes(&self) -> [u8; SIGNATURE_KEY_SIZE] {
@@ -9,7 +9,7 @@ pub trait Signable {
 
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Signer {
     secret: SecretKey,
     public: PublicKey,
@@ -33,6 +33,10 @@ impl Signer {
         self.address.clone()
     }
 
+    pub fn sign_data(&self, data: &[u8]) -> Signature {
+        self.secret.sign(data)
+    }
+
     pub fn sign(&self, signable: &mut dyn Signable) {
         let sb = signable.sign_bytes();
         let sig = self.secret.sign(&sb);
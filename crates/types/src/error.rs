@@ -9,6 +9,18 @@ pub enum Error {
     EncodeError(String),
     #[error("Invalid data length")]
     InvalidLength { expected: usize, found: usize },
+    #[error("Invalid stamp: {0}")]
+    InvalidStamp(String),
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("Invalid vote: {0}")]
+    InvalidVote(String),
+    #[error("Invalid proposal: {0}")]
+    InvalidProposal(String),
+    #[error("Invalid evidence: {0}")]
+    InvalidEvidence(String),
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
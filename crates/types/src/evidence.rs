@@ -0,0 +1,191 @@
+use crate::address::Address;
+use crate::crypto::public_key::PublicKey;
+use crate::error::{Error, Result};
+use crate::hash::Hash32;
+use crate::proposal::Proposal;
+use crate::vote::Vote;
+use minicbor::{Decode, Encode};
+
+/// Proof that a validator signed two conflicting messages at the same height and round.
+/// It is CBOR encoded, so it can be carried by a slashing payload.
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum Evidence {
+    #[n(1)]
+    DuplicateVote {
+        #[n(0)]
+        first: Vote,
+        #[n(1)]
+        second: Vote,
+    },
+    #[n(2)]
+    DuplicateProposal {
+        #[n(0)]
+        first: Box<Proposal>,
+        #[n(1)]
+        second: Box<Proposal>,
+    },
+}
+
+impl Evidence {
+    pub fn height(&self) -> i32 {
+        match self {
+            Evidence::DuplicateVote { first, .. } => first.height(),
+            Evidence::DuplicateProposal { first, .. } => first.height(),
+        }
+    }
+
+    pub fn round(&self) -> i32 {
+        match self {
+            Evidence::DuplicateVote { first, .. } => first.round(),
+            Evidence::DuplicateProposal { first, .. } => first.round(),
+        }
+    }
+
+    /// Returns the address of the validator that signed both messages.
+    pub fn offender(&self) -> &Address {
+        match self {
+            Evidence::DuplicateVote { first, .. } => first.signer(),
+            Evidence::DuplicateProposal { first, .. } => first.block().header().proposer_address(),
+        }
+    }
+
+    /// Returns the hash of the evidence. The order of the two messages doesn't change it.
+    pub fn hash(&self) -> Result<Hash32> {
+        Ok(Hash32::calculate(&self.sorted()?.to_bytes()?))
+    }
+
+    /// Returns the evidence with its messages sorted by their block hashes.
+    fn sorted(&self) -> Result<Evidence> {
+        let swap = match self {
+            Evidence::DuplicateVote { first, second } => {
+                first.block_hash().as_bytes() > second.block_hash().as_bytes()
+            }
+            Evidence::DuplicateProposal { first, second } => {
+                first.block().hash()?.as_bytes() > second.block().hash()?.as_bytes()
+            }
+        };
+        let evidence = match self.clone() {
+            Evidence::DuplicateVote { first, second } if swap => Evidence::DuplicateVote {
+                first: second,
+                second: first,
+            },
+            Evidence::DuplicateProposal { first, second } if swap => {
+                Evidence::DuplicateProposal {
+                    first: second,
+                    second: first,
+                }
+            }
+            evidence => evidence,
+        };
+        Ok(evidence)
+    }
+
+    /// Checks that both messages are signed by the offender and they conflict.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        self.basic_check()?;
+        match self {
+            Evidence::DuplicateVote { first, second } => {
+                first.verify(public_key)?;
+                second.verify(public_key)?;
+            }
+            Evidence::DuplicateProposal { first, second } => {
+                first.verify(public_key)?;
+                second.verify(public_key)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn basic_check(&self) -> Result<()> {
+        match self {
+            Evidence::DuplicateVote { first, second } => {
+                first.basic_check()?;
+                second.basic_check()?;
+                if first.vote_type() != second.vote_type()
+                    || first.height() != second.height()
+                    || first.round() != second.round()
+                    || first.signer() != second.signer()
+                {
+                    return Err(Error::InvalidEvidence(
+                        "votes are not for the same step".to_string(),
+                    ));
+                }
+                if first.block_hash() == second.block_hash() {
+                    return Err(Error::InvalidEvidence("votes don't conflict".to_string()));
+                }
+            }
+            Evidence::DuplicateProposal { first, second } => {
+                first.basic_check()?;
+                second.basic_check()?;
+                if first.height() != second.height()
+                    || first.round() != second.round()
+                    || first.block().header().proposer_address()
+                        != second.block().header().proposer_address()
+                {
+                    return Err(Error::InvalidEvidence(
+                        "proposals are not for the same round".to_string(),
+                    ));
+                }
+                if first.block().hash()? == second.block().hash()? {
+                    return Err(Error::InvalidEvidence(
+                        "proposals don't conflict".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    crate::impl_from_to_bytes!(Evidence);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::bls::secret_key::SecretKey;
+    use crate::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use crate::crypto::signer::Signer;
+    use crate::vote::VoteType;
+
+    fn vote(signer: &Signer, data: &[u8]) -> Vote {
+        let mut vote = Vote::new(
+            VoteType::Prepare,
+            1,
+            0,
+            Hash32::calculate(data),
+            signer.address(),
+        );
+        signer.sign(&mut vote);
+        vote
+    }
+
+    #[test]
+    fn test_duplicate_vote() {
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let evidence = Evidence::DuplicateVote {
+            first: vote(&signer, b"block1"),
+            second: vote(&signer, b"block2"),
+        };
+        assert!(evidence.verify(&signer.public_key()).is_ok());
+        assert_eq!(evidence.offender(), &signer.address());
+
+        let decoded = Evidence::from_bytes(&evidence.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.hash().unwrap(), evidence.hash().unwrap());
+
+        // The same votes in the other order are the same evidence
+        let swapped = Evidence::DuplicateVote {
+            first: vote(&signer, b"block2"),
+            second: vote(&signer, b"block1"),
+        };
+        assert_eq!(swapped.hash().unwrap(), evidence.hash().unwrap());
+
+        let other = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        assert!(evidence.verify(&other.public_key()).is_err());
+
+        let evidence = Evidence::DuplicateVote {
+            first: vote(&signer, b"block1"),
+            second: vote(&signer, b"block1"),
+        };
+        assert!(evidence.basic_check().is_err());
+    }
+}
@@ -3,10 +3,13 @@ use blake2b_simd::Params;
 
 const HASH32_SIZE: usize = 32;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Hash32([u8; HASH32_SIZE]);
 
 impl Hash32 {
+    /// The undefined hash, all bytes are zero.
+    pub const UNDEF: Hash32 = Hash32([0; HASH32_SIZE]);
+
     pub fn calculate(data: &[u8]) -> Self {
         let digest = Params::new()
             .hash_length(32)
@@ -28,9 +31,14 @@ impl Hash32 {
     pub fn as_bytes(&self) -> &[u8; HASH32_SIZE] {
         &self.0
     }
+
+    pub fn is_undef(&self) -> bool {
+        self == &Self::UNDEF
+    }
 }
 
 crate::impl_cbor!(Hash32);
+crate::impl_serde_hex!(Hash32);
 
 #[cfg(test)]
 mod tests {
@@ -41,6 +49,15 @@ mod tests {
         assert!(Hash32::from_bytes(&[]).is_err());
     }
 
+    #[test]
+    fn test_serde() {
+        let hash = Hash32::calculate("pactus".as_bytes());
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex::encode(hash.as_bytes())));
+        assert_eq!(serde_json::from_str::<Hash32>(&json).unwrap(), hash);
+        assert!(serde_json::from_str::<Hash32>("\"0102\"").is_err());
+    }
+
     #[test]
     fn test_calc() {
         let buf = hex::decode("12b38977f2d67f06f0c0cd54aaf7324cf4fee184398ea33d295e8d1543c2ee1a")
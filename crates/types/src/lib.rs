@@ -4,12 +4,15 @@ pub mod block;
 pub mod certificate;
 pub mod crypto;
 pub mod error;
+pub mod evidence;
 pub mod hash;
 pub mod params;
+pub mod proposal;
 pub mod sortition;
 pub mod stamp;
 pub mod transaction;
 pub mod validator;
+pub mod vote;
 
 macro_rules! impl_from_to_bytes {
     ($ty:ty) => {
@@ -50,5 +53,31 @@ macro_rules! impl_cbor {
     };
 }
 
+/// Serializes the type as a hex string.
+macro_rules! impl_serde_hex {
+    ($ty:ty) => {
+        impl serde::Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(&hex::encode(self.as_bytes()))
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<$ty, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                let data = hex::decode(s).map_err(serde::de::Error::custom)?;
+                <$ty>::from_bytes(&data).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 pub(crate) use impl_cbor;
+pub(crate) use impl_serde_hex;
 pub(crate) use impl_from_to_bytes;
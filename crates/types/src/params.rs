@@ -1,27 +1,27 @@
 use minicbor::{Decode, Encode};
 
 #[derive(Encode, Decode)]
-struct Params {
+pub struct Params {
     #[n(1)]
-    block_version: i32,
+    pub block_version: i32,
     #[n(2)]
-    block_time_in_second: i32,
+    pub block_time_in_second: i32,
     #[n(3)]
-    committee_size: i32,
+    pub committee_size: i32,
     #[n(4)]
-    block_reward: i64,
+    pub block_reward: i64,
     #[n(5)]
-    transaction_to_live_interval: i32,
+    pub transaction_to_live_interval: i32,
     #[n(6)]
-    unbond_interval: i32,
+    pub unbond_interval: i32,
     #[n(7)]
-    maximum_transaction_per_block: i32,
+    pub maximum_transaction_per_block: i32,
     #[n(8)]
-    maximum_memo_length: i32,
+    pub maximum_memo_length: i32,
     #[n(9)]
-    fee_fraction: f64,
+    pub fee_fraction: f64,
     #[n(10)]
-    minimum_fee: i64,
+    pub minimum_fee: i64,
 }
 
 impl Default for Params {
@@ -0,0 +1,105 @@
+use crate::block::Block;
+use crate::crypto::bls::signature::Signature;
+use crate::crypto::public_key::PublicKey;
+use crate::crypto::signature::Signature as CryptoSignature;
+use crate::crypto::signer::Signable;
+use crate::error::{Error, Result};
+use crate::hash::Hash32;
+use minicbor::{bytes::ByteVec, Decode, Encode};
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[cbor(map)]
+pub struct Proposal {
+    #[n(1)]
+    height: i32,
+    #[n(2)]
+    round: i32,
+    #[n(3)]
+    block: Block,
+    #[n(4)]
+    signature_data: Option<ByteVec>,
+}
+
+impl Proposal {
+    pub fn new(height: i32, round: i32, block: Block) -> Self {
+        Proposal {
+            height,
+            round,
+            block,
+            signature_data: None,
+        }
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn round(&self) -> i32 {
+        self.round
+    }
+
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    pub fn signature(&self) -> Result<Signature> {
+        match &self.signature_data {
+            Some(data) => Signature::from_bytes(data),
+            None => Err(Error::DecodeError("proposal is not signed".to_string())),
+        }
+    }
+
+    pub fn hash(&self) -> Result<Hash32> {
+        Ok(Hash32::calculate(&self.to_bytes()?))
+    }
+
+    /// Checks the signature of the proposal against the proposer's public key.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        if &public_key.address() != self.block.header().proposer_address() {
+            return Err(Error::InvalidProposal("invalid proposer".to_string()));
+        }
+        let sig = CryptoSignature::BLS(self.signature()?);
+        if !public_key.verify(&sig, &self.sign_bytes()) {
+            return Err(Error::InvalidProposal("invalid signature".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn basic_check(&self) -> Result<()> {
+        if self.height <= 0 {
+            return Err(Error::InvalidProposal(format!(
+                "invalid height: {}",
+                self.height
+            )));
+        }
+        if self.round < 0 {
+            return Err(Error::InvalidProposal(format!(
+                "invalid round: {}",
+                self.round
+            )));
+        }
+        self.signature()?;
+        Ok(())
+    }
+
+    crate::impl_from_to_bytes!(Proposal);
+}
+
+impl Signable for Proposal {
+    /// Sign bytes follow the Go implementation: block hash, height and round.
+    fn sign_bytes(&self) -> Vec<u8> {
+        let block_hash = self.block.hash().unwrap_or(Hash32::UNDEF);
+        let mut sb = block_hash.as_bytes().to_vec();
+        sb.extend_from_slice(&self.height.to_le_bytes());
+        sb.extend_from_slice(&(self.round as i16).to_le_bytes());
+        sb
+    }
+
+    fn set_public_key(&mut self, _pk: PublicKey) {
+        // The proposer address is part of the block
+    }
+
+    fn set_signature(&mut self, sig: CryptoSignature) {
+        self.signature_data = Some(ByteVec::from(sig.to_bytes()));
+    }
+}
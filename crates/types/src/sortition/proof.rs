@@ -1,6 +1,7 @@
 use crate::error::{Error, Result};
 
 const PROOF_SIZE: usize = 48;
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Proof([u8; PROOF_SIZE]);
 
 impl Proof {
@@ -1,9 +1,19 @@
+use crate::crypto::signer::Signer;
 use crate::error::{Error, Result};
 
 const SEED_SIZE: usize = 48;
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Seed([u8; SEED_SIZE]);
 
 impl Seed {
+    pub const UNDEF: Seed = Seed([0; SEED_SIZE]);
+
+    /// Generates the next seed by signing this seed.
+    pub fn generate(&self, signer: &Signer) -> Self {
+        let sig = signer.sign_data(&self.0);
+        Seed::from_bytes(&sig.to_bytes()).unwrap()
+    }
+
     pub fn from_bytes(buf: &[u8]) -> Result<Self> {
         let data = buf.try_into().map_err(|_| Error::InvalidLength {
             expected: SEED_SIZE,
@@ -18,3 +28,4 @@ impl Seed {
 }
 
 crate::impl_cbor!(Seed);
+crate::impl_serde_hex!(Seed);
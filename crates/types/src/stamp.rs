@@ -1,11 +1,20 @@
 use crate::error::{Error, Result};
+use crate::hash::Hash32;
+use std::collections::{HashMap, VecDeque};
 
 const STAMP_SIZE: usize = 4;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Stamp([u8; STAMP_SIZE]);
 
 impl Stamp {
+    /// Returns the stamp of a block, which is the first 4 bytes of its hash.
+    pub fn from_block_hash(hash: &Hash32) -> Self {
+        let mut data = [0; STAMP_SIZE];
+        data.copy_from_slice(&hash.as_bytes()[..STAMP_SIZE]);
+        Self(data)
+    }
+
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
         let bytes: &[u8; STAMP_SIZE] = data.try_into().map_err(|_| Error::InvalidLength {
             expected: STAMP_SIZE,
@@ -20,3 +29,102 @@ impl Stamp {
 }
 
 crate::impl_cbor!(Stamp);
+crate::impl_serde_hex!(Stamp);
+
+/// Keeps the stamps of the last `interval` blocks, mapped to their heights.
+///
+/// A transaction is valid only if its stamp refers to one of these blocks.
+#[derive(Debug)]
+pub struct StampIndex {
+    interval: i32,
+    heights: HashMap<Stamp, i32>,
+    stamps: VecDeque<(i32, Stamp)>,
+}
+
+impl StampIndex {
+    pub fn new(interval: i32) -> Self {
+        Self {
+            interval,
+            heights: HashMap::new(),
+            stamps: VecDeque::new(),
+        }
+    }
+
+    /// Adds the hash of a newly committed block.
+    /// Stamps older than the interval are removed from the index.
+    pub fn add_block_hash(&mut self, height: i32, hash: &Hash32) {
+        let stamp = Stamp::from_block_hash(hash);
+        self.heights.insert(stamp.clone(), height);
+        self.stamps.push_back((height, stamp));
+
+        while let Some((front_height, _)) = self.stamps.front() {
+            if height - front_height < self.interval {
+                break;
+            }
+            let (front_height, front_stamp) = self.stamps.pop_front().unwrap();
+            // The same stamp might be re-inserted by a newer block.
+            if self.heights.get(&front_stamp) == Some(&front_height) {
+                self.heights.remove(&front_stamp);
+            }
+        }
+    }
+
+    /// Returns the height of the block that the stamp refers to.
+    pub fn height(&self, stamp: &Stamp) -> Option<i32> {
+        self.heights.get(stamp).copied()
+    }
+
+    /// Returns the stamp of the last block. Wallets should use it when building transactions.
+    pub fn last_stamp(&self) -> Option<&Stamp> {
+        self.stamps.back().map(|(_, stamp)| stamp)
+    }
+
+    /// Checks the stamp against the index and returns the height of its block.
+    /// It fails if the stamp is unknown or it has expired.
+    pub fn check_stamp(&self, stamp: &Stamp) -> Result<i32> {
+        self.height(stamp).ok_or_else(|| {
+            Error::InvalidStamp(format!("stamp {} is unknown or expired", hex::encode(stamp.0)))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.stamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stamps.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_hash(height: i32) -> Hash32 {
+        Hash32::calculate(&height.to_le_bytes())
+    }
+
+    #[test]
+    fn test_from_block_hash() {
+        let hash = Hash32::calculate("pactus".as_bytes());
+        let stamp = Stamp::from_block_hash(&hash);
+        assert_eq!(stamp.as_bytes(), &hash.as_bytes()[..4]);
+    }
+
+    #[test]
+    fn test_stamp_index() {
+        let mut index = StampIndex::new(3);
+        assert!(index.last_stamp().is_none());
+
+        for height in 1..=5 {
+            index.add_block_hash(height, &block_hash(height));
+        }
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.last_stamp(), Some(&Stamp::from_block_hash(&block_hash(5))));
+        assert_eq!(index.check_stamp(&Stamp::from_block_hash(&block_hash(3))).unwrap(), 3);
+        assert_eq!(index.check_stamp(&Stamp::from_block_hash(&block_hash(5))).unwrap(), 5);
+        assert!(index.check_stamp(&Stamp::from_block_hash(&block_hash(2))).is_err());
+        assert!(index.check_stamp(&Stamp::from_bytes(&[1, 2, 3, 4]).unwrap()).is_err());
+    }
+}
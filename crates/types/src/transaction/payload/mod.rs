@@ -6,7 +6,7 @@ use crate::address::Address;
 use crate::error::Result;
 use minicbor::{Decode, Encode};
 
-#[derive(Debug, Decode, Encode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode)]
 #[cbor(index_only)]
 pub enum Type {
     #[n(1)]
@@ -35,11 +35,20 @@ pub enum Type {
 //     }
 // }
 
-pub trait Payload: Debug {
+pub trait Payload: Debug + Send + Sync {
     fn to_bytes(&self) -> Result<Vec<u8>>;
     fn signer(&self) -> &Address;
+    /// Returns the address that receives the value, if any.
+    fn receiver(&self) -> Option<&Address>;
     fn value(&self) -> i64;
     fn payload_type(&self) -> Type;
     fn basic_check(&self) -> Result<()>;
     fn fingerprint(&self) -> String;
+    fn clone_box(&self) -> Box<dyn Payload>;
+}
+
+impl Clone for Box<dyn Payload> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
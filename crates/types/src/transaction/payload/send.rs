@@ -14,6 +14,16 @@ pub struct SendPayload {
     amount: i64,
 }
 
+impl SendPayload {
+    pub fn new(sender: Address, receiver: Address, amount: i64) -> Self {
+        Self {
+            sender,
+            receiver,
+            amount,
+        }
+    }
+}
+
 impl Payload for SendPayload {
     fn to_bytes(&self) -> Result<Vec<u8>> {
         Ok(minicbor::to_vec(self)?)
@@ -21,6 +31,9 @@ impl Payload for SendPayload {
     fn signer(&self) -> &Address {
         &self.sender
     }
+    fn receiver(&self) -> Option<&Address> {
+        Some(&self.receiver)
+    }
     fn value(&self) -> i64 {
         self.amount
     }
@@ -33,4 +46,7 @@ impl Payload for SendPayload {
     fn fingerprint(&self) -> String {
         "".to_string()
     }
+    fn clone_box(&self) -> Box<dyn Payload> {
+        Box::new(self.clone())
+    }
 }
@@ -1,13 +1,15 @@
 use super::payload;
 use crate::crypto::public_key::PublicKey;
 use crate::crypto::signature::Signature;
+use crate::crypto::signer::Signable;
 use crate::crypto::KeyPairType;
 use crate::error::Result;
-use crate::stamp::Stamp;
+use crate::hash::Hash32;
+use crate::stamp::{Stamp, StampIndex};
 use minicbor::bytes::ByteVec;
 use minicbor::{Decode, Encode};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Transaction {
     pub stamp: Stamp,
     pub sequence: i32,
@@ -121,6 +123,11 @@ impl Transaction {
         Ok(minicbor::to_vec(self.to_raw_transaction()?)?)
     }
 
+    /// Returns the transaction ID, which is the hash of the sign bytes.
+    pub fn id(&self) -> Result<Hash32> {
+        Ok(Hash32::calculate(&self.sign_bytes()?))
+    }
+
     fn sign_bytes(&self) -> Result<Vec<u8>> {
         let payload_data = ByteVec::from(self.payload.to_bytes()?);
         let raw = RawTransaction {
@@ -137,6 +144,12 @@ impl Transaction {
         Ok(minicbor::to_vec(raw)?)
     }
 
+    /// Checks that the transaction stamp refers to one of the recent blocks.
+    pub fn check_stamp(&self, index: &StampIndex) -> Result<()> {
+        index.check_stamp(&self.stamp)?;
+        Ok(())
+    }
+
     pub fn check_signature(&self) -> bool {
         self.public_key.as_ref().unwrap().verify(
             self.signature.as_ref().unwrap(),
@@ -145,6 +158,18 @@ impl Transaction {
     }
 }
 
+impl Signable for Transaction {
+    fn sign_bytes(&self) -> Vec<u8> {
+        Transaction::sign_bytes(self).unwrap_or_default()
+    }
+    fn set_public_key(&mut self, pk: PublicKey) {
+        self.public_key = Some(pk);
+    }
+    fn set_signature(&mut self, sig: Signature) {
+        self.signature = Some(sig);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
+use crate::address::Address;
+use crate::crypto::bls::public_key::PublicKey;
+use crate::error::Result;
 use minicbor::{Decode, Encode, bytes::ByteVec};
 
-#[derive(Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 #[cbor(map)]
 pub struct Validator {
     #[n(1)]
@@ -20,6 +23,34 @@ pub struct Validator {
 }
 
 impl Validator {
+    pub fn new(public_key: &PublicKey, number: i32, stake: i64) -> Self {
+        Validator {
+            public_key_data: ByteVec::from(public_key.to_bytes()),
+            number,
+            sequence: 0,
+            stake,
+            last_bonding_height: 0,
+            unbonding_height: 0,
+            last_joined_height: 0,
+        }
+    }
+
+    pub fn public_key(&self) -> Result<PublicKey> {
+        PublicKey::from_bytes(&self.public_key_data)
+    }
+
+    pub fn address(&self) -> Result<Address> {
+        Ok(crate::crypto::public_key::PublicKey::BLS(self.public_key()?).address())
+    }
+
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+
+    pub fn stake(&self) -> i64 {
+        self.stake
+    }
+
     crate::impl_from_to_bytes!(Validator);
 }
 
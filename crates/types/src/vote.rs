@@ -0,0 +1,216 @@
+use crate::address::Address;
+use crate::certificate::Certificate;
+use crate::crypto::bls::signature::Signature;
+use crate::crypto::public_key::PublicKey;
+use crate::crypto::signature::Signature as CryptoSignature;
+use crate::crypto::signer::Signable;
+use crate::error::{Error, Result};
+use crate::hash::Hash32;
+use minicbor::{bytes::ByteVec, Decode, Encode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[cbor(index_only)]
+pub enum VoteType {
+    #[n(1)]
+    Prepare,
+    #[n(2)]
+    Precommit,
+    #[n(3)]
+    ChangeProposer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cbor(map)]
+pub struct Vote {
+    #[n(1)]
+    vote_type: VoteType,
+    #[n(2)]
+    height: i32,
+    #[n(3)]
+    round: i32,
+    #[n(4)]
+    block_hash: Hash32,
+    #[n(5)]
+    signer: Address,
+    #[n(6)]
+    signature_data: Option<ByteVec>,
+}
+
+impl Vote {
+    pub fn new(
+        vote_type: VoteType,
+        height: i32,
+        round: i32,
+        block_hash: Hash32,
+        signer: Address,
+    ) -> Self {
+        Vote {
+            vote_type,
+            height,
+            round,
+            block_hash,
+            signer,
+            signature_data: None,
+        }
+    }
+
+    pub fn vote_type(&self) -> VoteType {
+        self.vote_type
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn round(&self) -> i32 {
+        self.round
+    }
+
+    pub fn block_hash(&self) -> &Hash32 {
+        &self.block_hash
+    }
+
+    pub fn signer(&self) -> &Address {
+        &self.signer
+    }
+
+    pub fn signature(&self) -> Result<Signature> {
+        match &self.signature_data {
+            Some(data) => Signature::from_bytes(data),
+            None => Err(Error::DecodeError("vote is not signed".to_string())),
+        }
+    }
+
+    pub fn hash(&self) -> Result<Hash32> {
+        Ok(Hash32::calculate(&self.to_bytes()?))
+    }
+
+    /// Checks the signature of the vote against the signer's public key.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        if &public_key.address() != self.signer() {
+            return Err(Error::InvalidVote("invalid signer".to_string()));
+        }
+        let sig = CryptoSignature::BLS(self.signature()?);
+        if !public_key.verify(&sig, &self.sign_bytes()) {
+            return Err(Error::InvalidVote("invalid signature".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn basic_check(&self) -> Result<()> {
+        if self.height <= 0 {
+            return Err(Error::InvalidVote(format!(
+                "invalid height: {}",
+                self.height
+            )));
+        }
+        if self.round < 0 {
+            return Err(Error::InvalidVote(format!("invalid round: {}", self.round)));
+        }
+        self.signature()?;
+        Ok(())
+    }
+
+    crate::impl_from_to_bytes!(Vote);
+}
+
+impl Signable for Vote {
+    /// Sign bytes follow the Go implementation: the certificate sign bytes,
+    /// followed by a suffix that depends on the vote type.
+    /// They don't include the height, so a change-proposer vote, whose block hash is
+    /// undefined, is valid for the same round at any height. The receiver must check
+    /// the height of the vote before it accepts it.
+    fn sign_bytes(&self) -> Vec<u8> {
+        let mut sb = Certificate::sign_bytes(&self.block_hash, self.round);
+        match self.vote_type {
+            VoteType::Prepare => sb.extend_from_slice(b"prepare"),
+            VoteType::Precommit => {}
+            VoteType::ChangeProposer => sb.extend_from_slice(b"change-proposer"),
+        }
+        sb
+    }
+
+    fn set_public_key(&mut self, _pk: PublicKey) {
+        // The signer address is part of the vote
+    }
+
+    fn set_signature(&mut self, sig: CryptoSignature) {
+        self.signature_data = Some(ByteVec::from(sig.to_bytes()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::bls::secret_key::SecretKey;
+    use crate::crypto::secret_key::SecretKey as CryptoSecretKey;
+    use crate::crypto::signer::Signer;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        let hash = Hash32::calculate(b"block");
+        let mut vote = Vote::new(VoteType::Prepare, 1, 0, hash, signer.address());
+        assert!(vote.basic_check().is_err());
+
+        signer.sign(&mut vote);
+        assert!(vote.basic_check().is_ok());
+        assert!(vote.verify(&signer.public_key()).is_ok());
+
+        let other = Signer::new(CryptoSecretKey::BLS(SecretKey::random()));
+        assert!(vote.verify(&other.public_key()).is_err());
+
+        let decoded = Vote::from_bytes(&vote.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, vote);
+    }
+
+    #[test]
+    fn test_sign_bytes() {
+        let hash = Hash32::calculate(b"block");
+        let addr = Signer::new(CryptoSecretKey::BLS(SecretKey::random())).address();
+
+        let mut cert_sb = hash.as_bytes().to_vec();
+        cert_sb.extend_from_slice(&2i16.to_le_bytes());
+
+        let vote = Vote::new(VoteType::Precommit, 5, 2, hash.clone(), addr.clone());
+        assert_eq!(vote.sign_bytes(), cert_sb);
+
+        let vote = Vote::new(VoteType::Prepare, 5, 2, hash.clone(), addr.clone());
+        assert_eq!(vote.sign_bytes(), [cert_sb.as_slice(), b"prepare"].concat());
+
+        let vote = Vote::new(VoteType::ChangeProposer, 5, 2, hash, addr);
+        assert_eq!(
+            vote.sign_bytes(),
+            [cert_sb.as_slice(), b"change-proposer"].concat()
+        );
+    }
+
+    #[test]
+    fn test_sign_bytes_vectors() {
+        // The sign bytes of the Go implementation for the same votes
+        let addr = Signer::new(CryptoSecretKey::BLS(SecretKey::random())).address();
+        let vectors = [
+            (
+                VoteType::Prepare,
+                "00000000000000000000000000000000000000000000000000000000000000000300\
+                 70726570617265",
+            ),
+            (
+                VoteType::Precommit,
+                "00000000000000000000000000000000000000000000000000000000000000000300",
+            ),
+            (
+                VoteType::ChangeProposer,
+                "00000000000000000000000000000000000000000000000000000000000000000300\
+                 6368616e67652d70726f706f736572",
+            ),
+        ];
+        for (vote_type, expected) in vectors {
+            // The height is not part of the sign bytes
+            for height in [1, 1000] {
+                let vote = Vote::new(vote_type, height, 3, Hash32::UNDEF, addr.clone());
+                assert_eq!(hex::encode(vote.sign_bytes()), expected);
+            }
+        }
+    }
+}